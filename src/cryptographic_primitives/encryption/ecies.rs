@@ -0,0 +1,409 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! ECIES hybrid encryption to a public key `Y = y G` over any [Curve]
+//!
+//! Encryption samples ephemeral key `r`, derives symmetric key from shared point `r Y` by
+//! HKDF (instantiated with HMAC over hash `H`), and encrypts the plaintext by ChaCha20-Poly1305.
+//! Ciphertext consists of ephemeral public key `R = r G` and AEAD output. Decryption derives the
+//! same key from `y R`.
+//!
+//! Key derivation binds both `R` and `Y`, and every message uses a fresh ephemeral key, so the
+//! symmetric key is never reused and AEAD nonce is fixed to zero.
+//!
+//! ## Serialized format
+//! [EciesCiphertext::to_bytes] outputs `R || c || tag`, where `R` is compressed ephemeral point,
+//! `c` is encrypted plaintext of the same length as plaintext, and `tag` is 16 bytes
+//! authentication tag.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::encryption::ecies;
+//! use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+//! use curv::elliptic::curves::{Point, Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! let secret_keys: Vec<_> = (0..3).map(|_| Scalar::<Secp256k1>::random()).collect();
+//! let public_keys: Vec<_> = secret_keys.iter().map(|y| Point::generator() * y).collect();
+//!
+//! // Dealer encrypts every share to its recipient, so shares can be sent via untrusted relay
+//! let (vss, shares) = VerifiableSS::<_, Sha256>::share(1, 3, &Scalar::random());
+//! let encrypted = ecies::encrypt_shares::<_, Sha256>(&shares, &public_keys).unwrap();
+//!
+//! // Party 2 decrypts and validates its share
+//! let share = ecies::decrypt_share::<_, Sha256>(&secret_keys[1], 2, &encrypted[1]).unwrap();
+//! assert!(vss.validate_share(&share, 2).is_ok());
+//! ```
+
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use digest::{BlockInput, FixedOutput, Reset, Update};
+use hmac::{Hmac, Mac, NewMac};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use typenum::Unsigned;
+
+use crate::elliptic::curves::{Curve, ECPoint, Point, Scalar};
+
+const KDF_INFO: &[u8] = b"curv ECIES ChaCha20-Poly1305";
+const KEY_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+
+/// ECIES ciphertext: ephemeral public key and AEAD output (encrypted plaintext and tag)
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct EciesCiphertext<E: Curve> {
+    pub ephemeral_key: Point<E>,
+    #[serde(with = "serde_bytes")]
+    pub ciphertext: Vec<u8>,
+}
+
+impl<E: Curve> EciesCiphertext<E> {
+    /// Serializes ciphertext as `R || c || tag`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.ephemeral_key.to_bytes(true).to_vec();
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    /// Parses ciphertext serialized by [to_bytes](Self::to_bytes)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EciesError> {
+        let point_len = <E::Point as ECPoint>::CompressedPointLength::USIZE;
+        if bytes.len() < point_len + TAG_LEN {
+            return Err(EciesError::MalformedCiphertext);
+        }
+        let (point, ciphertext) = bytes.split_at(point_len);
+        let ephemeral_key =
+            Point::from_bytes(point).map_err(|_| EciesError::MalformedCiphertext)?;
+        if ephemeral_key.is_zero() {
+            return Err(EciesError::MalformedCiphertext);
+        }
+        Ok(Self {
+            ephemeral_key,
+            ciphertext: ciphertext.to_vec(),
+        })
+    }
+}
+
+/// Encrypts `plaintext` to `recipient` public key
+///
+/// Associated data `aad` isn't encrypted, but it's authenticated: decryption succeeds only if the
+/// same `aad` is provided.
+pub fn encrypt<E, H>(
+    recipient: &Point<E>,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<EciesCiphertext<E>, EciesError>
+where
+    E: Curve,
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    if recipient.is_zero() {
+        return Err(EciesError::InvalidPublicKey);
+    }
+    let r = Scalar::<E>::random();
+    let ephemeral_key = Point::generator() * &r;
+    let shared_point = recipient * &r;
+    let cipher = derive_cipher::<E, H>(&shared_point, &ephemeral_key, recipient);
+    let ciphertext = cipher
+        .encrypt(
+            &Nonce::default(),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| EciesError::EncryptionFailed)?;
+    Ok(EciesCiphertext {
+        ephemeral_key,
+        ciphertext,
+    })
+}
+
+/// Decrypts ciphertext with recipient's `secret_key`
+///
+/// Returns error if ciphertext wasn't produced for this key or was modified, or if `aad` doesn't
+/// match the one used at encryption.
+pub fn decrypt<E, H>(
+    secret_key: &Scalar<E>,
+    ciphertext: &EciesCiphertext<E>,
+    aad: &[u8],
+) -> Result<Vec<u8>, EciesError>
+where
+    E: Curve,
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    if ciphertext.ephemeral_key.is_zero() {
+        return Err(EciesError::MalformedCiphertext);
+    }
+    let public_key = Point::generator() * secret_key;
+    if public_key.is_zero() {
+        return Err(EciesError::InvalidPublicKey);
+    }
+    let shared_point = &ciphertext.ephemeral_key * secret_key;
+    let cipher = derive_cipher::<E, H>(&shared_point, &ciphertext.ephemeral_key, &public_key);
+    cipher
+        .decrypt(
+            &Nonce::default(),
+            Payload {
+                msg: &ciphertext.ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| EciesError::DecryptionFailed)
+}
+
+/// Encrypts a scalar to `recipient` public key
+pub fn encrypt_scalar<E, H>(
+    recipient: &Point<E>,
+    scalar: &Scalar<E>,
+    aad: &[u8],
+) -> Result<EciesCiphertext<E>, EciesError>
+where
+    E: Curve,
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    encrypt::<E, H>(recipient, &scalar.to_bytes(), aad)
+}
+
+/// Decrypts a scalar encrypted by [encrypt_scalar]
+pub fn decrypt_scalar<E, H>(
+    secret_key: &Scalar<E>,
+    ciphertext: &EciesCiphertext<E>,
+    aad: &[u8],
+) -> Result<Scalar<E>, EciesError>
+where
+    E: Curve,
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    let bytes = decrypt::<E, H>(secret_key, ciphertext, aad)?;
+    Scalar::from_bytes(&bytes).map_err(|_| EciesError::MalformedCiphertext)
+}
+
+/// Encrypts every secret share to its recipient: `shares[i]` is encrypted to `recipients[i]`
+///
+/// Index of the share (`i+1`) is authenticated, so a relay can't swap ciphertexts of different
+/// shares. `shares` can be [SecretShares](crate::cryptographic_primitives::secret_sharing::feldman_vss::SecretShares)
+/// returned by VSS.
+///
+/// ## Panics
+/// Panics if `shares` and `recipients` have different lengths, or there are more than
+/// `u16::MAX` shares.
+pub fn encrypt_shares<E, H>(
+    shares: &[Scalar<E>],
+    recipients: &[Point<E>],
+) -> Result<Vec<EciesCiphertext<E>>, EciesError>
+where
+    E: Curve,
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    assert_eq!(
+        shares.len(),
+        recipients.len(),
+        "shares and recipients must have the same length"
+    );
+    assert!(shares.len() <= usize::from(u16::MAX), "too many shares");
+    (1u16..)
+        .zip(shares.iter().zip(recipients))
+        .map(|(i, (share, recipient))| encrypt_scalar::<E, H>(recipient, share, &i.to_be_bytes()))
+        .collect()
+}
+
+/// Decrypts share with `index` (`1..=n`) encrypted by [encrypt_shares]
+pub fn decrypt_share<E, H>(
+    secret_key: &Scalar<E>,
+    index: u16,
+    ciphertext: &EciesCiphertext<E>,
+) -> Result<Scalar<E>, EciesError>
+where
+    E: Curve,
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    decrypt_scalar::<E, H>(secret_key, ciphertext, &index.to_be_bytes())
+}
+
+/// Derives AEAD key from shared point by HKDF (RFC 5869)
+fn derive_cipher<E, H>(
+    shared_point: &Point<E>,
+    ephemeral_key: &Point<E>,
+    recipient: &Point<E>,
+) -> ChaCha20Poly1305
+where
+    E: Curve,
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    // Zero salt, info = KDF_INFO || R || Y
+    let okm = hkdf::<H>(
+        &shared_point.to_bytes(true),
+        &[
+            KDF_INFO,
+            &ephemeral_key.to_bytes(true),
+            &recipient.to_bytes(true),
+        ],
+    );
+
+    ChaCha20Poly1305::new(Key::from_slice(&okm))
+}
+
+/// HKDF-Extract with zero salt followed by HKDF-Expand to [KEY_LEN] bytes (RFC 5869)
+fn hkdf<H>(ikm: &[u8], info: &[&[u8]]) -> [u8; KEY_LEN]
+where
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    // PRK = HMAC(salt, IKM), absent salt is a string of HashLen zeros
+    let salt = vec![0u8; H::OutputSize::USIZE];
+    let mut extract = Hmac::<H>::new_from_slice(&salt).expect("HMAC must take a key of any length");
+    extract.update(ikm);
+    let prk = extract.finalize().into_bytes();
+
+    // T(i) = HMAC(PRK, T(i-1) || info || i), OKM is the first KEY_LEN bytes of T(1) || T(2) || ...
+    let mut okm = [0u8; KEY_LEN];
+    let mut previous = vec![];
+    for (i, chunk) in (1u8..).zip(okm.chunks_mut(H::OutputSize::USIZE)) {
+        let mut expand =
+            Hmac::<H>::new_from_slice(&prk).expect("HMAC must take a key of any length");
+        expand.update(&previous);
+        for part in info {
+            expand.update(part);
+        }
+        expand.update(&[i]);
+        previous = expand.finalize().into_bytes().to_vec();
+        chunk.copy_from_slice(&previous[..chunk.len()]);
+    }
+    okm
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum EciesError {
+    #[error("public key is zero")]
+    InvalidPublicKey,
+    #[error("ciphertext is malformed")]
+    MalformedCiphertext,
+    #[error("encryption failed")]
+    EncryptionFailed,
+    #[error("decryption failed: ciphertext or associated data is not authentic")]
+    DecryptionFailed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use crate::elliptic::curves::Secp256k1;
+    use crate::test_for_all_curves;
+    use sha2::{Sha256, Sha512};
+
+    test_for_all_curves!(encrypt_decrypt_bytes);
+    fn encrypt_decrypt_bytes<E: Curve>() {
+        let secret_key = Scalar::<E>::random();
+        let public_key = Point::generator() * &secret_key;
+
+        for plaintext in [&b""[..], b"short message", &[0xAB; 1000]] {
+            let ciphertext = encrypt::<E, Sha256>(&public_key, plaintext, b"aad").unwrap();
+            assert_eq!(ciphertext.ciphertext.len(), plaintext.len() + TAG_LEN);
+            assert_eq!(
+                decrypt::<E, Sha256>(&secret_key, &ciphertext, b"aad"),
+                Ok(plaintext.to_vec())
+            );
+
+            let parsed = EciesCiphertext::from_bytes(&ciphertext.to_bytes()).unwrap();
+            assert_eq!(parsed, ciphertext);
+
+            assert_eq!(
+                decrypt::<E, Sha256>(&secret_key, &ciphertext, b"other aad"),
+                Err(EciesError::DecryptionFailed)
+            );
+            assert_eq!(
+                decrypt::<E, Sha256>(&Scalar::random(), &ciphertext, b"aad"),
+                Err(EciesError::DecryptionFailed)
+            );
+            assert_eq!(
+                decrypt::<E, Sha512>(&secret_key, &ciphertext, b"aad"),
+                Err(EciesError::DecryptionFailed)
+            );
+        }
+    }
+
+    test_for_all_curves!(encrypt_decrypt_shares);
+    fn encrypt_decrypt_shares<E: Curve>() {
+        let secret_keys: Vec<Scalar<E>> = (0..4).map(|_| Scalar::random()).collect();
+        let public_keys: Vec<Point<E>> =
+            secret_keys.iter().map(|y| Point::generator() * y).collect();
+        let (vss, shares) = VerifiableSS::<E, Sha256>::share(2, 4, &Scalar::random());
+
+        let encrypted = encrypt_shares::<E, Sha256>(&shares, &public_keys).unwrap();
+        for (i, (secret_key, ciphertext)) in (1..).zip(secret_keys.iter().zip(&encrypted)) {
+            let share = decrypt_share::<E, Sha256>(secret_key, i, ciphertext).unwrap();
+            assert!(vss.validate_share(&share, i).is_ok());
+        }
+
+        // ciphertext of share 1 can't be passed off as share 2, even if the key matches
+        let swapped = encrypt_shares::<E, Sha256>(&shares[..1], &public_keys[1..2]).unwrap();
+        assert_eq!(
+            decrypt_share::<E, Sha256>(&secret_keys[1], 2, &swapped[0]),
+            Err(EciesError::DecryptionFailed)
+        );
+    }
+
+    #[test]
+    fn tampered_ciphertext_is_rejected() {
+        let secret_key = Scalar::<Secp256k1>::random();
+        let public_key = Point::generator() * &secret_key;
+        let ciphertext = encrypt::<_, Sha256>(&public_key, b"message", b"").unwrap();
+        let bytes = ciphertext.to_bytes();
+
+        for i in 0..bytes.len() {
+            let mut tampered = bytes.clone();
+            tampered[i] ^= 1;
+            let result = EciesCiphertext::<Secp256k1>::from_bytes(&tampered)
+                .and_then(|c| decrypt::<_, Sha256>(&secret_key, &c, b""));
+            assert!(result.is_err());
+        }
+        assert_eq!(
+            EciesCiphertext::<Secp256k1>::from_bytes(&bytes[..40]),
+            Err(EciesError::MalformedCiphertext)
+        );
+        assert_eq!(
+            encrypt::<_, Sha256>(&Point::<Secp256k1>::zero(), b"message", b"").err(),
+            Some(EciesError::InvalidPublicKey)
+        );
+    }
+
+    // RFC 5869 test case 3 (zero-length salt, empty info), truncated to KEY_LEN bytes
+    #[test]
+    fn hkdf_rfc5869_test_vector() {
+        assert_eq!(
+            hex::encode(hkdf::<Sha256>(&[0x0b; 22], &[])),
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d"
+        );
+    }
+
+    // Ciphertext is produced by independent implementation using HKDF-SHA256 and
+    // ChaCha20-Poly1305 from pyca/cryptography, with secret key `y = 0x1111..11` and ephemeral key
+    // `r = 0x2222..22`
+    #[test]
+    fn secp256k1_test_vector() {
+        let secret_key =
+            Scalar::<Secp256k1>::from_bytes(&[0x11; 32]).expect("secret key is less than q");
+        let ciphertext = EciesCiphertext::<Secp256k1>::from_bytes(
+            &hex::decode(
+                "02466d7fcae563e5cb09a0d1870bb580344804617879a14949cf22285f1bae3f27\
+                 6e94d86a4e18c2364346151b2a9e58fab44d6377bde15bd906fd9bf2bb7d64e0a7c24e99b0c4",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            ciphertext.ephemeral_key,
+            Point::generator() * Scalar::from_bytes(&[0x22; 32]).unwrap()
+        );
+        assert_eq!(
+            decrypt::<_, Sha256>(&secret_key, &ciphertext, b"aad"),
+            Ok(b"curv ECIES test vector".to_vec())
+        );
+    }
+}
@@ -0,0 +1,433 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! ElGamal encryption over any [Curve]
+//!
+//! Public key is `Y = x G`. Point `M` is encrypted as `(c1, c2) = (r G, M + r Y)` with random `r`,
+//! and decrypted as `M = c2 - x c1`.
+//!
+//! Exponential ElGamal encrypts scalar `m` as point `m G`. It's additively homomorphic: sum of
+//! ciphertexts encrypts sum of scalars. Decryption requires computing discrete logarithm, which is
+//! only feasible for small `m` (see [DiscreteLogTable]).
+//!
+//! Ciphertexts match [HomoElGamalStatement] (`D = c2`, `E = c1`, `H = G`) and
+//! [HomoElGamalDlogStatement], so one can prove that a ciphertext encrypts a known scalar.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::encryption::elgamal::{DiscreteLogTable, SecretKey};
+//! use curv::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_enc::HomoELGamalProof;
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! let secret_key = SecretKey::<Secp256k1>::random();
+//! let public_key = secret_key.public_key();
+//!
+//! // Encrypt a vote and prove that the ciphertext is well-formed
+//! let (vote, witness) = public_key.encrypt_exponent_with_witness(&Scalar::from(1));
+//! let statement = vote.homo_elgamal_statement(&public_key);
+//! let proof = HomoELGamalProof::<Secp256k1, Sha256>::prove(&witness, &statement);
+//! assert!(proof.verify(&statement).is_ok());
+//!
+//! // Tally votes homomorphically
+//! let tally = &(&vote + &public_key.encrypt_exponent(&Scalar::from(1)))
+//!     + &public_key.encrypt_exponent(&Scalar::from(0));
+//! let table = DiscreteLogTable::new(1000);
+//! assert_eq!(secret_key.decrypt_exponent(&tally, &table), Ok(2));
+//! ```
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::{fmt, ops};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_enc::{
+    HomoElGamalStatement, HomoElGamalWitness,
+};
+use crate::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_encryption_of_dlog::{
+    HomoElGamalDlogStatement, HomoElGamalDlogWitness,
+};
+use crate::elliptic::curves::{Curve, Point, Scalar};
+
+/// Generates a random key pair
+pub fn keygen<E: Curve>() -> (SecretKey<E>, PublicKey<E>) {
+    let secret_key = SecretKey::random();
+    let public_key = secret_key.public_key();
+    (secret_key, public_key)
+}
+
+/// ElGamal secret key `x`
+#[derive(Clone)]
+pub struct SecretKey<E: Curve> {
+    scalar: Scalar<E>,
+}
+
+/// ElGamal public key `Y = x G`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PublicKey<E: Curve> {
+    point: Point<E>,
+}
+
+/// ElGamal ciphertext `(c1, c2) = (r G, M + r Y)`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Ciphertext<E: Curve> {
+    pub c1: Point<E>,
+    pub c2: Point<E>,
+}
+
+/// Precomputed table for solving discrete logarithms in range `[0, bound)` by baby-step
+/// giant-step algorithm
+///
+/// Takes `O(√bound)` memory and time to build, and `O(√bound)` time to solve a single discrete
+/// logarithm. Build the table once and reuse it for all decryptions.
+pub struct DiscreteLogTable<E: Curve> {
+    bound: u64,
+    step: u64,
+    /// `j G -> j` for `j` in `[0, step)`
+    baby_steps: HashMap<Vec<u8>, u64>,
+    /// `-step G`
+    giant_step: Point<E>,
+}
+
+impl<E: Curve> SecretKey<E> {
+    /// Samples a random secret key
+    pub fn random() -> Self {
+        loop {
+            if let Ok(secret_key) = Self::from_scalar(Scalar::random()) {
+                return secret_key;
+            }
+        }
+    }
+
+    /// Constructs a secret key from a scalar, returns error if it's zero
+    pub fn from_scalar(scalar: Scalar<E>) -> Result<Self, ElGamalError> {
+        if scalar.is_zero() {
+            return Err(ElGamalError::ZeroSecretKey);
+        }
+        Ok(Self { scalar })
+    }
+
+    pub fn as_scalar(&self) -> &Scalar<E> {
+        &self.scalar
+    }
+
+    pub fn public_key(&self) -> PublicKey<E> {
+        PublicKey {
+            point: Point::generator() * &self.scalar,
+        }
+    }
+
+    /// Decrypts a point `M = c2 - x c1`
+    pub fn decrypt(&self, ciphertext: &Ciphertext<E>) -> Point<E> {
+        &ciphertext.c2 - &ciphertext.c1 * &self.scalar
+    }
+
+    /// Decrypts exponential ElGamal ciphertext
+    ///
+    /// Returns error if plaintext isn't in range of the table.
+    pub fn decrypt_exponent(
+        &self,
+        ciphertext: &Ciphertext<E>,
+        table: &DiscreteLogTable<E>,
+    ) -> Result<u64, ElGamalError> {
+        table
+            .solve(&self.decrypt(ciphertext))
+            .ok_or(ElGamalError::DiscreteLogNotFound)
+    }
+}
+
+impl<E: Curve> PublicKey<E> {
+    /// Constructs a public key from a point, returns error if point is zero
+    pub fn from_point(point: Point<E>) -> Result<Self, ElGamalError> {
+        if point.is_zero() {
+            return Err(ElGamalError::InvalidPublicKey);
+        }
+        Ok(Self { point })
+    }
+
+    pub fn as_point(&self) -> &Point<E> {
+        &self.point
+    }
+
+    /// Encrypts a point
+    pub fn encrypt(&self, message: &Point<E>) -> Ciphertext<E> {
+        self.encrypt_with_randomness(message, &Scalar::random())
+    }
+
+    /// Encrypts a point with given randomness `r`
+    ///
+    /// Randomness must be uniformly random and never reused, otherwise use [encrypt](Self::encrypt).
+    pub fn encrypt_with_randomness(&self, message: &Point<E>, r: &Scalar<E>) -> Ciphertext<E> {
+        Ciphertext {
+            c1: Point::generator() * r,
+            c2: message + &self.point * r,
+        }
+    }
+
+    /// Encrypts a scalar `m` as a point `m G` (exponential ElGamal)
+    pub fn encrypt_exponent(&self, message: &Scalar<E>) -> Ciphertext<E> {
+        self.encrypt_with_randomness(&(Point::generator() * message), &Scalar::random())
+    }
+
+    /// Encrypts a scalar `m` as a point `m G`, and returns witness for [HomoElGamalStatement] of
+    /// the ciphertext (see [Ciphertext::homo_elgamal_statement])
+    pub fn encrypt_exponent_with_witness(
+        &self,
+        message: &Scalar<E>,
+    ) -> (Ciphertext<E>, HomoElGamalWitness<E>) {
+        let r = Scalar::random();
+        let ciphertext = self.encrypt_with_randomness(&(Point::generator() * message), &r);
+        let witness = HomoElGamalWitness {
+            r,
+            x: message.clone(),
+        };
+        (ciphertext, witness)
+    }
+
+    /// Encrypts a scalar `m` as a point `m G`, and returns witness for [HomoElGamalDlogStatement]
+    /// of the ciphertext (see [Ciphertext::homo_elgamal_dlog_statement])
+    pub fn encrypt_exponent_with_dlog_witness(
+        &self,
+        message: &Scalar<E>,
+    ) -> (Ciphertext<E>, HomoElGamalDlogWitness<E>) {
+        let r = Scalar::random();
+        let ciphertext = self.encrypt_with_randomness(&(Point::generator() * message), &r);
+        let witness = HomoElGamalDlogWitness {
+            r,
+            x: message.clone(),
+        };
+        (ciphertext, witness)
+    }
+}
+
+impl<E: Curve> Ciphertext<E> {
+    /// Re-randomizes the ciphertext, so it encrypts the same message, but can't be linked to the
+    /// original ciphertext
+    pub fn rerandomize(&self, public_key: &PublicKey<E>) -> Self {
+        self.rerandomize_with(public_key, &Scalar::random())
+    }
+
+    /// Re-randomizes the ciphertext by adding encryption of zero with randomness `r`
+    pub fn rerandomize_with(&self, public_key: &PublicKey<E>, r: &Scalar<E>) -> Self {
+        Ciphertext {
+            c1: &self.c1 + Point::generator() * r,
+            c2: &self.c2 + &public_key.point * r,
+        }
+    }
+
+    /// Statement that the ciphertext is exponential ElGamal encryption under `public_key`, i.e.
+    /// `c2 = x G + r Y`, `c1 = r G`
+    ///
+    /// Witness is returned by [PublicKey::encrypt_exponent_with_witness].
+    pub fn homo_elgamal_statement(&self, public_key: &PublicKey<E>) -> HomoElGamalStatement<E> {
+        let g = Point::generator().to_point();
+        HomoElGamalStatement {
+            G: g.clone(),
+            H: g,
+            Y: public_key.point.clone(),
+            D: self.c2.clone(),
+            E: self.c1.clone(),
+        }
+    }
+
+    /// Statement that the ciphertext is exponential ElGamal encryption under `public_key` of
+    /// discrete logarithm of `q`, i.e. `c2 = x G + r Y`, `c1 = r G`, `q = x G`
+    ///
+    /// Witness is returned by [PublicKey::encrypt_exponent_with_dlog_witness].
+    pub fn homo_elgamal_dlog_statement(
+        &self,
+        public_key: &PublicKey<E>,
+        q: &Point<E>,
+    ) -> HomoElGamalDlogStatement<E> {
+        HomoElGamalDlogStatement {
+            G: Point::generator().to_point(),
+            Y: public_key.point.clone(),
+            Q: q.clone(),
+            D: self.c2.clone(),
+            E: self.c1.clone(),
+        }
+    }
+}
+
+impl<E: Curve> DiscreteLogTable<E> {
+    /// Precomputes the table for discrete logarithms in range `[0, bound)`
+    pub fn new(bound: u64) -> Self {
+        let mut step = (bound as f64).sqrt().ceil() as u64;
+        while step.saturating_mul(step) < bound {
+            step += 1
+        }
+        let step = step.max(1);
+
+        let g = Point::<E>::generator();
+        let mut baby_steps = HashMap::with_capacity(usize::try_from(step).unwrap_or(0));
+        let mut point = Point::zero();
+        for j in 0..step {
+            baby_steps.entry(point.to_bytes(true).to_vec()).or_insert(j);
+            point = point + g;
+        }
+        Self {
+            bound,
+            step,
+            baby_steps,
+            giant_step: -point,
+        }
+    }
+
+    /// Upper bound (exclusive) of discrete logarithms that can be found
+    pub fn bound(&self) -> u64 {
+        self.bound
+    }
+
+    /// Finds `m` in range `[0, bound)` such that `point = m G`
+    pub fn solve(&self, point: &Point<E>) -> Option<u64> {
+        let mut gamma = point.clone();
+        for i in 0..self.step {
+            if let Some(j) = self.baby_steps.get(&gamma.to_bytes(true)[..]) {
+                let m = i * self.step + j;
+                return if m < self.bound { Some(m) } else { None };
+            }
+            gamma = gamma + &self.giant_step;
+        }
+        None
+    }
+}
+
+/// Adds two ciphertexts: the result encrypts sum of plaintexts
+impl<E: Curve> ops::Add for &Ciphertext<E> {
+    type Output = Ciphertext<E>;
+    fn add(self, other: Self) -> Self::Output {
+        Ciphertext {
+            c1: &self.c1 + &other.c1,
+            c2: &self.c2 + &other.c2,
+        }
+    }
+}
+
+/// Subtracts two ciphertexts: the result encrypts difference of plaintexts
+impl<E: Curve> ops::Sub for &Ciphertext<E> {
+    type Output = Ciphertext<E>;
+    fn sub(self, other: Self) -> Self::Output {
+        Ciphertext {
+            c1: &self.c1 - &other.c1,
+            c2: &self.c2 - &other.c2,
+        }
+    }
+}
+
+/// Multiplies a ciphertext at scalar: the result encrypts plaintext multiplied at scalar
+impl<E: Curve> ops::Mul<&Scalar<E>> for &Ciphertext<E> {
+    type Output = Ciphertext<E>;
+    fn mul(self, scalar: &Scalar<E>) -> Self::Output {
+        Ciphertext {
+            c1: &self.c1 * scalar,
+            c2: &self.c2 * scalar,
+        }
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ElGamalError {
+    #[error("public key is zero")]
+    InvalidPublicKey,
+    #[error("secret key is zero")]
+    ZeroSecretKey,
+    #[error("plaintext is out of range of discrete log table")]
+    DiscreteLogNotFound,
+}
+
+impl<E: Curve> fmt::Debug for SecretKey<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(f, "SecretKey{{ ... }}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::hashing::Digest;
+    use crate::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_enc::HomoELGamalProof;
+    use crate::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_encryption_of_dlog::HomoELGamalDlogProof;
+    use crate::elliptic::curves::Secp256k1;
+    use crate::{test_for_all_curves, test_for_all_curves_and_hashes};
+
+    test_for_all_curves!(encrypt_decrypt_point);
+    fn encrypt_decrypt_point<E: Curve>() {
+        let (secret_key, public_key) = keygen::<E>();
+        let message = Point::generator() * Scalar::random();
+        let ciphertext = public_key.encrypt(&message);
+        assert_eq!(secret_key.decrypt(&ciphertext), message);
+
+        let rerandomized = ciphertext.rerandomize(&public_key);
+        assert_ne!(rerandomized, ciphertext);
+        assert_eq!(secret_key.decrypt(&rerandomized), message);
+    }
+
+    test_for_all_curves!(homomorphic_operations);
+    fn homomorphic_operations<E: Curve>() {
+        let (secret_key, public_key) = keygen::<E>();
+        let table = DiscreteLogTable::new(10_000);
+        let a = public_key.encrypt_exponent(&Scalar::from(1234));
+        let b = public_key.encrypt_exponent(&Scalar::from(567));
+
+        assert_eq!(secret_key.decrypt_exponent(&(&a + &b), &table), Ok(1801));
+        assert_eq!(secret_key.decrypt_exponent(&(&a - &b), &table), Ok(667));
+        assert_eq!(
+            secret_key.decrypt_exponent(&(&b * &Scalar::from(3)), &table),
+            Ok(1701)
+        );
+        assert_eq!(
+            secret_key.decrypt_exponent(&(&b - &a), &table),
+            Err(ElGamalError::DiscreteLogNotFound)
+        );
+    }
+
+    #[test]
+    fn discrete_log_table_bounds() {
+        for &bound in &[1u64, 2, 10, 17, 100] {
+            let table = DiscreteLogTable::<Secp256k1>::new(bound);
+            let g = Point::<Secp256k1>::generator();
+            for m in 0..bound {
+                assert_eq!(table.solve(&(g * Scalar::from(m))), Some(m));
+            }
+            assert_eq!(table.solve(&(g * Scalar::from(bound))), None);
+        }
+    }
+
+    test_for_all_curves_and_hashes!(statements_match_proofs);
+    fn statements_match_proofs<E: Curve, H: Digest + Clone>() {
+        let public_key = SecretKey::<E>::random().public_key();
+        let message = Scalar::random();
+
+        let (ciphertext, witness) = public_key.encrypt_exponent_with_witness(&message);
+        let statement = ciphertext.homo_elgamal_statement(&public_key);
+        let proof = HomoELGamalProof::<E, H>::prove(&witness, &statement);
+        assert!(proof.verify(&statement).is_ok());
+
+        let (ciphertext, witness) = public_key.encrypt_exponent_with_dlog_witness(&message);
+        let q = Point::generator() * &message;
+        let statement = ciphertext.homo_elgamal_dlog_statement(&public_key, &q);
+        let proof = HomoELGamalDlogProof::<E, H>::prove(&witness, &statement);
+        assert!(proof.verify(&statement).is_ok());
+    }
+
+    #[test]
+    fn zero_keys_are_rejected() {
+        assert_eq!(
+            SecretKey::<Secp256k1>::from_scalar(Scalar::zero()).err(),
+            Some(ElGamalError::ZeroSecretKey)
+        );
+        assert_eq!(
+            PublicKey::<Secp256k1>::from_point(Point::zero()).err(),
+            Some(ElGamalError::InvalidPublicKey)
+        );
+    }
+}
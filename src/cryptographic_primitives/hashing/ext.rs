@@ -78,6 +78,20 @@ pub trait DigestExt {
 
     fn result_bigint(self) -> BigInt;
     fn result_scalar<E: Curve>(self) -> Scalar<E>;
+    /// Reduces the hash modulo group order using at least `L = ceil((log2(q) + 128) / 8)` bytes
+    ///
+    /// Unlike [result_scalar](Self::result_scalar), it doesn't use try-and-increment, so it never
+    /// rehashes and its output is statistically close to uniform. Note that reduction is done via
+    /// [BigInt], so it's not constant time.
+    ///
+    /// If hash output is shorter than `L` bytes (e.g. SHA-256 output for 256 bits group order),
+    /// it's extended by concatenating `H(data || 0u32) || H(data || 1u32) || ...`, otherwise the
+    /// whole hash output is reduced (e.g. SHA-512 output for ed25519 scalars, like [RFC 8032] does).
+    ///
+    /// Prefer [Scalar::hash_from_bytes] if you need interoperability with other implementations.
+    ///
+    /// [RFC 8032]: https://www.rfc-editor.org/rfc/rfc8032.html
+    fn result_scalar_wide<E: Curve>(self) -> Scalar<E>;
 
     fn digest_bigint(bytes: &[u8]) -> BigInt;
 }
@@ -122,6 +136,19 @@ where
         unreachable!("The probably of this reaching is extremely small ((2^n-q)/(2^n))^(2^32)")
     }
 
+    fn result_scalar_wide<E: Curve>(self) -> Scalar<E> {
+        let bytes_len = (Scalar::<E>::group_order().bit_length() + 128).div_ceil(8);
+        let bytes = if Self::output_size() >= bytes_len {
+            self.finalize().to_vec()
+        } else {
+            let blocks = bytes_len.div_ceil(Self::output_size()) as u32;
+            (0..blocks)
+                .flat_map(|i| self.clone().chain(i.to_be_bytes()).finalize())
+                .collect()
+        };
+        Scalar::from_bigint(&BigInt::from_bytes(&bytes))
+    }
+
     fn digest_bigint(bytes: &[u8]) -> BigInt {
         Self::new().chain(bytes).result_bigint()
     }
@@ -300,6 +327,36 @@ mod test {
         assert_eq!(result2, result3);
     }
 
+    crate::test_for_all_curves_and_hashes!(result_scalar_wide_test);
+    fn result_scalar_wide_test<E: Curve, H: Digest + Clone>() {
+        let generator = Point::<E>::generator();
+        let base_point2 = Point::<E>::base_point2();
+        let result1 = H::new()
+            .chain_point(&generator)
+            .chain_point(base_point2)
+            .result_scalar_wide::<E>();
+        assert!(result1.to_bigint().bit_length() > 240);
+        let result2 = H::new()
+            .chain_point(base_point2)
+            .chain_point(&generator)
+            .result_scalar_wide::<E>();
+        assert_ne!(result1, result2);
+        let result3 = H::new()
+            .chain_point(base_point2)
+            .chain_point(&generator)
+            .result_scalar_wide::<E>();
+        assert_eq!(result2, result3);
+    }
+
+    #[test]
+    fn result_scalar_wide_reduces_whole_sha512_output() {
+        use crate::elliptic::curves::Ed25519;
+
+        let hash = Digest::chain(Sha512::new(), b"message");
+        let expected = Scalar::<Ed25519>::from_bigint(&hash.clone().result_bigint());
+        assert_eq!(hash.result_scalar_wide::<Ed25519>(), expected);
+    }
+
     crate::test_for_all_hashes!(create_hmac_test);
     fn create_hmac_test<H>()
     where
@@ -0,0 +1,264 @@
+use std::borrow::Cow;
+
+use digest::generic_array::typenum::Unsigned;
+use digest::{BlockInput, Digest};
+use thiserror::Error;
+
+use crate::arithmetic::*;
+
+/// Expands a message into `len_in_bytes` uniformly distributed bytes
+///
+/// Implements `expand_message_xmd` defined in [RFC 9380, section 5.3.1][xmd]. `dst` is a domain
+/// separation tag. Tags longer than 255 bytes are hashed as described in
+/// [section 5.3.3][oversize-dst].
+///
+/// Returns error if `len_in_bytes` exceeds `65535` or `255 * H::output_size()`.
+///
+/// ## Example
+/// ```rust
+/// use curv::cryptographic_primitives::hashing::expand_message_xmd;
+/// use sha2::Sha256;
+///
+/// let bytes = expand_message_xmd::<Sha256>(b"message", b"MY-PROTOCOL-V1", 48)?;
+/// assert_eq!(bytes.len(), 48);
+/// # Ok::<(), curv::cryptographic_primitives::hashing::ExpandMessageError>(())
+/// ```
+///
+/// [xmd]: https://www.rfc-editor.org/rfc/rfc9380.html#name-expand_message_xmd
+/// [oversize-dst]: https://www.rfc-editor.org/rfc/rfc9380.html#name-using-dsts-longer-than-255-
+pub fn expand_message_xmd<H>(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<u8>, ExpandMessageError>
+where
+    H: Digest + BlockInput,
+{
+    let b_in_bytes = H::output_size();
+    let r_in_bytes = H::BlockSize::to_usize();
+    let ell = len_in_bytes.div_ceil(b_in_bytes);
+    if ell > 255 || len_in_bytes > usize::from(u16::MAX) {
+        return Err(ExpandMessageError::OutputTooLong { len_in_bytes });
+    }
+
+    let dst = reduce_dst::<H>(dst);
+    let dst_len = [dst.len() as u8];
+
+    let b_0 = H::new()
+        .chain(vec![0u8; r_in_bytes])
+        .chain(msg)
+        .chain((len_in_bytes as u16).to_be_bytes())
+        .chain([0u8])
+        .chain(&dst)
+        .chain(dst_len)
+        .finalize();
+    let mut b_i = H::new()
+        .chain(&b_0)
+        .chain([1u8])
+        .chain(&dst)
+        .chain(dst_len)
+        .finalize();
+
+    let mut uniform_bytes = Vec::with_capacity(ell * b_in_bytes);
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let b_0_xor_b_i: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(x, y)| x ^ y).collect();
+        b_i = H::new()
+            .chain(b_0_xor_b_i)
+            .chain([i as u8])
+            .chain(&dst)
+            .chain(dst_len)
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+/// Hashes a message to `count` elements of prime field $\mathbb{Z}_p$
+///
+/// Implements `hash_to_field` defined in [RFC 9380, section 5.2][h2f] (with extension degree
+/// `m = 1` and `expand_message_xmd` as an expander) targeting 128 bits of security: every element
+/// is obtained by reducing `L = ceil((ceil(log2(p)) + 128) / 8)` uniform bytes modulo `p`. E.g.
+/// `L = 48` for 256 bits modulus, so the bias of resulting elements is negligible.
+///
+/// [h2f]: https://www.rfc-editor.org/rfc/rfc9380.html#name-hash_to_field-implementatio
+pub fn hash_to_field<H>(
+    msg: &[u8],
+    dst: &[u8],
+    modulus: &BigInt,
+    count: usize,
+) -> Result<Vec<BigInt>, ExpandMessageError>
+where
+    H: Digest + BlockInput,
+{
+    let l = (modulus.bit_length() + 128).div_ceil(8);
+    let uniform_bytes = expand_message_xmd::<H>(msg, dst, count * l)?;
+    Ok(uniform_bytes
+        .chunks(l)
+        .map(|tv| BigInt::from_bytes(tv).modulus(modulus))
+        .collect())
+}
+
+/// Hashes domain separation tag if it's longer than 255 bytes
+pub(crate) fn reduce_dst<H: Digest>(dst: &[u8]) -> Cow<'_, [u8]> {
+    if dst.len() > 255 {
+        let hashed = H::new().chain(b"H2C-OVERSIZE-DST-").chain(dst).finalize();
+        Cow::Owned(hashed.to_vec())
+    } else {
+        Cow::Borrowed(dst)
+    }
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ExpandMessageError {
+    #[error("requested {len_in_bytes} bytes, that's more than expand_message_xmd can output")]
+    OutputTooLong { len_in_bytes: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Sha256, Sha512};
+
+    use super::*;
+    use crate::elliptic::curves::{Curve, Scalar};
+
+    /// Messages used in RFC 9380 test vectors
+    fn test_messages() -> Vec<Vec<u8>> {
+        let q128 = [&b"q128_"[..], &[b'q'; 128]].concat();
+        let a512 = [&b"a512_"[..], &[b'a'; 512]].concat();
+        vec![
+            b"".to_vec(),
+            b"abc".to_vec(),
+            b"abcdef0123456789".to_vec(),
+            q128,
+            a512,
+        ]
+    }
+
+    fn check_vectors<H: Digest + BlockInput>(dst: &[u8], len_in_bytes: usize, expected: &[&str]) {
+        for (msg, expected) in test_messages().iter().zip(expected) {
+            let uniform_bytes = expand_message_xmd::<H>(msg, dst, len_in_bytes).unwrap();
+            assert_eq!(hex::encode(uniform_bytes), *expected);
+        }
+    }
+
+    // Test vectors are taken from RFC 9380, Appendix K.1
+    #[test]
+    fn expand_message_xmd_sha256() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        check_vectors::<Sha256>(
+            dst,
+            0x20,
+            &[
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+                "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+                "b23a1d2b4d97b2ef7785562a7e8bac7eed54ed6e97e29aa51bfe3f12ddad1ff9",
+                "4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c",
+            ],
+        );
+        check_vectors::<Sha256>(
+            dst,
+            0x80,
+            &[
+                "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced",
+                "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40",
+                "ef904a29bffc4cf9ee82832451c946ac3c8f8058ae97d8d629831a74c6572bd9ebd0df635cd1f208e2038e760c4994984ce73f0d55ea9f22af83ba4734569d4bc95e18350f740c07eef653cbb9f87910d833751825f0ebefa1abe5420bb52be14cf489b37fe1a72f7de2d10be453b2c9d9eb20c7e3f6edc5a60629178d9478df",
+                "80be107d0884f0d881bb460322f0443d38bd222db8bd0b0a5312a6fedb49c1bbd88fd75d8b9a09486c60123dfa1d73c1cc3169761b17476d3c6b7cbbd727acd0e2c942f4dd96ae3da5de368d26b32286e32de7e5a8cb2949f866a0b80c58116b29fa7fabb3ea7d520ee603e0c25bcaf0b9a5e92ec6a1fe4e0391d1cdbce8c68a",
+                "546aff5444b5b79aa6148bd81728704c32decb73a3ba76e9e75885cad9def1d06d6792f8a7d12794e90efed817d96920d728896a4510864370c207f99bd4a608ea121700ef01ed879745ee3e4ceef777eda6d9e5e38b90c86ea6fb0b36504ba4a45d22e86f6db5dd43d98a294bebb9125d5b794e9d2a81181066eb954966a487",
+            ],
+        );
+    }
+
+    // Test vectors are taken from RFC 9380, Appendix K.2
+    #[test]
+    fn expand_message_xmd_sha256_long_dst() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-1111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111";
+        check_vectors::<Sha256>(
+            dst,
+            0x20,
+            &[
+                "e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3",
+                "52dbf4f36cf560fca57dedec2ad924ee9c266341d8f3d6afe5171733b16bbb12",
+                "35387dcf22618f3728e6c686490f8b431f76550b0b2c61cbc1ce7001536f4521",
+                "01b637612bb18e840028be900a833a74414140dde0c4754c198532c3a0ba42bc",
+                "20cce7033cabc5460743180be6fa8aac5a103f56d481cf369a8accc0c374431b",
+            ],
+        );
+    }
+
+    // Test vectors are taken from RFC 9380, Appendix K.3
+    #[test]
+    fn expand_message_xmd_sha512() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
+        check_vectors::<Sha512>(
+            dst,
+            0x20,
+            &[
+                "6b9a7312411d92f921c6f68ca0b6380730a1a4d982c507211a90964c394179ba",
+                "0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc",
+            ],
+        );
+    }
+
+    #[test]
+    fn expand_message_xmd_rejects_too_long_output() {
+        assert!(expand_message_xmd::<Sha256>(b"", b"DST", 255 * 32).is_ok());
+        assert!(expand_message_xmd::<Sha256>(b"", b"DST", 255 * 32 + 1).is_err());
+    }
+
+    // Expected values are `OS2IP(expand_message_xmd(msg, DST, 48)) mod n`, i.e. `hash_to_field`
+    // from RFC 9380, Section 5.2 with `m = 1`, `L = 48`, where `n` is the group order. They can be
+    // reproduced with any independent implementation of `expand_message_xmd`
+    #[test]
+    fn hash_to_scalar_test_vectors() {
+        use crate::elliptic::curves::{Secp256k1, Secp256r1};
+
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let vectors = [
+            (
+                &b""[..],
+                "bf49427ebd8cb06f29c06b4dd96d9a22159db67ff68d8459d660a6ab51eb1e15",
+                "e291ec5389a61e6887d2e6d25b3830dbff54d7e9f494236a27e62b5e8a173ecb",
+            ),
+            (
+                b"abc",
+                "26a475d37f1a1585e49e28e02de9fb234184bc3a1da5ccdb3f992a7c6dbcb4a2",
+                "48eae75451d2850e478e29e92f48c4dc93f4f6d04a6ee0e1e1ebdd347f627f76",
+            ),
+            (
+                b"abcdef0123456789",
+                "b6abe727d22c665008fb348191f0f71ff3e59186bb8c9b8532492d0e18759a56",
+                "4117bac584cbed5f3601ce31a64ed86fa7f3d426184e3b5c8091b71ca54df63d",
+            ),
+        ];
+        for (msg, secp256k1, p256) in vectors.iter() {
+            let s1 = Scalar::<Secp256k1>::hash_from_bytes::<Sha256>(dst, msg);
+            assert_eq!(s1.to_bigint(), BigInt::from_hex(secp256k1).unwrap());
+            let s2 = Scalar::<Secp256r1>::hash_from_bytes::<Sha256>(dst, msg);
+            assert_eq!(s2.to_bigint(), BigInt::from_hex(p256).unwrap());
+        }
+    }
+
+    crate::test_for_all_curves_and_hashes!(hash_to_scalar_is_domain_separated);
+    fn hash_to_scalar_is_domain_separated<E: Curve, H: Digest + BlockInput>() {
+        let s = Scalar::<E>::hash_from_bytes::<H>(b"DST-1", b"message");
+        assert_eq!(s, Scalar::hash_from_bytes::<H>(b"DST-1", b"message"));
+        assert_ne!(s, Scalar::hash_from_bytes::<H>(b"DST-2", b"message"));
+        assert_ne!(
+            s,
+            Scalar::hash_from_bytes::<H>(b"DST-1", b"another message")
+        );
+        assert!(s.to_bigint().bit_length() > 240);
+    }
+
+    #[test]
+    fn hash_to_field_outputs_reduced_elements() {
+        let p = BigInt::from(2).pow(255) - BigInt::from(19);
+        let elements = hash_to_field::<Sha512>(b"abc", b"DST", &p, 3).unwrap();
+        assert_eq!(elements.len(), 3);
+        assert!(elements.iter().all(|e| e < &p));
+        assert_ne!(elements[0], elements[1]);
+    }
+}
@@ -5,7 +5,9 @@
     License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
 */
 mod ext;
+mod hash_to_field;
 pub mod merkle_tree;
 
 pub use digest::Digest;
 pub use ext::*;
+pub use hash_to_field::*;
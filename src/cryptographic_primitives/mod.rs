@@ -6,7 +6,9 @@
 */
 
 pub mod commitments;
+pub mod encryption;
 pub mod hashing;
 pub mod proofs;
 pub mod secret_sharing;
+pub mod signatures;
 pub mod twoparty;
@@ -0,0 +1,266 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use std::iter;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::proofs::transcript::Transcript;
+use crate::cryptographic_primitives::proofs::ProofError;
+use crate::elliptic::curves::{Curve, Point, Scalar};
+use crate::marker::HashChoice;
+
+use super::inner_product;
+
+/// Inner product argument (protocol 2 of Bulletproofs paper)
+///
+/// Proves knowledge of vectors `a`, `b` of length `n` (power of two) such that
+/// `P = <a, G> + <b, H> + <a, b> Q` for public `P`, `Q`, `G`, `H`. Proof consists of `2 log n`
+/// points and two scalars.
+///
+/// The protocol:
+/// 1. While `n > 1`, prover halves vectors: sends `L = <a_lo, G_hi> + <b_hi, H_lo> + <a_lo, b_hi> Q`
+///    and `R = <a_hi, G_lo> + <b_lo, H_hi> + <a_hi, b_lo> Q`, receives challenge `u`, and folds
+///    `a = u a_lo + u^-1 a_hi`, `b = u^-1 b_lo + u b_hi`, `G = u^-1 G_lo + u G_hi`,
+///    `H = u H_lo + u^-1 H_hi`.
+/// 2. Prover sends remaining scalars `a`, `b`.
+///
+/// Verifier checks `P + Σ (u_j^2 L_j + u_j^-2 R_j) = a <s, G> + b <s^-1, H> + ab Q`, where
+/// `s_i = Π u_j^{±1}` depending on bits of `i`. Challenges are derived from the [Transcript].
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct InnerProductProof<E: Curve, H: Digest + Clone> {
+    pub l_vec: Vec<Point<E>>,
+    pub r_vec: Vec<Point<E>>,
+    pub a: Scalar<E>,
+    pub b: Scalar<E>,
+    #[serde(skip)]
+    pub hash_choice: HashChoice<H>,
+}
+
+/// Scalars needed to verify [InnerProductProof] in a single multiscalar multiplication
+pub(super) struct VerificationScalars<E: Curve> {
+    /// `u_j^2`
+    pub challenges_sq: Vec<Scalar<E>>,
+    /// `u_j^-2`
+    pub challenges_inv_sq: Vec<Scalar<E>>,
+    /// `s_i`, note that `s_i^-1 = s_{n-1-i}`
+    pub s: Vec<Scalar<E>>,
+}
+
+impl<E: Curve, H: Digest + Clone> InnerProductProof<E, H> {
+    /// Proves that `P = <a, G> + <b, H> + <a, b> Q`
+    ///
+    /// `P` isn't appended to the transcript, it's up to the caller to bind it.
+    ///
+    /// ## Panics
+    /// Panics if vectors have different lengths, or length is not a power of two.
+    pub fn prove(
+        transcript: &mut Transcript<H>,
+        q: &Point<E>,
+        g_vec: &[Point<E>],
+        h_vec: &[Point<E>],
+        a: &[Scalar<E>],
+        b: &[Scalar<E>],
+    ) -> Self {
+        let mut n = g_vec.len();
+        assert!(
+            h_vec.len() == n && a.len() == n && b.len() == n,
+            "vectors must have the same length"
+        );
+        assert!(n.is_power_of_two(), "length must be a power of two");
+
+        transcript.append_message(b"proof", b"curv/inner-product-proof");
+        transcript.append_u64(b"n", n as u64);
+
+        let mut g = g_vec.to_vec();
+        let mut h = h_vec.to_vec();
+        let mut a = a.to_vec();
+        let mut b = b.to_vec();
+        let mut l_vec = vec![];
+        let mut r_vec = vec![];
+
+        while n > 1 {
+            n /= 2;
+            let (a_lo, a_hi) = a.split_at(n);
+            let (b_lo, b_hi) = b.split_at(n);
+            let (g_lo, g_hi) = g.split_at(n);
+            let (h_lo, h_hi) = h.split_at(n);
+
+            let c_l = inner_product(a_lo, b_hi);
+            let c_r = inner_product(a_hi, b_lo);
+            let l = Point::multiscalar_mul(
+                a_lo.iter().chain(b_hi).chain(iter::once(&c_l)),
+                g_hi.iter().chain(h_lo).chain(iter::once(q)),
+            );
+            let r = Point::multiscalar_mul(
+                a_hi.iter().chain(b_lo).chain(iter::once(&c_r)),
+                g_lo.iter().chain(h_hi).chain(iter::once(q)),
+            );
+
+            transcript.append_point(b"L", &l);
+            transcript.append_point(b"R", &r);
+            let u: Scalar<E> = transcript.challenge_scalar(b"u");
+            let u_inv = u.invert().expect("challenge is zero");
+
+            a = (0..n).map(|i| &a_lo[i] * &u + &a_hi[i] * &u_inv).collect();
+            b = (0..n).map(|i| &b_lo[i] * &u_inv + &b_hi[i] * &u).collect();
+            g = (0..n)
+                .map(|i| Point::multiscalar_mul([&u_inv, &u], [&g_lo[i], &g_hi[i]]))
+                .collect();
+            h = (0..n)
+                .map(|i| Point::multiscalar_mul([&u, &u_inv], [&h_lo[i], &h_hi[i]]))
+                .collect();
+
+            l_vec.push(l);
+            r_vec.push(r);
+        }
+
+        InnerProductProof {
+            l_vec,
+            r_vec,
+            a: a.remove(0),
+            b: b.remove(0),
+            hash_choice: HashChoice::new(),
+        }
+    }
+
+    /// Verifies that `P = <a, G> + <b, H> + <a, b> Q`
+    ///
+    /// Transcript has to be in the same state as it was when [proving](Self::prove).
+    pub fn verify(
+        &self,
+        transcript: &mut Transcript<H>,
+        q: &Point<E>,
+        g_vec: &[Point<E>],
+        h_vec: &[Point<E>],
+        p: &Point<E>,
+    ) -> Result<(), ProofError> {
+        let n = g_vec.len();
+        if h_vec.len() != n {
+            return Err(ProofError);
+        }
+        let scalars = self.verification_scalars(n, transcript)?;
+
+        let ab = &self.a * &self.b;
+        let minus_one = -Scalar::from(1);
+        let g_scalars = scalars.s.iter().map(|s_i| &self.a * s_i);
+        let h_scalars = scalars.s.iter().rev().map(|s_i| &self.b * s_i);
+        let scalars: Vec<Scalar<E>> = g_scalars
+            .chain(h_scalars)
+            .chain([ab, minus_one])
+            .chain(scalars.challenges_sq.iter().map(|u| -u))
+            .chain(scalars.challenges_inv_sq.iter().map(|u| -u))
+            .collect();
+        let points = g_vec
+            .iter()
+            .chain(h_vec)
+            .chain([q, p])
+            .chain(&self.l_vec)
+            .chain(&self.r_vec);
+
+        if Point::multiscalar_mul(&scalars, points).is_zero() {
+            Ok(())
+        } else {
+            Err(ProofError)
+        }
+    }
+
+    /// Replays the transcript and computes scalars needed for verification of the proof for
+    /// vectors of length `n`
+    pub(super) fn verification_scalars(
+        &self,
+        n: usize,
+        transcript: &mut Transcript<H>,
+    ) -> Result<VerificationScalars<E>, ProofError> {
+        let rounds = self.l_vec.len();
+        if rounds >= 32 || n != 1 << rounds || self.r_vec.len() != rounds {
+            return Err(ProofError);
+        }
+
+        transcript.append_message(b"proof", b"curv/inner-product-proof");
+        transcript.append_u64(b"n", n as u64);
+
+        let mut challenges = Vec::with_capacity(rounds);
+        for (l, r) in self.l_vec.iter().zip(&self.r_vec) {
+            transcript.append_point(b"L", l);
+            transcript.append_point(b"R", r);
+            challenges.push(transcript.challenge_scalar::<E>(b"u"));
+        }
+        let challenges_inv = challenges
+            .iter()
+            .map(|u| u.invert())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ProofError)?;
+
+        let challenges_sq: Vec<Scalar<E>> = challenges.iter().map(|u| u * u).collect();
+        let challenges_inv_sq: Vec<Scalar<E>> = challenges_inv.iter().map(|u| u * u).collect();
+
+        // s_0 = Π u_j^-1, and every set bit of index `i` turns u_j^-1 into u_j, where the
+        // highest bit corresponds to the first round
+        let mut s = Vec::with_capacity(n);
+        s.push(
+            challenges_inv
+                .iter()
+                .fold(Scalar::from(1), |acc, u| acc * u),
+        );
+        for i in 1..n {
+            let lg_i = (usize::BITS - 1 - i.leading_zeros()) as usize;
+            let k = 1 << lg_i;
+            let s_i = &s[i - k] * &challenges_sq[rounds - 1 - lg_i];
+            s.push(s_i);
+        }
+
+        Ok(VerificationScalars {
+            challenges_sq,
+            challenges_inv_sq,
+            s,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::BulletproofGens;
+    use super::*;
+    use crate::elliptic::curves::HashToCurve;
+    use crate::test_for_all_curves_and_hashes;
+
+    test_for_all_curves_and_hashes!(test_inner_product_proof);
+    fn test_inner_product_proof<E: HashToCurve, H: Digest + Clone>() {
+        for &n in &[1usize, 2, 8] {
+            let gens = BulletproofGens::<E>::new(n);
+            let q = Point::<E>::base_point2();
+            let a: Vec<Scalar<E>> = (0..n).map(|_| Scalar::random()).collect();
+            let b: Vec<Scalar<E>> = (0..n).map(|_| Scalar::random()).collect();
+            let p = Point::multiscalar_mul(&a, &gens.g_vec)
+                + Point::multiscalar_mul(&b, &gens.h_vec)
+                + q * inner_product(&a, &b);
+
+            let mut transcript = Transcript::<H>::new(b"test");
+            let proof =
+                InnerProductProof::prove(&mut transcript, q, &gens.g_vec, &gens.h_vec, &a, &b);
+            assert_eq!(proof.l_vec.len(), n.trailing_zeros() as usize);
+
+            let mut transcript = Transcript::<H>::new(b"test");
+            assert!(proof
+                .verify(&mut transcript, q, &gens.g_vec, &gens.h_vec, &p)
+                .is_ok());
+
+            // there are no challenges for vectors of length 1
+            let mut transcript = Transcript::<H>::new(b"another test");
+            let result = proof.verify(&mut transcript, q, &gens.g_vec, &gens.h_vec, &p);
+            assert_eq!(result.is_ok(), n == 1);
+
+            let mut transcript = Transcript::<H>::new(b"test");
+            assert!(proof
+                .verify(&mut transcript, q, &gens.g_vec, &gens.h_vec, &(&p + q))
+                .is_err());
+        }
+    }
+}
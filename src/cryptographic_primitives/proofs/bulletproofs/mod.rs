@@ -0,0 +1,102 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Bulletproofs: short range proofs without trusted setup
+//!
+//! Implementation of "Bulletproofs: Short Proofs for Confidential Transactions and More" by
+//! Bünz, Bootle, Boneh, Poelstra, Wuille and Maxwell, generic over any [Curve]:
+//! * [RangeProof](range_proof::RangeProof) proves that one or several Pedersen commitments
+//!   `V = v G + γ H` open to values in `[0, 2^n)`. Commitments follow
+//!   [PedersenCommitment](crate::cryptographic_primitives::commitments::pedersen_commitment::PedersenCommitment)
+//!   convention: `G` is [generator](Point::generator) and `H` is [base_point2](Point::base_point2).
+//! * [InnerProductProof](inner_product::InnerProductProof) is the underlying logarithmic-size
+//!   argument, which can be reused on its own.
+//!
+//! Vector generators are [derived](BulletproofGens::new) deterministically by hashing to curve,
+//! so nobody knows discrete logarithms between them.
+
+use crate::elliptic::curves::{Curve, HashToCurve, Point, Scalar};
+
+pub mod inner_product;
+pub mod range_proof;
+
+const GENERATORS_DST: &[u8] = b"CURV-BULLETPROOFS-V1-GENERATORS";
+
+/// Vector generators `G_1, ..., G_N`, `H_1, ..., H_N` used in Bulletproofs
+///
+/// Range proof of `m` values in `[0, 2^n)` requires capacity of at least `n * m`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BulletproofGens<E: Curve> {
+    pub g_vec: Vec<Point<E>>,
+    pub h_vec: Vec<Point<E>>,
+}
+
+impl<E: HashToCurve> BulletproofGens<E> {
+    /// Derives `capacity` pairs of generators by hashing to curve
+    ///
+    /// Derivation is deterministic: generators with smaller capacity are prefix of generators with
+    /// larger capacity.
+    pub fn new(capacity: usize) -> Self {
+        let derive = |label: u8, i: usize| {
+            let mut msg = vec![label];
+            msg.extend_from_slice(&(i as u64).to_be_bytes());
+            Point::hash_to_curve(&msg, GENERATORS_DST)
+        };
+        Self {
+            g_vec: (0..capacity).map(|i| derive(b'G', i)).collect(),
+            h_vec: (0..capacity).map(|i| derive(b'H', i)).collect(),
+        }
+    }
+}
+
+impl<E: Curve> BulletproofGens<E> {
+    /// Number of generators pairs
+    pub fn capacity(&self) -> usize {
+        self.g_vec.len()
+    }
+}
+
+/// Computes inner product `<a, b> = Σ a_i b_i`
+///
+/// ## Panics
+/// Panics if vectors have different lengths.
+pub fn inner_product<E: Curve>(a: &[Scalar<E>], b: &[Scalar<E>]) -> Scalar<E> {
+    assert_eq!(a.len(), b.len(), "vectors must have the same length");
+    a.iter().zip(b).map(|(a_i, b_i)| a_i * b_i).sum()
+}
+
+/// Computes `1, x, x^2, ..., x^{n-1}`
+fn powers<E: Curve>(x: &Scalar<E>, n: usize) -> Vec<Scalar<E>> {
+    std::iter::successors(Some(Scalar::from(1)), |p| Some(p * x))
+        .take(n)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    test_for_all_curves!(generators_are_deterministic);
+    fn generators_are_deterministic<E: HashToCurve>() {
+        let gens = BulletproofGens::<E>::new(16);
+        assert_eq!(gens.capacity(), 16);
+        assert_eq!(gens, BulletproofGens::new(16));
+
+        let small = BulletproofGens::<E>::new(4);
+        assert_eq!(small.g_vec[..], gens.g_vec[..4]);
+        assert_eq!(small.h_vec[..], gens.h_vec[..4]);
+
+        let mut all: Vec<&Point<E>> = gens.g_vec.iter().chain(&gens.h_vec).collect();
+        all.push(Point::generator().as_point());
+        all.push(Point::base_point2());
+        for (i, p) in all.iter().enumerate() {
+            assert!(!p.is_zero());
+            assert!(all[..i].iter().all(|q| q != p));
+        }
+    }
+}
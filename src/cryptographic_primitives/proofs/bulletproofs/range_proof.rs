@@ -0,0 +1,611 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Range proofs (section 4 of Bulletproofs paper)
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::proofs::bulletproofs::range_proof::RangeProof;
+//! use curv::cryptographic_primitives::proofs::bulletproofs::BulletproofGens;
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! // Prove that two committed amounts are 32-bits values
+//! let gens = BulletproofGens::<Secp256k1>::new(64);
+//! let blindings = [Scalar::random(), Scalar::random()];
+//! let (proof, commitments) =
+//!     RangeProof::<_, Sha256>::prove(&gens, &[1000, 42], &blindings, 32).unwrap();
+//! assert!(proof.verify(&gens, &commitments, 32).is_ok());
+//! ```
+
+use std::iter;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::proofs::transcript::Transcript;
+use crate::cryptographic_primitives::proofs::{
+    batch_verification_weight, BatchVerificationError, ProofError,
+};
+use crate::elliptic::curves::{Curve, Point, Scalar};
+use crate::marker::HashChoice;
+
+use super::inner_product::InnerProductProof;
+use super::{inner_product, powers, BulletproofGens};
+
+/// Aggregated range proof: proves that Pedersen commitments `V_j = v_j G + γ_j H` open to values
+/// `v_j` in `[0, 2^n)`
+///
+/// Size of the proof is `2 log(n m) + 4` points and `5` scalars for `m` commitments.
+///
+/// The protocol (prover's side):
+/// 1. Commits to bits `a_L` of all values and `a_R = a_L - 1`: `A = α H + <a_L, G> + <a_R, H>`,
+///    and to blinding vectors `s_L`, `s_R`: `S = ρ H + <s_L, G> + <s_R, H>`.
+/// 2. Receives challenges `y`, `z`, and commits to coefficients of
+///    `t(X) = <l(X), r(X)> = t_0 + t_1 X + t_2 X^2`, where `l(X) = a_L - z 1 + s_L X`,
+///    `r(X) = y^{nm} ∘ (a_R + z 1 + s_R X) + Σ_j z^{2+j} (0^{jn} || 2^n || 0^{(m-j-1)n})`:
+///    `T_i = t_i G + τ_i H`.
+/// 3. Receives challenge `x`, sends `t̂ = t(x)`, `τ_x = τ_2 x^2 + τ_1 x + Σ_j z^{2+j} γ_j`,
+///    `μ = α + ρ x`, and proves that `l(x)`, `r(x)` are consistent with `A`, `S` and `t̂` via
+///    [InnerProductProof] with generators `G`, `H' = y^{-i} H` and `Q = w G` for challenge `w`.
+///
+/// Verifier checks `t̂ G + τ_x H = Σ_j z^{2+j} V_j + δ(y, z) G + x T_1 + x^2 T_2` and the inner
+/// product argument. Both checks are combined in a single multiscalar multiplication.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct RangeProof<E: Curve, H: Digest + Clone> {
+    pub a: Point<E>,
+    pub s: Point<E>,
+    pub t1: Point<E>,
+    pub t2: Point<E>,
+    pub tau_x: Scalar<E>,
+    pub mu: Scalar<E>,
+    pub t_hat: Scalar<E>,
+    pub ipp_proof: InnerProductProof<E, H>,
+    #[serde(skip)]
+    pub hash_choice: HashChoice<H>,
+}
+
+impl<E: Curve, H: Digest + Clone> RangeProof<E, H> {
+    /// Proves that every `values[j]` is in range `[0, 2^n)`
+    ///
+    /// Returns the proof and commitments `V_j = values[j] G + blindings[j] H`. Number of values must
+    /// be a power of two, and `n` must be one of 8, 16, 32, 64.
+    pub fn prove(
+        gens: &BulletproofGens<E>,
+        values: &[u64],
+        blindings: &[Scalar<E>],
+        n: usize,
+    ) -> Result<(Self, Vec<Point<E>>), RangeProofError> {
+        let mut transcript = Transcript::new(b"curv/bulletproofs-range-proof");
+        Self::prove_with_transcript(gens, values, blindings, n, &mut transcript)
+    }
+
+    /// Proves that a single value is in range `[0, 2^n)`
+    pub fn prove_single(
+        gens: &BulletproofGens<E>,
+        value: u64,
+        blinding: &Scalar<E>,
+        n: usize,
+    ) -> Result<(Self, Point<E>), RangeProofError> {
+        let (proof, mut commitments) =
+            Self::prove(gens, &[value], std::slice::from_ref(blinding), n)?;
+        Ok((proof, commitments.remove(0)))
+    }
+
+    /// Verifies that every commitment opens to a value in range `[0, 2^n)`
+    pub fn verify(
+        &self,
+        gens: &BulletproofGens<E>,
+        commitments: &[Point<E>],
+        n: usize,
+    ) -> Result<(), ProofError> {
+        let mut transcript = Transcript::new(b"curv/bulletproofs-range-proof");
+        self.verify_with_transcript(gens, commitments, n, &mut transcript)
+    }
+
+    /// Verifies proof produced by [prove_single](Self::prove_single)
+    pub fn verify_single(
+        &self,
+        gens: &BulletproofGens<E>,
+        commitment: &Point<E>,
+        n: usize,
+    ) -> Result<(), ProofError> {
+        self.verify(gens, std::slice::from_ref(commitment), n)
+    }
+
+    /// Verifies a batch of proofs at once, `proofs[i]` is verified against `commitments[i]`
+    ///
+    /// Verification equations of all proofs are combined with random weights and checked via
+    /// single multiscalar multiplication. If the batch is invalid, proofs are verified
+    /// individually to find out which of them failed.
+    ///
+    /// ## Panics
+    /// Panics if `proofs` and `commitments` have different lengths.
+    pub fn batch_verify(
+        gens: &BulletproofGens<E>,
+        proofs: &[Self],
+        commitments: &[Vec<Point<E>>],
+        n: usize,
+    ) -> Result<(), BatchVerificationError> {
+        assert_eq!(
+            proofs.len(),
+            commitments.len(),
+            "proofs and commitments must have the same length"
+        );
+        let mut equation = VerificationEquation::new(gens);
+        let combined = proofs.iter().zip(commitments).try_for_each(|(proof, v)| {
+            let mut transcript = Transcript::new(b"curv/bulletproofs-range-proof");
+            let weight = batch_verification_weight::<E>();
+            proof.append_to_equation(&mut equation, &weight, v, n, &mut transcript)
+        });
+        if combined.is_ok() && equation.is_satisfied(gens) {
+            Ok(())
+        } else {
+            BatchVerificationError::find_invalid_proofs(
+                proofs
+                    .iter()
+                    .zip(commitments)
+                    .map(|(proof, v)| proof.verify(gens, v, n)),
+            )
+        }
+    }
+
+    /// Proves that every `values[j]` is in range `[0, 2^n)`, deriving challenges from the
+    /// transcript
+    ///
+    /// Transcript has to be in the same state when [verifying](Self::verify_with_transcript) the
+    /// proof.
+    pub fn prove_with_transcript(
+        gens: &BulletproofGens<E>,
+        values: &[u64],
+        blindings: &[Scalar<E>],
+        n: usize,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(Self, Vec<Point<E>>), RangeProofError> {
+        let m = values.len();
+        check_parameters(gens.capacity(), n, m)?;
+        if blindings.len() != m {
+            return Err(RangeProofError::WrongNumberOfBlindings);
+        }
+        if n < 64 && values.iter().any(|v| v >> n != 0) {
+            return Err(RangeProofError::ValueOutOfRange);
+        }
+
+        let g = Point::<E>::generator();
+        let h = Point::<E>::base_point2();
+        let commitments: Vec<Point<E>> = values
+            .iter()
+            .zip(blindings)
+            .map(|(&v, gamma)| Point::multiscalar_mul([&Scalar::from(v), gamma], [g.as_point(), h]))
+            .collect();
+        append_statement(transcript, n, &commitments);
+
+        let nm = n * m;
+        let g_vec = &gens.g_vec[..nm];
+        let h_vec = &gens.h_vec[..nm];
+
+        let one = Scalar::<E>::from(1);
+        let a_l: Vec<Scalar<E>> = values
+            .iter()
+            .flat_map(|v| (0..n).map(move |i| Scalar::from((v >> i) & 1)))
+            .collect();
+        let a_r: Vec<Scalar<E>> = a_l.iter().map(|a| a - &one).collect();
+        let alpha = Scalar::random();
+        let a = Point::multiscalar_mul(
+            iter::once(&alpha).chain(&a_l).chain(&a_r),
+            iter::once(h).chain(g_vec).chain(h_vec),
+        );
+
+        let s_l: Vec<Scalar<E>> = (0..nm).map(|_| Scalar::random()).collect();
+        let s_r: Vec<Scalar<E>> = (0..nm).map(|_| Scalar::random()).collect();
+        let rho = Scalar::random();
+        let s = Point::multiscalar_mul(
+            iter::once(&rho).chain(&s_l).chain(&s_r),
+            iter::once(h).chain(g_vec).chain(h_vec),
+        );
+
+        transcript.append_point(b"A", &a);
+        transcript.append_point(b"S", &s);
+        let y: Scalar<E> = transcript.challenge_scalar(b"y");
+        let z: Scalar<E> = transcript.challenge_scalar(b"z");
+
+        let y_pow = powers(&y, nm);
+        let z_pow = powers(&z, m + 2);
+        let two_pow = powers(&Scalar::from(2), n);
+        let zs2: Vec<Scalar<E>> = (0..m)
+            .flat_map(|j| {
+                let z_j = &z_pow[j + 2];
+                two_pow.iter().map(move |p| z_j * p)
+            })
+            .collect();
+
+        let l0: Vec<Scalar<E>> = a_l.iter().map(|a| a - &z).collect();
+        let l1 = s_l;
+        let r0: Vec<Scalar<E>> = (0..nm)
+            .map(|i| &y_pow[i] * (&a_r[i] + &z) + &zs2[i])
+            .collect();
+        let r1: Vec<Scalar<E>> = (0..nm).map(|i| &y_pow[i] * &s_r[i]).collect();
+
+        let t1 = inner_product(&l0, &r1) + inner_product(&l1, &r0);
+        let t2 = inner_product(&l1, &r1);
+        let tau1 = Scalar::random();
+        let tau2 = Scalar::random();
+        let t1_commitment = Point::multiscalar_mul([&t1, &tau1], [g.as_point(), h]);
+        let t2_commitment = Point::multiscalar_mul([&t2, &tau2], [g.as_point(), h]);
+
+        transcript.append_point(b"T1", &t1_commitment);
+        transcript.append_point(b"T2", &t2_commitment);
+        let x: Scalar<E> = transcript.challenge_scalar(b"x");
+
+        let tau_x = &tau2 * &x * &x
+            + &tau1 * &x
+            + blindings
+                .iter()
+                .zip(&z_pow[2..])
+                .map(|(gamma, z_j)| gamma * z_j)
+                .sum::<Scalar<E>>();
+        let mu = &alpha + &rho * &x;
+        let l: Vec<Scalar<E>> = l0.iter().zip(&l1).map(|(l0, l1)| l0 + l1 * &x).collect();
+        let r: Vec<Scalar<E>> = r0.iter().zip(&r1).map(|(r0, r1)| r0 + r1 * &x).collect();
+        let t_hat = inner_product(&l, &r);
+
+        transcript.append_scalar(b"t_hat", &t_hat);
+        transcript.append_scalar(b"tau_x", &tau_x);
+        transcript.append_scalar(b"mu", &mu);
+        let w: Scalar<E> = transcript.challenge_scalar(b"w");
+        let q = g * &w;
+
+        let y_inv = y.invert().ok_or(RangeProofError::ZeroChallenge)?;
+        let h_prime: Vec<Point<E>> = h_vec
+            .iter()
+            .zip(powers(&y_inv, nm))
+            .map(|(h_i, y_inv_i)| h_i * y_inv_i)
+            .collect();
+        let ipp_proof = InnerProductProof::prove(transcript, &q, g_vec, &h_prime, &l, &r);
+
+        let proof = RangeProof {
+            a,
+            s,
+            t1: t1_commitment,
+            t2: t2_commitment,
+            tau_x,
+            mu,
+            t_hat,
+            ipp_proof,
+            hash_choice: HashChoice::new(),
+        };
+        Ok((proof, commitments))
+    }
+
+    /// Verifies the proof produced by [prove_with_transcript](Self::prove_with_transcript)
+    pub fn verify_with_transcript(
+        &self,
+        gens: &BulletproofGens<E>,
+        commitments: &[Point<E>],
+        n: usize,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let mut equation = VerificationEquation::new(gens);
+        self.append_to_equation(&mut equation, &Scalar::from(1), commitments, n, transcript)?;
+        if equation.is_satisfied(gens) {
+            Ok(())
+        } else {
+            Err(ProofError)
+        }
+    }
+
+    /// Adds verification equation of the proof multiplied by `weight` to `equation`
+    fn append_to_equation(
+        &self,
+        equation: &mut VerificationEquation<E>,
+        weight: &Scalar<E>,
+        commitments: &[Point<E>],
+        n: usize,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let m = commitments.len();
+        check_parameters(equation.capacity(), n, m).map_err(|_| ProofError)?;
+        let nm = n * m;
+
+        append_statement(transcript, n, commitments);
+        transcript.append_point(b"A", &self.a);
+        transcript.append_point(b"S", &self.s);
+        let y: Scalar<E> = transcript.challenge_scalar(b"y");
+        let z: Scalar<E> = transcript.challenge_scalar(b"z");
+        transcript.append_point(b"T1", &self.t1);
+        transcript.append_point(b"T2", &self.t2);
+        let x: Scalar<E> = transcript.challenge_scalar(b"x");
+        transcript.append_scalar(b"t_hat", &self.t_hat);
+        transcript.append_scalar(b"tau_x", &self.tau_x);
+        transcript.append_scalar(b"mu", &self.mu);
+        let w: Scalar<E> = transcript.challenge_scalar(b"w");
+        let ipp = self.ipp_proof.verification_scalars(nm, transcript)?;
+
+        let y_inv = y.invert().ok_or(ProofError)?;
+        let y_pow = powers(&y, nm);
+        let y_inv_pow = powers(&y_inv, nm);
+        let z_pow = powers(&z, m + 3);
+        let two_pow = powers(&Scalar::from(2), n);
+        let sum_two_pow: Scalar<E> = two_pow.iter().sum();
+        let sum_y_pow: Scalar<E> = y_pow.iter().sum();
+
+        // δ(y, z) = (z - z^2) <1, y^{nm}> - Σ_j z^{3+j} <1, 2^n>
+        let delta =
+            (&z - &z_pow[2]) * sum_y_pow - z_pow[3..].iter().sum::<Scalar<E>>() * &sum_two_pow;
+        // random weight combining polynomial check with inner product check
+        let c = batch_verification_weight::<E>();
+
+        for i in 0..nm {
+            // G_i: -z - a s_i
+            let g_i = -(&z + &self.ipp_proof.a * &ipp.s[i]);
+            // H_i: z + y^{-i} (z^{2+j} 2^k - b s_i^-1), where i = j n + k
+            let zs2 = &z_pow[2 + i / n] * &two_pow[i % n];
+            let h_i = &z + &y_inv_pow[i] * (zs2 - &self.ipp_proof.b * &ipp.s[nm - 1 - i]);
+            equation.g_scalars[i] = &equation.g_scalars[i] + weight * g_i;
+            equation.h_scalars[i] = &equation.h_scalars[i] + weight * h_i;
+        }
+
+        let ab = &self.ipp_proof.a * &self.ipp_proof.b;
+        let g = w * (&self.t_hat - ab) + &c * (delta - &self.t_hat);
+        let h = -(&self.mu + &c * &self.tau_x);
+        equation.g = &equation.g + weight * g;
+        equation.h = &equation.h + weight * h;
+
+        equation.push(weight.clone(), &self.a);
+        equation.push(weight * &x, &self.s);
+        equation.push(weight * &c * &x, &self.t1);
+        equation.push(weight * &c * &x * &x, &self.t2);
+        for (v, z_j) in commitments.iter().zip(&z_pow[2..]) {
+            equation.push(weight * &c * z_j, v);
+        }
+        for (l, u_sq) in self.ipp_proof.l_vec.iter().zip(ipp.challenges_sq) {
+            equation.push(weight * u_sq, l);
+        }
+        for (r, u_inv_sq) in self.ipp_proof.r_vec.iter().zip(ipp.challenges_inv_sq) {
+            equation.push(weight * u_inv_sq, r);
+        }
+        Ok(())
+    }
+}
+
+/// Linear combination of points that must be equal to zero for valid proofs
+struct VerificationEquation<E: Curve> {
+    g_scalars: Vec<Scalar<E>>,
+    h_scalars: Vec<Scalar<E>>,
+    /// Scalar of `G` (generator)
+    g: Scalar<E>,
+    /// Scalar of `H` (base point 2)
+    h: Scalar<E>,
+    scalars: Vec<Scalar<E>>,
+    points: Vec<Point<E>>,
+}
+
+impl<E: Curve> VerificationEquation<E> {
+    fn new(gens: &BulletproofGens<E>) -> Self {
+        Self {
+            g_scalars: vec![Scalar::zero(); gens.capacity()],
+            h_scalars: vec![Scalar::zero(); gens.capacity()],
+            g: Scalar::zero(),
+            h: Scalar::zero(),
+            scalars: vec![],
+            points: vec![],
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.g_scalars.len()
+    }
+
+    fn push(&mut self, scalar: Scalar<E>, point: &Point<E>) {
+        self.scalars.push(scalar);
+        self.points.push(point.clone());
+    }
+
+    fn is_satisfied(&self, gens: &BulletproofGens<E>) -> bool {
+        let scalars = self
+            .g_scalars
+            .iter()
+            .chain(&self.h_scalars)
+            .chain([&self.g, &self.h])
+            .chain(&self.scalars);
+        let points = gens
+            .g_vec
+            .iter()
+            .chain(&gens.h_vec)
+            .chain([Point::generator().as_point(), Point::base_point2()])
+            .chain(&self.points);
+        Point::multiscalar_mul(scalars, points).is_zero()
+    }
+}
+
+fn check_parameters(capacity: usize, n: usize, m: usize) -> Result<(), RangeProofError> {
+    if !matches!(n, 8 | 16 | 32 | 64) {
+        return Err(RangeProofError::InvalidBitsize);
+    }
+    if m == 0 || !m.is_power_of_two() {
+        return Err(RangeProofError::InvalidAggregation);
+    }
+    if capacity < n * m {
+        return Err(RangeProofError::NotEnoughGenerators);
+    }
+    Ok(())
+}
+
+fn append_statement<E: Curve, H: Digest + Clone>(
+    transcript: &mut Transcript<H>,
+    n: usize,
+    commitments: &[Point<E>],
+) {
+    transcript.append_message(b"proof", b"curv/bulletproofs-range-proof");
+    transcript.append_u64(b"n", n as u64);
+    transcript.append_points(b"V", commitments);
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RangeProofError {
+    #[error("bit size must be one of 8, 16, 32, 64")]
+    InvalidBitsize,
+    #[error("number of values must be a power of two")]
+    InvalidAggregation,
+    #[error("not enough generators for given bit size and number of values")]
+    NotEnoughGenerators,
+    #[error("number of blindings doesn't match number of values")]
+    WrongNumberOfBlindings,
+    #[error("value is out of range")]
+    ValueOutOfRange,
+    #[error("challenge is zero")]
+    ZeroChallenge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::commitments::pedersen_commitment::PedersenCommitment;
+    use crate::cryptographic_primitives::commitments::traits::Commitment;
+    use crate::elliptic::curves::HashToCurve;
+    use crate::elliptic::curves::Secp256k1;
+    use crate::test_for_all_curves_and_hashes;
+    use crate::BigInt;
+    use sha2::Sha256;
+
+    test_for_all_curves_and_hashes!(test_single_range_proof);
+    fn test_single_range_proof<E: HashToCurve, H: Digest + Clone>() {
+        let gens = BulletproofGens::<E>::new(16);
+        for &(value, n) in &[(0u64, 8), (255, 8), (12345, 16)] {
+            let blinding = Scalar::random();
+            let (proof, commitment) =
+                RangeProof::<E, H>::prove_single(&gens, value, &blinding, n).unwrap();
+            let expected = PedersenCommitment::<E>::create_commitment_with_user_defined_randomness(
+                &BigInt::from(value),
+                &blinding.to_bigint(),
+            );
+            assert_eq!(commitment, expected);
+            assert!(proof.verify_single(&gens, &commitment, n).is_ok());
+
+            let other = &commitment + Point::generator();
+            assert!(proof.verify_single(&gens, &other, n).is_err());
+        }
+        assert_eq!(
+            RangeProof::<E, H>::prove_single(&gens, 256, &Scalar::random(), 8).err(),
+            Some(RangeProofError::ValueOutOfRange)
+        );
+    }
+
+    test_for_all_curves_and_hashes!(test_aggregated_range_proof);
+    fn test_aggregated_range_proof<E: HashToCurve, H: Digest + Clone>() {
+        let gens = BulletproofGens::<E>::new(32);
+        let values = [7u64, 1 << 5, 0, 255];
+        let blindings: Vec<Scalar<E>> = (0..4).map(|_| Scalar::random()).collect();
+        let (proof, commitments) =
+            RangeProof::<E, H>::prove(&gens, &values, &blindings, 8).unwrap();
+        assert_eq!(proof.ipp_proof.l_vec.len(), 5);
+        assert!(proof.verify(&gens, &commitments, 8).is_ok());
+        assert!(proof.verify(&gens, &commitments, 16).is_err());
+        assert!(proof.verify(&gens, &commitments[..2], 8).is_err());
+
+        let mut swapped = commitments.clone();
+        swapped.swap(0, 1);
+        assert!(proof.verify(&gens, &swapped, 8).is_err());
+
+        let mut transcript = Transcript::<H>::new(b"session");
+        let (proof, commitments) = RangeProof::<E, H>::prove_with_transcript(
+            &gens,
+            &values[..2],
+            &blindings[..2],
+            8,
+            &mut transcript,
+        )
+        .unwrap();
+        assert!(proof.verify(&gens, &commitments, 8).is_err());
+        let mut transcript = Transcript::<H>::new(b"session");
+        assert!(proof
+            .verify_with_transcript(&gens, &commitments, 8, &mut transcript)
+            .is_ok());
+    }
+
+    test_for_all_curves_and_hashes!(test_batch_verification);
+    fn test_batch_verification<E: HashToCurve, H: Digest + Clone>() {
+        let gens = BulletproofGens::<E>::new(16);
+        let mut proofs = vec![];
+        let mut commitments = vec![];
+        for m in [1usize, 2, 1] {
+            let values: Vec<u64> = (0..m).map(|_| 100 + m as u64).collect();
+            let blindings: Vec<Scalar<E>> = (0..m).map(|_| Scalar::random()).collect();
+            let (proof, v) = RangeProof::<E, H>::prove(&gens, &values, &blindings, 8).unwrap();
+            proofs.push(proof);
+            commitments.push(v);
+        }
+        assert!(RangeProof::batch_verify(&gens, &proofs, &commitments, 8).is_ok());
+
+        commitments[1][0] = &commitments[1][0] + Point::generator();
+        proofs[2].t_hat = &proofs[2].t_hat + Scalar::from(1);
+        assert_eq!(
+            RangeProof::batch_verify(&gens, &proofs, &commitments, 8),
+            Err(BatchVerificationError {
+                invalid_proofs: vec![1, 2]
+            })
+        );
+    }
+
+    #[test]
+    fn test_64_bits_range_proof() {
+        let gens = BulletproofGens::<Secp256k1>::new(64);
+        let blinding = Scalar::random();
+        let (proof, commitment) =
+            RangeProof::<_, Sha256>::prove_single(&gens, u64::MAX, &blinding, 64).unwrap();
+        assert!(proof.verify_single(&gens, &commitment, 64).is_ok());
+        assert!(proof.verify_single(&gens, &commitment, 32).is_err());
+    }
+
+    #[test]
+    fn out_of_range_commitment_cant_be_proven() {
+        // Commitment to -1 = 2^n - 1 + (q - 2^n): prover has to use bits of a different value
+        let gens = BulletproofGens::<Secp256k1>::new(8);
+        let blinding = Scalar::random();
+        let minus_one =
+            Point::generator() * -Scalar::<Secp256k1>::from(1) + Point::base_point2() * &blinding;
+        let (proof, _) = RangeProof::<_, Sha256>::prove_single(&gens, 255, &blinding, 8).unwrap();
+        assert!(proof.verify_single(&gens, &minus_one, 8).is_err());
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let gens = BulletproofGens::<Secp256k1>::new(16);
+        let blindings = [Scalar::random(), Scalar::random()];
+        let (proof, commitments) =
+            RangeProof::<_, Sha256>::prove(&gens, &[1, 2], &blindings, 8).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let parsed: RangeProof<Secp256k1, Sha256> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        assert!(parsed.verify(&gens, &commitments, 8).is_ok());
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        let gens = BulletproofGens::<Secp256k1>::new(32);
+        let prove = |values: &[u64], n| {
+            let blindings: Vec<_> = values.iter().map(|_| Scalar::random()).collect();
+            RangeProof::<_, Sha256>::prove(&gens, values, &blindings, n).err()
+        };
+        assert_eq!(prove(&[1], 7), Some(RangeProofError::InvalidBitsize));
+        assert_eq!(
+            prove(&[1, 2, 3], 8),
+            Some(RangeProofError::InvalidAggregation)
+        );
+        assert_eq!(prove(&[], 8), Some(RangeProofError::InvalidAggregation));
+        assert_eq!(
+            prove(&[1, 2], 32),
+            Some(RangeProofError::NotEnoughGenerators)
+        );
+        assert_eq!(
+            RangeProof::<_, Sha256>::prove(&gens, &[1, 2], &[Scalar::random()], 8).err(),
+            Some(RangeProofError::WrongNumberOfBlindings)
+        );
+    }
+}
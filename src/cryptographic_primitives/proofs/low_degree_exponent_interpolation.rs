@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::cryptographic_primitives::hashing::DigestExt;
+use crate::cryptographic_primitives::proofs::transcript::Transcript;
 use crate::cryptographic_primitives::proofs::ProofError;
 use crate::cryptographic_primitives::secret_sharing::Polynomial;
 use crate::elliptic::curves::{Curve, Point, Scalar};
@@ -76,10 +77,58 @@ impl<E: Curve, H: Digest + Clone> LdeiProof<E, H> {
     /// The prover samples `u(X) ← Z_q[X]` with `deg(u) ≤ d` and computes `a_i = g_i^u(alpha_i)`
     /// for all `i ∈ [m]`, in addition to `e = H(g_1,...,g_m,x_1,...,x_m,a_1,...,a_m)`, and
     /// `z(X) = u(X) − e · w(X)`. The proof is `(a_1,...,a_m,e,z)`.
-    #[allow(clippy::many_single_char_names)]
     pub fn prove(
         witness: &LdeiWitness<E>,
         statement: &LdeiStatement<E>,
+    ) -> Result<LdeiProof<E, H>, InvalidLdeiStatement> {
+        Self::prove_with_challenge(witness, statement, |a| Self::hash_challenge(statement, a))
+    }
+
+    /// Verifies correctness of a statement
+    ///
+    /// ## Protocol
+    ///
+    /// The verifier checks that `e = H(g1,...,gm,x1,...,xm,a1,...,am)`, that
+    /// `deg(z) ≤ d`, and that `a_i = g_i^z(αlpha_i) * x_i^e` for all i, and accepts if all of this is
+    /// true, otherwise rejects.
+    pub fn verify(&self, statement: &LdeiStatement<E>) -> Result<(), ProofError>
+    where
+        H: Digest + Clone,
+    {
+        let e = Self::hash_challenge(statement, &self.a);
+        self.verify_challenge(statement, &e)
+    }
+
+    /// Proves correctness of [LdeiStatement], deriving the challenge from the transcript
+    ///
+    /// Same as [prove](Self::prove), but `e` is extracted from the transcript after appending the
+    /// whole statement (including `alpha` and `d`) and `a_1,...,a_m` to it. Transcript has to be
+    /// in the same state when [verifying](Self::verify_with_transcript) the proof.
+    pub fn prove_with_transcript(
+        witness: &LdeiWitness<E>,
+        statement: &LdeiStatement<E>,
+        transcript: &mut Transcript<H>,
+    ) -> Result<LdeiProof<E, H>, InvalidLdeiStatement> {
+        Self::prove_with_challenge(witness, statement, |a| {
+            Self::transcript_challenge(transcript, statement, a)
+        })
+    }
+
+    /// Verifies the proof produced by [prove_with_transcript](Self::prove_with_transcript)
+    pub fn verify_with_transcript(
+        &self,
+        statement: &LdeiStatement<E>,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let e = Self::transcript_challenge(transcript, statement, &self.a);
+        self.verify_challenge(statement, &e)
+    }
+
+    #[allow(clippy::many_single_char_names)]
+    fn prove_with_challenge(
+        witness: &LdeiWitness<E>,
+        statement: &LdeiStatement<E>,
+        challenge: impl FnOnce(&[Point<E>]) -> Scalar<E>,
     ) -> Result<LdeiProof<E, H>, InvalidLdeiStatement> {
         if statement.alpha.len() != statement.g.len() {
             return Err(InvalidLdeiStatement::AlphaLengthDoesntMatchG);
@@ -109,11 +158,7 @@ impl<E: Curve, H: Digest + Clone> LdeiProof<E, H> {
             .map(|(g, a)| g * u.evaluate(a))
             .collect();
 
-        let e = H::new()
-            .chain_points(&statement.g)
-            .chain_points(&statement.x)
-            .chain_points(&a)
-            .result_scalar();
+        let e = challenge(&a);
 
         let z = &u - &(&witness.w * &e);
 
@@ -125,24 +170,12 @@ impl<E: Curve, H: Digest + Clone> LdeiProof<E, H> {
         })
     }
 
-    /// Verifies correctness of a statement
-    ///
-    /// ## Protocol
-    ///
-    /// The verifier checks that `e = H(g1,...,gm,x1,...,xm,a1,...,am)`, that
-    /// `deg(z) ≤ d`, and that `a_i = g_i^z(αlpha_i) * x_i^e` for all i, and accepts if all of this is
-    /// true, otherwise rejects.
-    pub fn verify(&self, statement: &LdeiStatement<E>) -> Result<(), ProofError>
-    where
-        H: Digest + Clone,
-    {
-        let e = H::new()
-            .chain_points(&statement.g)
-            .chain_points(&statement.x)
-            .chain_points(&self.a)
-            .result_scalar();
-
-        if e != self.e {
+    fn verify_challenge(
+        &self,
+        statement: &LdeiStatement<E>,
+        e: &Scalar<E>,
+    ) -> Result<(), ProofError> {
+        if e != &self.e {
             return Err(ProofError);
         }
         if self.z.degree() > statement.d.into() {
@@ -154,7 +187,7 @@ impl<E: Curve, H: Digest + Clone> LdeiProof<E, H> {
             .iter()
             .zip(&statement.alpha)
             .zip(&statement.x)
-            .map(|((g, a), x)| g * self.z.evaluate(a) + x * &e)
+            .map(|((g, a), x)| Point::multiscalar_mul([&self.z.evaluate(a), e], [g, x]))
             .collect();
 
         if self.a == expected_a {
@@ -163,6 +196,28 @@ impl<E: Curve, H: Digest + Clone> LdeiProof<E, H> {
             Err(ProofError)
         }
     }
+
+    fn hash_challenge(statement: &LdeiStatement<E>, a: &[Point<E>]) -> Scalar<E> {
+        H::new()
+            .chain_points(&statement.g)
+            .chain_points(&statement.x)
+            .chain_points(a)
+            .result_scalar()
+    }
+
+    fn transcript_challenge(
+        transcript: &mut Transcript<H>,
+        statement: &LdeiStatement<E>,
+        a: &[Point<E>],
+    ) -> Scalar<E> {
+        transcript.append_message(b"proof", b"curv/ldei-proof");
+        transcript.append_scalars(b"alpha", &statement.alpha);
+        transcript.append_points(b"g", &statement.g);
+        transcript.append_points(b"x", &statement.x);
+        transcript.append_u64(b"d", statement.d.into());
+        transcript.append_points(b"a", a);
+        transcript.challenge_scalar(b"challenge")
+    }
 }
 
 /// Indicates that statement is not valid or doesn't match a witness
@@ -215,4 +270,35 @@ mod tests {
         let proof = LdeiProof::<_, H>::prove(&witness, &statement).expect("failed to prove");
         proof.verify(&statement).expect("failed to validate proof");
     }
+
+    test_for_all_curves_and_hashes!(correctly_proofs_with_transcript);
+    fn correctly_proofs_with_transcript<E: Curve, H: Digest + Clone>() {
+        let transcript = |session_id: &[u8]| {
+            let mut t = Transcript::<H>::new(b"test");
+            t.append_message(b"session_id", session_id);
+            t
+        };
+        let d = 5;
+        let poly = Polynomial::<E>::sample_exact(5);
+        let witness = LdeiWitness { w: poly };
+
+        let alpha: Vec<Scalar<E>> = (1..=10).map(Scalar::from).collect();
+        let g: Vec<Point<E>> = iter::repeat_with(Scalar::random)
+            .map(|x| Point::generator() * x)
+            .take(10)
+            .collect();
+
+        let statement = LdeiStatement::new(&witness, alpha, g, d).unwrap();
+
+        let proof =
+            LdeiProof::<_, H>::prove_with_transcript(&witness, &statement, &mut transcript(b"1"))
+                .expect("failed to prove");
+        proof
+            .verify_with_transcript(&statement, &mut transcript(b"1"))
+            .expect("failed to validate proof");
+        assert!(proof
+            .verify_with_transcript(&statement, &mut transcript(b"2"))
+            .is_err());
+        assert!(proof.verify(&statement).is_err());
+    }
 }
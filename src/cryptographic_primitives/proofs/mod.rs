@@ -8,13 +8,19 @@
 use std::error::Error;
 use std::fmt;
 
+use crate::arithmetic::*;
+use crate::elliptic::curves::{Curve, Scalar};
+
+pub mod bulletproofs;
 pub mod low_degree_exponent_interpolation;
 pub mod sigma_correct_homomorphic_elgamal_enc;
 pub mod sigma_correct_homomorphic_elgamal_encryption_of_dlog;
 pub mod sigma_dlog;
 pub mod sigma_ec_ddh;
+pub mod sigma_protocol;
 pub mod sigma_valid_pedersen;
 pub mod sigma_valid_pedersen_blind;
+pub mod transcript;
 
 #[derive(Debug, Clone, Copy)]
 pub struct ProofError;
@@ -30,3 +36,45 @@ impl Error for ProofError {
         "Error while verifying"
     }
 }
+
+/// Batch verification failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchVerificationError {
+    /// Indices of invalid proofs in the batch
+    pub invalid_proofs: Vec<usize>,
+}
+
+impl BatchVerificationError {
+    /// Checks proofs individually after batch verification failed, collecting indexes of invalid
+    /// ones
+    pub(crate) fn find_invalid_proofs<Err>(
+        results: impl IntoIterator<Item = Result<(), Err>>,
+    ) -> Result<(), Self> {
+        let invalid_proofs: Vec<usize> = results
+            .into_iter()
+            .enumerate()
+            .filter(|(_, result)| result.is_err())
+            .map(|(i, _)| i)
+            .collect();
+        if invalid_proofs.is_empty() {
+            Ok(())
+        } else {
+            Err(Self { invalid_proofs })
+        }
+    }
+}
+
+impl fmt::Display for BatchVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid proofs at indexes {:?}", self.invalid_proofs)
+    }
+}
+
+impl Error for BatchVerificationError {}
+
+/// Samples random 128 bits weight for random linear combination in batch verification
+///
+/// Invalid proof passes batch verification with probability at most `2^-128`.
+pub(crate) fn batch_verification_weight<E: Curve>() -> Scalar<E> {
+    Scalar::from_bigint(&BigInt::sample(128))
+}
@@ -13,6 +13,7 @@ use crate::cryptographic_primitives::hashing::DigestExt;
 use crate::elliptic::curves::{Curve, Point, Scalar};
 use crate::marker::HashChoice;
 
+use super::transcript::Transcript;
 use super::ProofError;
 
 /// This is a proof of knowledge that a pair of group elements {D, E}
@@ -53,6 +54,43 @@ impl<E: Curve, H: Digest + Clone> HomoELGamalProof<E, H> {
     pub fn prove(
         w: &HomoElGamalWitness<E>,
         delta: &HomoElGamalStatement<E>,
+    ) -> HomoELGamalProof<E, H> {
+        Self::prove_with_challenge(w, delta, |T, A3| Self::hash_challenge(delta, T, A3))
+    }
+
+    pub fn verify(&self, delta: &HomoElGamalStatement<E>) -> Result<(), ProofError> {
+        let e = Self::hash_challenge(delta, &self.T, &self.A3);
+        self.verify_challenge(delta, &e)
+    }
+
+    /// Proves the statement, deriving the challenge from the transcript
+    ///
+    /// Statement and commitments are appended to the transcript, so it has to be in the same state
+    /// when [verifying](Self::verify_with_transcript) the proof.
+    pub fn prove_with_transcript(
+        w: &HomoElGamalWitness<E>,
+        delta: &HomoElGamalStatement<E>,
+        transcript: &mut Transcript<H>,
+    ) -> HomoELGamalProof<E, H> {
+        Self::prove_with_challenge(w, delta, |T, A3| {
+            Self::transcript_challenge(transcript, delta, T, A3)
+        })
+    }
+
+    /// Verifies the proof produced by [prove_with_transcript](Self::prove_with_transcript)
+    pub fn verify_with_transcript(
+        &self,
+        delta: &HomoElGamalStatement<E>,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let e = Self::transcript_challenge(transcript, delta, &self.T, &self.A3);
+        self.verify_challenge(delta, &e)
+    }
+
+    fn prove_with_challenge(
+        w: &HomoElGamalWitness<E>,
+        delta: &HomoElGamalStatement<E>,
+        challenge: impl FnOnce(&Point<E>, &Point<E>) -> Scalar<E>,
     ) -> HomoELGamalProof<E, H> {
         let s1: Scalar<E> = Scalar::random();
         let s2: Scalar<E> = Scalar::random();
@@ -60,15 +98,7 @@ impl<E: Curve, H: Digest + Clone> HomoELGamalProof<E, H> {
         let A2 = &delta.Y * &s2;
         let A3 = &delta.G * &s2;
         let T = A1 + A2;
-        let e = H::new()
-            .chain_point(&T)
-            .chain_point(&A3)
-            .chain_point(&delta.G)
-            .chain_point(&delta.H)
-            .chain_point(&delta.Y)
-            .chain_point(&delta.D)
-            .chain_point(&delta.E)
-            .result_scalar();
+        let e = challenge(&T, &A3);
         // dealing with zero field element
         let z1 = &s1 + &w.x * &e;
         let z2 = s2 + &w.r * e;
@@ -80,26 +110,51 @@ impl<E: Curve, H: Digest + Clone> HomoELGamalProof<E, H> {
             hash_choice: HashChoice::new(),
         }
     }
-    pub fn verify(&self, delta: &HomoElGamalStatement<E>) -> Result<(), ProofError> {
-        let e = H::new()
-            .chain_point(&self.T)
-            .chain_point(&self.A3)
-            .chain_point(&delta.G)
-            .chain_point(&delta.H)
-            .chain_point(&delta.Y)
-            .chain_point(&delta.D)
-            .chain_point(&delta.E)
-            .result_scalar();
+
+    fn verify_challenge(
+        &self,
+        delta: &HomoElGamalStatement<E>,
+        e: &Scalar<E>,
+    ) -> Result<(), ProofError> {
         let z1H_plus_z2Y = &delta.H * &self.z1 + &delta.Y * &self.z2;
-        let T_plus_eD = &self.T + &delta.D * &e;
+        let T_plus_eD = &self.T + &delta.D * e;
         let z2G = &delta.G * &self.z2;
-        let A3_plus_eE = &self.A3 + &delta.E * &e;
+        let A3_plus_eE = &self.A3 + &delta.E * e;
         if z1H_plus_z2Y == T_plus_eD && z2G == A3_plus_eE {
             Ok(())
         } else {
             Err(ProofError)
         }
     }
+
+    fn hash_challenge(delta: &HomoElGamalStatement<E>, T: &Point<E>, A3: &Point<E>) -> Scalar<E> {
+        H::new()
+            .chain_point(T)
+            .chain_point(A3)
+            .chain_point(&delta.G)
+            .chain_point(&delta.H)
+            .chain_point(&delta.Y)
+            .chain_point(&delta.D)
+            .chain_point(&delta.E)
+            .result_scalar()
+    }
+
+    fn transcript_challenge(
+        transcript: &mut Transcript<H>,
+        delta: &HomoElGamalStatement<E>,
+        T: &Point<E>,
+        A3: &Point<E>,
+    ) -> Scalar<E> {
+        transcript.append_message(b"proof", b"curv/homo-elgamal-proof");
+        transcript.append_point(b"G", &delta.G);
+        transcript.append_point(b"H", &delta.H);
+        transcript.append_point(b"Y", &delta.Y);
+        transcript.append_point(b"D", &delta.D);
+        transcript.append_point(b"E", &delta.E);
+        transcript.append_point(b"T", T);
+        transcript.append_point(b"A3", A3);
+        transcript.challenge_scalar(b"challenge")
+    }
 }
 
 #[cfg(test)]
@@ -153,6 +208,43 @@ mod tests {
         assert!(proof.verify(&delta).is_ok());
     }
 
+    test_for_all_curves_and_hashes!(test_homo_elgamal_with_transcript);
+    fn test_homo_elgamal_with_transcript<E: Curve, H: Digest + Clone>() {
+        let transcript = |session_id: &[u8]| {
+            let mut t = Transcript::<H>::new(b"test");
+            t.append_message(b"session_id", session_id);
+            t
+        };
+        let witness = HomoElGamalWitness {
+            r: Scalar::random(),
+            x: Scalar::random(),
+        };
+        let G = Point::<E>::generator();
+        let y = Scalar::random();
+        let Y = G * y;
+        let D = G * &witness.x + &Y * &witness.r;
+        let E = G * &witness.r;
+        let delta = HomoElGamalStatement {
+            G: G.to_point(),
+            H: G.to_point(),
+            Y,
+            D,
+            E,
+        };
+        let proof = HomoELGamalProof::<E, H>::prove_with_transcript(
+            &witness,
+            &delta,
+            &mut transcript(b"1"),
+        );
+        assert!(proof
+            .verify_with_transcript(&delta, &mut transcript(b"1"))
+            .is_ok());
+        assert!(proof
+            .verify_with_transcript(&delta, &mut transcript(b"2"))
+            .is_err());
+        assert!(proof.verify(&delta).is_err());
+    }
+
     test_for_all_curves_and_hashes!(test_wrong_homo_elgamal);
     fn test_wrong_homo_elgamal<E: Curve, H: Digest + Clone>() {
         // test for E = (r+1)G
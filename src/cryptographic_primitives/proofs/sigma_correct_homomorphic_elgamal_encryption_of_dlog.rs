@@ -12,6 +12,7 @@ use crate::cryptographic_primitives::hashing::{Digest, DigestExt};
 use crate::elliptic::curves::{Curve, Point, Scalar};
 use crate::marker::HashChoice;
 
+use super::transcript::Transcript;
 use super::ProofError;
 
 /// This is a proof of knowledge that a pair of group elements {D, E}
@@ -53,15 +54,52 @@ impl<E: Curve, H: Digest + Clone> HomoELGamalDlogProof<E, H> {
     pub fn prove(
         w: &HomoElGamalDlogWitness<E>,
         delta: &HomoElGamalDlogStatement<E>,
+    ) -> HomoELGamalDlogProof<E, H> {
+        Self::prove_with_challenge(w, delta, |A1, A2, A3| {
+            Self::hash_challenge(delta, A1, A2, A3)
+        })
+    }
+
+    pub fn verify(&self, delta: &HomoElGamalDlogStatement<E>) -> Result<(), ProofError> {
+        let e = Self::hash_challenge(delta, &self.A1, &self.A2, &self.A3);
+        self.verify_challenge(delta, &e)
+    }
+
+    /// Proves the statement, deriving the challenge from the transcript
+    ///
+    /// Statement and commitments are appended to the transcript, so it has to be in the same state
+    /// when [verifying](Self::verify_with_transcript) the proof.
+    pub fn prove_with_transcript(
+        w: &HomoElGamalDlogWitness<E>,
+        delta: &HomoElGamalDlogStatement<E>,
+        transcript: &mut Transcript<H>,
+    ) -> HomoELGamalDlogProof<E, H> {
+        Self::prove_with_challenge(w, delta, |A1, A2, A3| {
+            Self::transcript_challenge(transcript, delta, A1, A2, A3)
+        })
+    }
+
+    /// Verifies the proof produced by [prove_with_transcript](Self::prove_with_transcript)
+    pub fn verify_with_transcript(
+        &self,
+        delta: &HomoElGamalDlogStatement<E>,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let e = Self::transcript_challenge(transcript, delta, &self.A1, &self.A2, &self.A3);
+        self.verify_challenge(delta, &e)
+    }
+
+    fn prove_with_challenge(
+        w: &HomoElGamalDlogWitness<E>,
+        delta: &HomoElGamalDlogStatement<E>,
+        challenge: impl FnOnce(&Point<E>, &Point<E>, &Point<E>) -> Scalar<E>,
     ) -> HomoELGamalDlogProof<E, H> {
         let s1 = Scalar::<E>::random();
         let s2 = Scalar::<E>::random();
         let A1 = &delta.G * &s1;
         let A2 = &delta.Y * &s2;
         let A3 = &delta.G * &s2;
-        let e = H::new()
-            .chain_points([&A1, &A2, &A3, &delta.G, &delta.Y, &delta.D, &delta.E])
-            .result_scalar();
+        let e = challenge(&A1, &A2, &A3);
         let z1 = &s1 + &e * &w.x;
         let z2 = &s2 + e * &w.r;
         HomoELGamalDlogProof {
@@ -74,17 +112,16 @@ impl<E: Curve, H: Digest + Clone> HomoELGamalDlogProof<E, H> {
         }
     }
 
-    pub fn verify(&self, delta: &HomoElGamalDlogStatement<E>) -> Result<(), ProofError> {
-        let e = H::new()
-            .chain_points([
-                &self.A1, &self.A2, &self.A3, &delta.G, &delta.Y, &delta.D, &delta.E,
-            ])
-            .result_scalar();
+    fn verify_challenge(
+        &self,
+        delta: &HomoElGamalDlogStatement<E>,
+        e: &Scalar<E>,
+    ) -> Result<(), ProofError> {
         let z1G = &delta.G * &self.z1;
         let z2Y = &delta.Y * &self.z2;
         let z2G = &delta.G * &self.z2;
-        let A1_plus_eQ = &self.A1 + &delta.Q * &e;
-        let A3_plus_eE = &self.A3 + &delta.E * &e;
+        let A1_plus_eQ = &self.A1 + &delta.Q * e;
+        let A3_plus_eE = &self.A3 + &delta.E * e;
         let D_minus_Q = &delta.D - &delta.Q;
         let A2_plus_eDmQ = self.A2.clone() + D_minus_Q * e;
         if z1G == A1_plus_eQ && z2G == A3_plus_eE && z2Y == A2_plus_eDmQ {
@@ -93,6 +130,36 @@ impl<E: Curve, H: Digest + Clone> HomoELGamalDlogProof<E, H> {
             Err(ProofError)
         }
     }
+
+    fn hash_challenge(
+        delta: &HomoElGamalDlogStatement<E>,
+        A1: &Point<E>,
+        A2: &Point<E>,
+        A3: &Point<E>,
+    ) -> Scalar<E> {
+        H::new()
+            .chain_points([A1, A2, A3, &delta.G, &delta.Y, &delta.D, &delta.E])
+            .result_scalar()
+    }
+
+    fn transcript_challenge(
+        transcript: &mut Transcript<H>,
+        delta: &HomoElGamalDlogStatement<E>,
+        A1: &Point<E>,
+        A2: &Point<E>,
+        A3: &Point<E>,
+    ) -> Scalar<E> {
+        transcript.append_message(b"proof", b"curv/homo-elgamal-dlog-proof");
+        transcript.append_point(b"G", &delta.G);
+        transcript.append_point(b"Y", &delta.Y);
+        transcript.append_point(b"Q", &delta.Q);
+        transcript.append_point(b"D", &delta.D);
+        transcript.append_point(b"E", &delta.E);
+        transcript.append_point(b"A1", A1);
+        transcript.append_point(b"A2", A2);
+        transcript.append_point(b"A3", A3);
+        transcript.challenge_scalar(b"challenge")
+    }
 }
 
 #[cfg(test)]
@@ -123,6 +190,43 @@ mod tests {
         assert!(proof.verify(&delta).is_ok());
     }
 
+    test_for_all_curves_and_hashes!(test_homo_elgamal_with_transcript);
+    fn test_homo_elgamal_with_transcript<E: Curve, H: Digest + Clone>() {
+        let transcript = |session_id: &[u8]| {
+            let mut t = Transcript::<H>::new(b"test");
+            t.append_message(b"session_id", session_id);
+            t
+        };
+        let witness = HomoElGamalDlogWitness {
+            r: Scalar::random(),
+            x: Scalar::random(),
+        };
+        let G = Point::<E>::generator();
+        let Y = G * Scalar::random();
+        let D = G * &witness.x + &Y * &witness.r;
+        let E = G * &witness.r;
+        let Q = G * &witness.x;
+        let delta = HomoElGamalDlogStatement {
+            G: G.to_point(),
+            Y,
+            Q,
+            D,
+            E,
+        };
+        let proof = HomoELGamalDlogProof::<E, H>::prove_with_transcript(
+            &witness,
+            &delta,
+            &mut transcript(b"1"),
+        );
+        assert!(proof
+            .verify_with_transcript(&delta, &mut transcript(b"1"))
+            .is_ok());
+        assert!(proof
+            .verify_with_transcript(&delta, &mut transcript(b"2"))
+            .is_err());
+        assert!(proof.verify(&delta).is_err());
+    }
+
     // TODO: add more fail scenarios
     test_for_all_curves_and_hashes!(test_wrong_homo_elgamal);
     fn test_wrong_homo_elgamal<E: Curve, H: Digest + Clone>() {
@@ -11,7 +11,9 @@ use crate::cryptographic_primitives::hashing::{Digest, DigestExt};
 use crate::elliptic::curves::{Curve, Point, Scalar};
 use crate::marker::HashChoice;
 
-use super::ProofError;
+use super::sigma_protocol::SigmaProtocol;
+use super::transcript::Transcript;
+use super::{batch_verification_weight, BatchVerificationError, ProofError};
 
 /// This is implementation of Schnorr's identification protocol for elliptic curve groups or a
 /// sigma protocol for Proof of knowledge of the discrete log of an Elliptic-curve point:
@@ -35,21 +37,80 @@ pub struct DLogProof<E: Curve, H: Digest + Clone> {
 
 impl<E: Curve, H: Digest + Clone> DLogProof<E, H> {
     pub fn prove(sk: &Scalar<E>) -> DLogProof<E, H> {
-        let generator = Point::<E>::generator();
+        Self::prove_with_challenge(sk, |pk, pk_t_rand_commitment| {
+            Self::hash_challenge(pk, pk_t_rand_commitment)
+        })
+    }
 
-        let sk_t_rand_commitment = Scalar::random();
-        let pk_t_rand_commitment = generator * &sk_t_rand_commitment;
+    pub fn verify(proof: &DLogProof<E, H>) -> Result<(), ProofError> {
+        let challenge = Self::hash_challenge(&proof.pk, &proof.pk_t_rand_commitment);
+        proof.verify_challenge(&challenge)
+    }
+
+    /// Verifies a batch of proofs at once
+    ///
+    /// Proofs are checked as a random linear combination `Σ ρ_i (z_i G + c_i pk_i - R_i) = O`
+    /// computed via single multiscalar multiplication, which is considerably faster than
+    /// verifying proofs one by one. If the batch is invalid, proofs are verified individually
+    /// to find out which of them failed.
+    pub fn batch_verify(proofs: &[DLogProof<E, H>]) -> Result<(), BatchVerificationError> {
+        let mut scalars = Vec::with_capacity(2 * proofs.len() + 1);
+        let mut points = Vec::with_capacity(2 * proofs.len() + 1);
+        let mut generator_scalar = Scalar::zero();
+        for proof in proofs {
+            let weight = batch_verification_weight::<E>();
+            let challenge = Self::hash_challenge(&proof.pk, &proof.pk_t_rand_commitment);
+            generator_scalar = generator_scalar + &weight * &proof.challenge_response;
+            scalars.push(&weight * &challenge);
+            points.push(&proof.pk);
+            scalars.push(-weight);
+            points.push(&proof.pk_t_rand_commitment);
+        }
+        let generator = Point::<E>::generator().to_point();
+        scalars.push(generator_scalar);
+        points.push(&generator);
+
+        if Point::multiscalar_mul(&scalars, points).is_zero() {
+            Ok(())
+        } else {
+            BatchVerificationError::find_invalid_proofs(proofs.iter().map(Self::verify))
+        }
+    }
 
+    /// Proves knowledge of `sk`, deriving the challenge from the transcript
+    ///
+    /// Statement and commitment are appended to the transcript, so it has to be in the same state
+    /// when [verifying](Self::verify_with_transcript) the proof.
+    pub fn prove_with_transcript(
+        sk: &Scalar<E>,
+        transcript: &mut Transcript<H>,
+    ) -> DLogProof<E, H> {
+        Self::prove_with_challenge(sk, |pk, pk_t_rand_commitment| {
+            Self::transcript_challenge(transcript, pk, pk_t_rand_commitment)
+        })
+    }
+
+    /// Verifies the proof produced by [prove_with_transcript](Self::prove_with_transcript)
+    pub fn verify_with_transcript(
+        proof: &DLogProof<E, H>,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let challenge =
+            Self::transcript_challenge(transcript, &proof.pk, &proof.pk_t_rand_commitment);
+        proof.verify_challenge(&challenge)
+    }
+
+    fn prove_with_challenge(
+        sk: &Scalar<E>,
+        challenge: impl FnOnce(&Point<E>, &Point<E>) -> Scalar<E>,
+    ) -> DLogProof<E, H> {
         let pk = Point::generator() * sk;
 
-        let challenge = H::new()
-            .chain_point(&pk_t_rand_commitment)
-            .chain_point(&generator.to_point())
-            .chain_point(&pk)
-            .result_scalar();
+        let (pk_t_rand_commitment, sk_t_rand_commitment) = Self::commit(&pk, sk);
 
-        let challenge_mul_sk = challenge * sk;
-        let challenge_response = &sk_t_rand_commitment - &challenge_mul_sk;
+        let challenge = challenge(&pk, &pk_t_rand_commitment);
+
+        let challenge_response = Self::respond(&pk, sk, sk_t_rand_commitment, &challenge);
         DLogProof {
             pk,
             pk_t_rand_commitment,
@@ -58,25 +119,98 @@ impl<E: Curve, H: Digest + Clone> DLogProof<E, H> {
         }
     }
 
-    pub fn verify(proof: &DLogProof<E, H>) -> Result<(), ProofError> {
-        let generator = Point::<E>::generator();
+    fn verify_challenge(&self, challenge: &Scalar<E>) -> Result<(), ProofError> {
+        Self::verify_response(
+            &self.pk,
+            &self.pk_t_rand_commitment,
+            challenge,
+            &self.challenge_response,
+        )
+    }
 
-        let challenge = H::new()
-            .chain_point(&proof.pk_t_rand_commitment)
-            .chain_point(&generator.to_point())
-            .chain_point(&proof.pk)
-            .result_scalar();
+    pub(crate) fn hash_challenge(pk: &Point<E>, pk_t_rand_commitment: &Point<E>) -> Scalar<E> {
+        H::new()
+            .chain_point(pk_t_rand_commitment)
+            .chain_point(&Point::<E>::generator().to_point())
+            .chain_point(pk)
+            .result_scalar()
+    }
 
-        let pk_challenge = &proof.pk * &challenge;
+    fn transcript_challenge(
+        transcript: &mut Transcript<H>,
+        pk: &Point<E>,
+        pk_t_rand_commitment: &Point<E>,
+    ) -> Scalar<E> {
+        transcript.append_message(b"proof", b"curv/dlog-proof");
+        transcript.append_point(b"generator", &Point::<E>::generator().to_point());
+        transcript.append_point(b"pk", pk);
+        transcript.append_point(b"pk_t_rand_commitment", pk_t_rand_commitment);
+        transcript.challenge_scalar(b"challenge")
+    }
+}
+
+/// Schnorr's protocol as a [sigma protocol](SigmaProtocol): statement is `pk`, witness is `sk`
+/// such that `pk = sk * G`
+impl<E: Curve, H: Digest + Clone> SigmaProtocol<E> for DLogProof<E, H> {
+    type Statement = Point<E>;
+    type Witness = Scalar<E>;
+    type Commitment = Point<E>;
+    type CommitmentSecret = Scalar<E>;
+    type Response = Scalar<E>;
+
+    fn commit(_pk: &Point<E>, _sk: &Scalar<E>) -> (Point<E>, Scalar<E>) {
+        let sk_t_rand_commitment = Scalar::random();
+        let pk_t_rand_commitment = Point::generator() * &sk_t_rand_commitment;
+        (pk_t_rand_commitment, sk_t_rand_commitment)
+    }
+
+    fn respond(
+        _pk: &Point<E>,
+        sk: &Scalar<E>,
+        sk_t_rand_commitment: Scalar<E>,
+        challenge: &Scalar<E>,
+    ) -> Scalar<E> {
+        let challenge_mul_sk = challenge * sk;
+        sk_t_rand_commitment - challenge_mul_sk
+    }
+
+    fn verify_response(
+        pk: &Point<E>,
+        pk_t_rand_commitment: &Point<E>,
+        challenge: &Scalar<E>,
+        challenge_response: &Scalar<E>,
+    ) -> Result<(), ProofError> {
+        let generator = Point::<E>::generator();
+
+        let pk_challenge = pk * challenge;
 
-        let pk_verifier = generator * &proof.challenge_response + pk_challenge;
+        let pk_verifier = generator * challenge_response + pk_challenge;
 
-        if pk_verifier == proof.pk_t_rand_commitment {
+        if &pk_verifier == pk_t_rand_commitment {
             Ok(())
         } else {
             Err(ProofError)
         }
     }
+
+    fn simulate(pk: &Point<E>, challenge: &Scalar<E>) -> (Point<E>, Scalar<E>) {
+        let challenge_response = Scalar::random();
+        let pk_t_rand_commitment = Point::generator() * &challenge_response + pk * challenge;
+        (pk_t_rand_commitment, challenge_response)
+    }
+
+    fn append_statement<T: Digest + Clone>(transcript: &mut Transcript<T>, pk: &Point<E>) {
+        transcript.append_message(b"protocol", b"dlog");
+        transcript.append_point(b"generator", &Point::<E>::generator().to_point());
+        transcript.append_point(b"pk", pk);
+    }
+
+    fn append_commitment<T: Digest + Clone>(
+        transcript: &mut Transcript<T>,
+        pk_t_rand_commitment: &Point<E>,
+    ) {
+        transcript.append_point(b"pk_t_rand_commitment", pk_t_rand_commitment);
+    }
 }
 
 #[cfg(test)]
@@ -89,4 +223,32 @@ mod tests {
         let dlog_proof = DLogProof::<E, H>::prove(&witness);
         assert!(DLogProof::verify(&dlog_proof).is_ok());
     }
+
+    crate::test_for_all_curves_and_hashes!(test_dlog_proof_batch_verify);
+    fn test_dlog_proof_batch_verify<E: Curve, H: Digest + Clone>() {
+        let mut proofs: Vec<_> = (0..10)
+            .map(|_| DLogProof::<E, H>::prove(&Scalar::random()))
+            .collect();
+        assert!(DLogProof::<E, H>::batch_verify(&[]).is_ok());
+        assert!(DLogProof::batch_verify(&proofs).is_ok());
+
+        proofs[3].challenge_response = Scalar::random();
+        proofs[7].pk = Point::generator() * Scalar::random();
+        let err = DLogProof::batch_verify(&proofs).unwrap_err();
+        assert_eq!(err.invalid_proofs, vec![3, 7]);
+    }
+
+    crate::test_for_all_curves_and_hashes!(test_dlog_proof_with_transcript);
+    fn test_dlog_proof_with_transcript<E: Curve, H: Digest + Clone>() {
+        let transcript = |session_id: &[u8]| {
+            let mut t = Transcript::<H>::new(b"test");
+            t.append_message(b"session_id", session_id);
+            t
+        };
+        let witness = Scalar::random();
+        let dlog_proof = DLogProof::<E, H>::prove_with_transcript(&witness, &mut transcript(b"1"));
+        assert!(DLogProof::verify_with_transcript(&dlog_proof, &mut transcript(b"1")).is_ok());
+        assert!(DLogProof::verify_with_transcript(&dlog_proof, &mut transcript(b"2")).is_err());
+        assert!(DLogProof::verify(&dlog_proof).is_err());
+    }
 }
@@ -11,7 +11,9 @@ use crate::cryptographic_primitives::hashing::{Digest, DigestExt};
 use crate::elliptic::curves::{Curve, Point, Scalar};
 use crate::marker::HashChoice;
 
-use super::ProofError;
+use super::sigma_protocol::SigmaProtocol;
+use super::transcript::Transcript;
+use super::{batch_verification_weight, BatchVerificationError, ProofError};
 
 /// This protocol is the elliptic curve form of the protocol from :
 ///  D. Chaum, T. P. Pedersen. Transferred cash grows in size. In Advances in Cryptology, EUROCRYPT , volume 658 of Lecture Notes in Computer Science, pages 390 - 407, 1993.
@@ -50,18 +52,97 @@ pub struct ECDDHWitness<E: Curve> {
 
 impl<E: Curve, H: Digest + Clone> ECDDHProof<E, H> {
     pub fn prove(w: &ECDDHWitness<E>, delta: &ECDDHStatement<E>) -> ECDDHProof<E, H> {
-        let s = Scalar::random();
-        let a1 = &delta.g1 * &s;
-        let a2 = &delta.g2 * &s;
-        let e = H::new()
-            .chain_point(&delta.g1)
-            .chain_point(&delta.h1)
-            .chain_point(&delta.g2)
-            .chain_point(&delta.h2)
-            .chain_point(&a1)
-            .chain_point(&a2)
-            .result_scalar();
-        let z = &s + e * &w.x;
+        Self::prove_with_challenge(w, delta, |a1, a2| Self::hash_challenge(delta, a1, a2))
+    }
+
+    pub fn verify(&self, delta: &ECDDHStatement<E>) -> Result<(), ProofError> {
+        let e = Self::hash_challenge(delta, &self.a1, &self.a2);
+        self.verify_challenge(delta, &e)
+    }
+
+    /// Verifies a batch of proofs at once, `proofs[i]` is verified against `statements[i]`
+    ///
+    /// Both equations of every proof are combined with random weights and checked via single
+    /// multiscalar multiplication. If the batch is invalid, proofs are verified individually
+    /// to find out which of them failed.
+    ///
+    /// ## Panics
+    /// Panics if `proofs` and `statements` have different lengths.
+    pub fn batch_verify(
+        proofs: &[ECDDHProof<E, H>],
+        statements: &[ECDDHStatement<E>],
+    ) -> Result<(), BatchVerificationError> {
+        assert_eq!(
+            proofs.len(),
+            statements.len(),
+            "proofs and statements must have the same length"
+        );
+        let mut scalars = Vec::with_capacity(6 * proofs.len());
+        let mut points = Vec::with_capacity(6 * proofs.len());
+        for (proof, delta) in proofs.iter().zip(statements) {
+            let e = Self::hash_challenge(delta, &proof.a1, &proof.a2);
+            // zG1 - A1 - eH1 = O
+            let weight1 = batch_verification_weight::<E>();
+            scalars.push(&weight1 * &proof.z);
+            points.push(&delta.g1);
+            scalars.push(-&weight1);
+            points.push(&proof.a1);
+            scalars.push(-(weight1 * &e));
+            points.push(&delta.h1);
+            // zG2 - A2 - eH2 = O
+            let weight2 = batch_verification_weight::<E>();
+            scalars.push(&weight2 * &proof.z);
+            points.push(&delta.g2);
+            scalars.push(-&weight2);
+            points.push(&proof.a2);
+            scalars.push(-(weight2 * e));
+            points.push(&delta.h2);
+        }
+
+        if Point::multiscalar_mul(&scalars, points).is_zero() {
+            Ok(())
+        } else {
+            BatchVerificationError::find_invalid_proofs(
+                proofs
+                    .iter()
+                    .zip(statements)
+                    .map(|(proof, delta)| proof.verify(delta)),
+            )
+        }
+    }
+
+    /// Proves the statement, deriving the challenge from the transcript
+    ///
+    /// Statement and commitments are appended to the transcript, so it has to be in the same state
+    /// when [verifying](Self::verify_with_transcript) the proof.
+    pub fn prove_with_transcript(
+        w: &ECDDHWitness<E>,
+        delta: &ECDDHStatement<E>,
+        transcript: &mut Transcript<H>,
+    ) -> ECDDHProof<E, H> {
+        Self::prove_with_challenge(w, delta, |a1, a2| {
+            Self::transcript_challenge(transcript, delta, a1, a2)
+        })
+    }
+
+    /// Verifies the proof produced by [prove_with_transcript](Self::prove_with_transcript)
+    pub fn verify_with_transcript(
+        &self,
+        delta: &ECDDHStatement<E>,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let e = Self::transcript_challenge(transcript, delta, &self.a1, &self.a2);
+        self.verify_challenge(delta, &e)
+    }
+
+    fn prove_with_challenge(
+        w: &ECDDHWitness<E>,
+        delta: &ECDDHStatement<E>,
+        challenge: impl FnOnce(&Point<E>, &Point<E>) -> Scalar<E>,
+    ) -> ECDDHProof<E, H> {
+        let ((a1, a2), s) = Self::commit(delta, w);
+        let e = challenge(&a1, &a2);
+        let z = Self::respond(delta, w, s, &e);
         ECDDHProof {
             a1,
             a2,
@@ -70,25 +151,108 @@ impl<E: Curve, H: Digest + Clone> ECDDHProof<E, H> {
         }
     }
 
-    pub fn verify(&self, delta: &ECDDHStatement<E>) -> Result<(), ProofError> {
-        let e = H::new()
+    fn verify_challenge(&self, delta: &ECDDHStatement<E>, e: &Scalar<E>) -> Result<(), ProofError> {
+        Self::verify_response(delta, &(self.a1.clone(), self.a2.clone()), e, &self.z)
+    }
+
+    fn hash_challenge(delta: &ECDDHStatement<E>, a1: &Point<E>, a2: &Point<E>) -> Scalar<E> {
+        H::new()
             .chain_point(&delta.g1)
             .chain_point(&delta.h1)
             .chain_point(&delta.g2)
             .chain_point(&delta.h2)
-            .chain_point(&self.a1)
-            .chain_point(&self.a2)
-            .result_scalar();
-        let z_g1 = &delta.g1 * &self.z;
-        let z_g2 = &delta.g2 * &self.z;
-        let a1_plus_e_h1 = &self.a1 + &delta.h1 * &e;
-        let a2_plus_e_h2 = &self.a2 + &delta.h2 * e;
+            .chain_point(a1)
+            .chain_point(a2)
+            .result_scalar()
+    }
+
+    fn transcript_challenge(
+        transcript: &mut Transcript<H>,
+        delta: &ECDDHStatement<E>,
+        a1: &Point<E>,
+        a2: &Point<E>,
+    ) -> Scalar<E> {
+        transcript.append_message(b"proof", b"curv/ec-ddh-proof");
+        transcript.append_point(b"g1", &delta.g1);
+        transcript.append_point(b"h1", &delta.h1);
+        transcript.append_point(b"g2", &delta.g2);
+        transcript.append_point(b"h2", &delta.h2);
+        transcript.append_point(b"a1", a1);
+        transcript.append_point(b"a2", a2);
+        transcript.challenge_scalar(b"challenge")
+    }
+}
+
+/// DDH membership protocol as a [sigma protocol](SigmaProtocol): commitment is `(A1, A2)`,
+/// response is `z`
+impl<E: Curve, H: Digest + Clone> SigmaProtocol<E> for ECDDHProof<E, H> {
+    type Statement = ECDDHStatement<E>;
+    type Witness = ECDDHWitness<E>;
+    type Commitment = (Point<E>, Point<E>);
+    type CommitmentSecret = Scalar<E>;
+    type Response = Scalar<E>;
+
+    fn commit(
+        delta: &ECDDHStatement<E>,
+        _w: &ECDDHWitness<E>,
+    ) -> ((Point<E>, Point<E>), Scalar<E>) {
+        let s = Scalar::random();
+        let a1 = &delta.g1 * &s;
+        let a2 = &delta.g2 * &s;
+        ((a1, a2), s)
+    }
+
+    fn respond(
+        _delta: &ECDDHStatement<E>,
+        w: &ECDDHWitness<E>,
+        s: Scalar<E>,
+        e: &Scalar<E>,
+    ) -> Scalar<E> {
+        s + e * &w.x
+    }
+
+    fn verify_response(
+        delta: &ECDDHStatement<E>,
+        (a1, a2): &(Point<E>, Point<E>),
+        e: &Scalar<E>,
+        z: &Scalar<E>,
+    ) -> Result<(), ProofError> {
+        let z_g1 = &delta.g1 * z;
+        let z_g2 = &delta.g2 * z;
+        let a1_plus_e_h1 = a1 + &delta.h1 * e;
+        let a2_plus_e_h2 = a2 + &delta.h2 * e;
         if z_g1 == a1_plus_e_h1 && z_g2 == a2_plus_e_h2 {
             Ok(())
         } else {
             Err(ProofError)
         }
     }
+
+    fn simulate(delta: &ECDDHStatement<E>, e: &Scalar<E>) -> ((Point<E>, Point<E>), Scalar<E>) {
+        let z = Scalar::random();
+        let a1 = &delta.g1 * &z - &delta.h1 * e;
+        let a2 = &delta.g2 * &z - &delta.h2 * e;
+        ((a1, a2), z)
+    }
+
+    fn append_statement<T: Digest + Clone>(
+        transcript: &mut Transcript<T>,
+        delta: &ECDDHStatement<E>,
+    ) {
+        transcript.append_message(b"protocol", b"ec-ddh");
+        transcript.append_point(b"g1", &delta.g1);
+        transcript.append_point(b"h1", &delta.h1);
+        transcript.append_point(b"g2", &delta.g2);
+        transcript.append_point(b"h2", &delta.h2);
+    }
+
+    fn append_commitment<T: Digest + Clone>(
+        transcript: &mut Transcript<T>,
+        (a1, a2): &(Point<E>, Point<E>),
+    ) {
+        transcript.append_point(b"a1", a1);
+        transcript.append_point(b"a2", a2);
+    }
 }
 
 #[cfg(test)]
@@ -115,6 +279,59 @@ mod tests {
         assert!(proof.verify(&delta).is_ok());
     }
 
+    test_for_all_curves_and_hashes!(test_ecddh_proof_batch_verify);
+    fn test_ecddh_proof_batch_verify<E: Curve, H: Digest + Clone>() {
+        let g1 = Point::generator();
+        let g2 = Point::base_point2();
+        let (mut proofs, mut statements): (Vec<_>, Vec<_>) = (0..10)
+            .map(|_| {
+                let x = Scalar::random();
+                let delta = ECDDHStatement {
+                    g1: g1.to_point(),
+                    g2: g2.clone(),
+                    h1: g1 * &x,
+                    h2: g2 * &x,
+                };
+                let proof = ECDDHProof::<E, H>::prove(&ECDDHWitness { x }, &delta);
+                (proof, delta)
+            })
+            .unzip();
+        assert!(ECDDHProof::<E, H>::batch_verify(&[], &[]).is_ok());
+        assert!(ECDDHProof::batch_verify(&proofs, &statements).is_ok());
+
+        proofs[2].z = Scalar::random();
+        statements[5].h2 = g2 * Scalar::random();
+        let err = ECDDHProof::batch_verify(&proofs, &statements).unwrap_err();
+        assert_eq!(err.invalid_proofs, vec![2, 5]);
+    }
+
+    test_for_all_curves_and_hashes!(test_ecddh_proof_with_transcript);
+    fn test_ecddh_proof_with_transcript<E: Curve, H: Digest + Clone>() {
+        let transcript = |session_id: &[u8]| {
+            let mut t = Transcript::<H>::new(b"test");
+            t.append_message(b"session_id", session_id);
+            t
+        };
+        let x = Scalar::random();
+        let g1 = Point::generator();
+        let g2 = Point::base_point2();
+        let delta = ECDDHStatement {
+            g1: g1.to_point(),
+            g2: g2.clone(),
+            h1: g1 * &x,
+            h2: g2 * &x,
+        };
+        let w = ECDDHWitness { x };
+        let proof = ECDDHProof::<E, H>::prove_with_transcript(&w, &delta, &mut transcript(b"1"));
+        assert!(proof
+            .verify_with_transcript(&delta, &mut transcript(b"1"))
+            .is_ok());
+        assert!(proof
+            .verify_with_transcript(&delta, &mut transcript(b"2"))
+            .is_err());
+        assert!(proof.verify(&delta).is_err());
+    }
+
     test_for_all_curves_and_hashes!(test_wrong_ecddh_proof);
     fn test_wrong_ecddh_proof<E: Curve, H: Digest + Clone>() {
         let x = Scalar::random();
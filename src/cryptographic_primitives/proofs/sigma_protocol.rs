@@ -0,0 +1,758 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Generic sigma protocols and their composition
+//!
+//! [SigmaProtocol] describes a 3-move public-coin proof of knowledge: prover sends a commitment,
+//! verifier replies with a random challenge, prover sends a response. Protocols can be composed:
+//! * [And] proves knowledge of witnesses for both statements
+//! * [Or] proves knowledge of a witness for at least one of two statements without revealing
+//!   which one (Cramer–Damgård–Schoenmakers composition)
+//! * [OrN] proves knowledge of a witness for one of `n` statements of the same kind (eg. 1-out-of-n
+//!   ring membership)
+//!
+//! Any sigma protocol can be made non-interactive via [SigmaProof] that derives the challenge from
+//! a [Transcript].
+//!
+//! ## Example
+//! Proving knowledge of discrete log of `A` or `B`:
+//!
+//! ```rust
+//! use curv::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+//! use curv::cryptographic_primitives::proofs::sigma_protocol::{Or, OrWitness, SigmaProof};
+//! use curv::cryptographic_primitives::proofs::transcript::Transcript;
+//! use curv::elliptic::curves::{Point, Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! type DLog = DLogProof<Secp256k1, Sha256>;
+//!
+//! let b = Scalar::<Secp256k1>::random();
+//! let statement = (Point::<Secp256k1>::base_point2().clone(), Point::generator() * &b);
+//!
+//! let mut transcript = Transcript::<Sha256>::new(b"MY-PROTOCOL-V1");
+//! let proof = SigmaProof::<_, Or<DLog, DLog>>::prove(&statement, &OrWitness::Right(b), &mut transcript);
+//!
+//! let mut transcript = Transcript::<Sha256>::new(b"MY-PROTOCOL-V1");
+//! assert!(proof.verify(&statement, &mut transcript).is_ok());
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use digest::Digest;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::elliptic::curves::{Curve, Scalar};
+
+use super::transcript::Transcript;
+use super::ProofError;
+
+/// Sigma protocol proving knowledge of a witness for a statement
+///
+/// Challenge is a scalar, so protocols over the same curve can be [composed](self).
+pub trait SigmaProtocol<E: Curve> {
+    /// Public statement
+    type Statement;
+    /// Secret witness for the statement
+    type Witness;
+    /// First message of the prover
+    type Commitment: Clone + fmt::Debug + PartialEq + Serialize + DeserializeOwned;
+    /// Prover's secret state between sending a commitment and receiving a challenge
+    type CommitmentSecret;
+    /// Last message of the prover
+    type Response: Clone + fmt::Debug + PartialEq + Serialize + DeserializeOwned;
+
+    /// Samples prover's commitment
+    fn commit(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+    ) -> (Self::Commitment, Self::CommitmentSecret);
+    /// Computes a response to the challenge
+    fn respond(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        secret: Self::CommitmentSecret,
+        challenge: &Scalar<E>,
+    ) -> Self::Response;
+    /// Verifies that `(commitment, challenge, response)` is an accepting transcript for the
+    /// statement
+    fn verify_response(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Scalar<E>,
+        response: &Self::Response,
+    ) -> Result<(), ProofError>;
+    /// Special honest-verifier zero-knowledge simulator
+    ///
+    /// Outputs an accepting transcript for given statement and challenge without knowing the
+    /// witness. Simulated transcripts are distributed identically to real ones.
+    fn simulate(
+        statement: &Self::Statement,
+        challenge: &Scalar<E>,
+    ) -> (Self::Commitment, Self::Response);
+
+    /// Appends the statement to the transcript
+    fn append_statement<H: Digest + Clone>(
+        transcript: &mut Transcript<H>,
+        statement: &Self::Statement,
+    );
+    /// Appends prover's commitment to the transcript
+    fn append_commitment<H: Digest + Clone>(
+        transcript: &mut Transcript<H>,
+        commitment: &Self::Commitment,
+    );
+}
+
+/// Non-interactive proof for sigma protocol `P` obtained via Fiat-Shamir transform
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SigmaProof<E: Curve, P: SigmaProtocol<E>> {
+    pub commitment: P::Commitment,
+    pub response: P::Response,
+}
+
+impl<E: Curve, P: SigmaProtocol<E>> SigmaProof<E, P> {
+    /// Proves the statement, deriving the challenge from the transcript
+    ///
+    /// Statement and commitment are appended to the transcript, so it has to be in the same state
+    /// when [verifying](Self::verify) the proof.
+    pub fn prove<H: Digest + Clone>(
+        statement: &P::Statement,
+        witness: &P::Witness,
+        transcript: &mut Transcript<H>,
+    ) -> Self {
+        let (commitment, secret) = P::commit(statement, witness);
+        let challenge = Self::challenge(transcript, statement, &commitment);
+        let response = P::respond(statement, witness, secret, &challenge);
+        Self {
+            commitment,
+            response,
+        }
+    }
+
+    /// Verifies the proof produced by [prove](Self::prove)
+    pub fn verify<H: Digest + Clone>(
+        &self,
+        statement: &P::Statement,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let challenge = Self::challenge(transcript, statement, &self.commitment);
+        P::verify_response(statement, &self.commitment, &challenge, &self.response)
+    }
+
+    fn challenge<H: Digest + Clone>(
+        transcript: &mut Transcript<H>,
+        statement: &P::Statement,
+        commitment: &P::Commitment,
+    ) -> Scalar<E> {
+        transcript.append_message(b"proof", b"curv/sigma-protocol");
+        P::append_statement(transcript, statement);
+        P::append_commitment(transcript, commitment);
+        transcript.challenge_scalar(b"challenge")
+    }
+}
+
+impl<E: Curve, P: SigmaProtocol<E>> Clone for SigmaProof<E, P> {
+    fn clone(&self) -> Self {
+        Self {
+            commitment: self.commitment.clone(),
+            response: self.response.clone(),
+        }
+    }
+}
+
+impl<E: Curve, P: SigmaProtocol<E>> fmt::Debug for SigmaProof<E, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SigmaProof")
+            .field("commitment", &self.commitment)
+            .field("response", &self.response)
+            .finish()
+    }
+}
+
+impl<E: Curve, P: SigmaProtocol<E>> PartialEq for SigmaProof<E, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.commitment == other.commitment && self.response == other.response
+    }
+}
+
+/// AND composition of sigma protocols: proves knowledge of witnesses for both statements
+///
+/// Both protocols are run in parallel with the same challenge.
+pub struct And<P1, P2>(PhantomData<(P1, P2)>);
+
+impl<E, P1, P2> SigmaProtocol<E> for And<P1, P2>
+where
+    E: Curve,
+    P1: SigmaProtocol<E>,
+    P2: SigmaProtocol<E>,
+{
+    type Statement = (P1::Statement, P2::Statement);
+    type Witness = (P1::Witness, P2::Witness);
+    type Commitment = (P1::Commitment, P2::Commitment);
+    type CommitmentSecret = (P1::CommitmentSecret, P2::CommitmentSecret);
+    type Response = (P1::Response, P2::Response);
+
+    fn commit(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+    ) -> (Self::Commitment, Self::CommitmentSecret) {
+        let (commitment1, secret1) = P1::commit(&statement.0, &witness.0);
+        let (commitment2, secret2) = P2::commit(&statement.1, &witness.1);
+        ((commitment1, commitment2), (secret1, secret2))
+    }
+
+    fn respond(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        secret: Self::CommitmentSecret,
+        challenge: &Scalar<E>,
+    ) -> Self::Response {
+        (
+            P1::respond(&statement.0, &witness.0, secret.0, challenge),
+            P2::respond(&statement.1, &witness.1, secret.1, challenge),
+        )
+    }
+
+    fn verify_response(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Scalar<E>,
+        response: &Self::Response,
+    ) -> Result<(), ProofError> {
+        P1::verify_response(&statement.0, &commitment.0, challenge, &response.0)?;
+        P2::verify_response(&statement.1, &commitment.1, challenge, &response.1)
+    }
+
+    fn simulate(
+        statement: &Self::Statement,
+        challenge: &Scalar<E>,
+    ) -> (Self::Commitment, Self::Response) {
+        let (commitment1, response1) = P1::simulate(&statement.0, challenge);
+        let (commitment2, response2) = P2::simulate(&statement.1, challenge);
+        ((commitment1, commitment2), (response1, response2))
+    }
+
+    fn append_statement<H: Digest + Clone>(
+        transcript: &mut Transcript<H>,
+        statement: &Self::Statement,
+    ) {
+        transcript.append_message(b"protocol", b"and");
+        P1::append_statement(transcript, &statement.0);
+        P2::append_statement(transcript, &statement.1);
+    }
+
+    fn append_commitment<H: Digest + Clone>(
+        transcript: &mut Transcript<H>,
+        commitment: &Self::Commitment,
+    ) {
+        P1::append_commitment(transcript, &commitment.0);
+        P2::append_commitment(transcript, &commitment.1);
+    }
+}
+
+/// OR composition of sigma protocols: proves knowledge of a witness for one of the statements
+///
+/// Implements Cramer–Damgård–Schoenmakers technique: prover simulates the protocol for
+/// the statement it doesn't know witness for, and splits the challenge `c = c1 + c2` so that
+/// it can honestly answer the other one. Verifier doesn't learn which statement is proven.
+///
+/// Ronald Cramer, Ivan Damgård, and Berry Schoenmakers. Proofs of Partial Knowledge and Simplified
+/// Design of Witness Hiding Protocols. In CRYPTO 1994, Springer (LNCS 839), pages 174–187, 1994.
+pub struct Or<P1, P2>(PhantomData<(P1, P2)>);
+
+/// Witness for one of the statements of [Or] protocol
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrWitness<W1, W2> {
+    /// Witness for the first statement
+    Left(W1),
+    /// Witness for the second statement
+    Right(W2),
+}
+
+/// Prover's secret state of [Or] protocol
+pub enum OrCommitmentSecret<E: Curve, P1: SigmaProtocol<E>, P2: SigmaProtocol<E>> {
+    Left {
+        secret: P1::CommitmentSecret,
+        simulated_challenge: Scalar<E>,
+        simulated_response: P2::Response,
+    },
+    Right {
+        secret: P2::CommitmentSecret,
+        simulated_challenge: Scalar<E>,
+        simulated_response: P1::Response,
+    },
+}
+
+/// Response of [Or] protocol
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "R1: Serialize, R2: Serialize",
+    deserialize = "R1: Deserialize<'de>, R2: Deserialize<'de>"
+))]
+pub struct OrResponse<E: Curve, R1, R2> {
+    /// Challenge of the first protocol, challenge of the second one is `c - c1`
+    pub c1: Scalar<E>,
+    pub response1: R1,
+    pub response2: R2,
+}
+
+impl<E, P1, P2> SigmaProtocol<E> for Or<P1, P2>
+where
+    E: Curve,
+    P1: SigmaProtocol<E>,
+    P2: SigmaProtocol<E>,
+{
+    type Statement = (P1::Statement, P2::Statement);
+    type Witness = OrWitness<P1::Witness, P2::Witness>;
+    type Commitment = (P1::Commitment, P2::Commitment);
+    type CommitmentSecret = OrCommitmentSecret<E, P1, P2>;
+    type Response = OrResponse<E, P1::Response, P2::Response>;
+
+    fn commit(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+    ) -> (Self::Commitment, Self::CommitmentSecret) {
+        let simulated_challenge = Scalar::random();
+        match witness {
+            OrWitness::Left(witness) => {
+                let (commitment1, secret) = P1::commit(&statement.0, witness);
+                let (commitment2, simulated_response) =
+                    P2::simulate(&statement.1, &simulated_challenge);
+                let secret = OrCommitmentSecret::Left {
+                    secret,
+                    simulated_challenge,
+                    simulated_response,
+                };
+                ((commitment1, commitment2), secret)
+            }
+            OrWitness::Right(witness) => {
+                let (commitment1, simulated_response) =
+                    P1::simulate(&statement.0, &simulated_challenge);
+                let (commitment2, secret) = P2::commit(&statement.1, witness);
+                let secret = OrCommitmentSecret::Right {
+                    secret,
+                    simulated_challenge,
+                    simulated_response,
+                };
+                ((commitment1, commitment2), secret)
+            }
+        }
+    }
+
+    fn respond(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        secret: Self::CommitmentSecret,
+        challenge: &Scalar<E>,
+    ) -> Self::Response {
+        match (witness, secret) {
+            (
+                OrWitness::Left(witness),
+                OrCommitmentSecret::Left {
+                    secret,
+                    simulated_challenge,
+                    simulated_response,
+                },
+            ) => {
+                let c1 = challenge - &simulated_challenge;
+                OrResponse {
+                    response1: P1::respond(&statement.0, witness, secret, &c1),
+                    response2: simulated_response,
+                    c1,
+                }
+            }
+            (
+                OrWitness::Right(witness),
+                OrCommitmentSecret::Right {
+                    secret,
+                    simulated_challenge,
+                    simulated_response,
+                },
+            ) => {
+                let c2 = challenge - &simulated_challenge;
+                OrResponse {
+                    response1: simulated_response,
+                    response2: P2::respond(&statement.1, witness, secret, &c2),
+                    c1: simulated_challenge,
+                }
+            }
+            _ => panic!("commitment secret doesn't match the witness"),
+        }
+    }
+
+    fn verify_response(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Scalar<E>,
+        response: &Self::Response,
+    ) -> Result<(), ProofError> {
+        let c2 = challenge - &response.c1;
+        P1::verify_response(
+            &statement.0,
+            &commitment.0,
+            &response.c1,
+            &response.response1,
+        )?;
+        P2::verify_response(&statement.1, &commitment.1, &c2, &response.response2)
+    }
+
+    fn simulate(
+        statement: &Self::Statement,
+        challenge: &Scalar<E>,
+    ) -> (Self::Commitment, Self::Response) {
+        let c1 = Scalar::random();
+        let c2 = challenge - &c1;
+        let (commitment1, response1) = P1::simulate(&statement.0, &c1);
+        let (commitment2, response2) = P2::simulate(&statement.1, &c2);
+        let response = OrResponse {
+            c1,
+            response1,
+            response2,
+        };
+        ((commitment1, commitment2), response)
+    }
+
+    fn append_statement<H: Digest + Clone>(
+        transcript: &mut Transcript<H>,
+        statement: &Self::Statement,
+    ) {
+        transcript.append_message(b"protocol", b"or");
+        P1::append_statement(transcript, &statement.0);
+        P2::append_statement(transcript, &statement.1);
+    }
+
+    fn append_commitment<H: Digest + Clone>(
+        transcript: &mut Transcript<H>,
+        commitment: &Self::Commitment,
+    ) {
+        P1::append_commitment(transcript, &commitment.0);
+        P2::append_commitment(transcript, &commitment.1);
+    }
+}
+
+/// OR composition of `n` instances of the same sigma protocol: proves knowledge of a witness for
+/// one of the statements
+///
+/// It's a generalization of [Or]: challenges `c_1, ..., c_n` must sum up to verifier's challenge
+/// `c`, prover simulates all the instances except the one it knows the witness for.
+///
+/// ## Panics
+/// Proving panics if [witness index](OrNWitness::index) is out of range of statements list.
+pub struct OrN<P>(PhantomData<P>);
+
+/// Witness for one of the statements of [OrN] protocol
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrNWitness<W> {
+    /// Index of the statement in the list
+    pub index: usize,
+    /// Witness for the statement
+    pub witness: W,
+}
+
+/// Prover's secret state of [OrN] protocol
+pub struct OrNCommitmentSecret<E: Curve, P: SigmaProtocol<E>> {
+    secret: P::CommitmentSecret,
+    simulated: Vec<Option<(Scalar<E>, P::Response)>>,
+}
+
+/// Response of [OrN] protocol
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound(serialize = "R: Serialize", deserialize = "R: Deserialize<'de>"))]
+pub struct OrNResponse<E: Curve, R> {
+    /// Challenges `c_1, ..., c_n` that must sum up to verifier's challenge
+    pub challenges: Vec<Scalar<E>>,
+    pub responses: Vec<R>,
+}
+
+impl<E, P> SigmaProtocol<E> for OrN<P>
+where
+    E: Curve,
+    P: SigmaProtocol<E>,
+{
+    type Statement = Vec<P::Statement>;
+    type Witness = OrNWitness<P::Witness>;
+    type Commitment = Vec<P::Commitment>;
+    type CommitmentSecret = OrNCommitmentSecret<E, P>;
+    type Response = OrNResponse<E, P::Response>;
+
+    fn commit(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+    ) -> (Self::Commitment, Self::CommitmentSecret) {
+        assert!(
+            witness.index < statement.len(),
+            "witness index is out of range"
+        );
+        let mut commitments = Vec::with_capacity(statement.len());
+        let mut simulated = Vec::with_capacity(statement.len());
+        let mut real_secret = None;
+        for (i, statement_i) in statement.iter().enumerate() {
+            if i == witness.index {
+                let (commitment, secret) = P::commit(statement_i, &witness.witness);
+                commitments.push(commitment);
+                simulated.push(None);
+                real_secret = Some(secret);
+            } else {
+                let challenge = Scalar::random();
+                let (commitment, response) = P::simulate(statement_i, &challenge);
+                commitments.push(commitment);
+                simulated.push(Some((challenge, response)));
+            }
+        }
+        let secret = OrNCommitmentSecret {
+            secret: real_secret.expect("witness index is in range"),
+            simulated,
+        };
+        (commitments, secret)
+    }
+
+    fn respond(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        secret: Self::CommitmentSecret,
+        challenge: &Scalar<E>,
+    ) -> Self::Response {
+        let simulated_challenges_sum = secret
+            .simulated
+            .iter()
+            .flatten()
+            .fold(Scalar::zero(), |sum, (c_i, _)| sum + c_i);
+        let real_challenge = challenge - simulated_challenges_sum;
+        let mut real_response = Some(P::respond(
+            &statement[witness.index],
+            &witness.witness,
+            secret.secret,
+            &real_challenge,
+        ));
+
+        let mut challenges = Vec::with_capacity(statement.len());
+        let mut responses = Vec::with_capacity(statement.len());
+        for simulated in secret.simulated {
+            match simulated {
+                Some((c_i, response_i)) => {
+                    challenges.push(c_i);
+                    responses.push(response_i);
+                }
+                None => {
+                    challenges.push(real_challenge.clone());
+                    responses.push(real_response.take().expect("only one response is real"));
+                }
+            }
+        }
+        OrNResponse {
+            challenges,
+            responses,
+        }
+    }
+
+    fn verify_response(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Scalar<E>,
+        response: &Self::Response,
+    ) -> Result<(), ProofError> {
+        let n = statement.len();
+        if n == 0
+            || commitment.len() != n
+            || response.challenges.len() != n
+            || response.responses.len() != n
+        {
+            return Err(ProofError);
+        }
+        let challenges_sum = response
+            .challenges
+            .iter()
+            .fold(Scalar::zero(), |sum, c_i| sum + c_i);
+        if &challenges_sum != challenge {
+            return Err(ProofError);
+        }
+        for (((statement_i, commitment_i), c_i), response_i) in statement
+            .iter()
+            .zip(commitment)
+            .zip(&response.challenges)
+            .zip(&response.responses)
+        {
+            P::verify_response(statement_i, commitment_i, c_i, response_i)?;
+        }
+        Ok(())
+    }
+
+    fn simulate(
+        statement: &Self::Statement,
+        challenge: &Scalar<E>,
+    ) -> (Self::Commitment, Self::Response) {
+        let n = statement.len();
+        let mut challenges: Vec<Scalar<E>> = (1..n).map(|_| Scalar::random()).collect();
+        let last_challenge = challenges
+            .iter()
+            .fold(challenge.clone(), |last, c_i| last - c_i);
+        challenges.push(last_challenge);
+
+        let (commitments, responses) = statement
+            .iter()
+            .zip(&challenges)
+            .map(|(statement_i, c_i)| P::simulate(statement_i, c_i))
+            .unzip();
+        let response = OrNResponse {
+            challenges,
+            responses,
+        };
+        (commitments, response)
+    }
+
+    fn append_statement<H: Digest + Clone>(
+        transcript: &mut Transcript<H>,
+        statement: &Self::Statement,
+    ) {
+        transcript.append_message(b"protocol", b"or-n");
+        transcript.append_u64(b"n", statement.len() as u64);
+        for statement_i in statement {
+            P::append_statement(transcript, statement_i);
+        }
+    }
+
+    fn append_commitment<H: Digest + Clone>(
+        transcript: &mut Transcript<H>,
+        commitment: &Self::Commitment,
+    ) {
+        for commitment_i in commitment {
+            P::append_commitment(transcript, commitment_i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+    use crate::cryptographic_primitives::proofs::sigma_ec_ddh::{
+        ECDDHProof, ECDDHStatement, ECDDHWitness,
+    };
+    use crate::cryptographic_primitives::proofs::sigma_valid_pedersen::PedersenProof;
+    use crate::cryptographic_primitives::proofs::sigma_valid_pedersen_blind::PedersenBlindingProof;
+    use crate::elliptic::curves::Point;
+    use crate::test_for_all_curves_and_hashes;
+
+    fn transcript<H: Digest + Clone>() -> Transcript<H> {
+        Transcript::new(b"test")
+    }
+
+    fn ddh_instance<E: Curve>() -> (ECDDHStatement<E>, ECDDHWitness<E>) {
+        let x = Scalar::random();
+        let g1 = Point::generator();
+        let g2 = Point::base_point2();
+        let statement = ECDDHStatement {
+            g1: g1.to_point(),
+            g2: g2.clone(),
+            h1: g1 * &x,
+            h2: g2 * &x,
+        };
+        (statement, ECDDHWitness { x })
+    }
+
+    fn random_point<E: Curve>() -> Point<E> {
+        Point::generator() * Scalar::random()
+    }
+
+    /// Checks that simulated transcripts are accepted
+    fn check_simulator<E: Curve, P: SigmaProtocol<E>>(statement: &P::Statement) {
+        let challenge = Scalar::random();
+        let (commitment, response) = P::simulate(statement, &challenge);
+        assert!(P::verify_response(statement, &commitment, &challenge, &response).is_ok());
+        assert!(P::verify_response(statement, &commitment, &Scalar::random(), &response).is_err());
+    }
+
+    test_for_all_curves_and_hashes!(instances_have_valid_simulators);
+    fn instances_have_valid_simulators<E: Curve, H: Digest + Clone>() {
+        check_simulator::<E, DLogProof<E, H>>(&random_point());
+        check_simulator::<E, ECDDHProof<E, H>>(&ddh_instance().0);
+        check_simulator::<E, PedersenProof<E, H>>(&random_point());
+        check_simulator::<E, PedersenBlindingProof<E, H>>(&(Scalar::random(), random_point()));
+        check_simulator::<E, And<DLogProof<E, H>, ECDDHProof<E, H>>>(&(
+            random_point(),
+            ddh_instance().0,
+        ));
+        check_simulator::<E, Or<DLogProof<E, H>, ECDDHProof<E, H>>>(&(
+            random_point(),
+            ddh_instance().0,
+        ));
+        check_simulator::<E, OrN<DLogProof<E, H>>>(&vec![random_point(), random_point()]);
+    }
+
+    test_for_all_curves_and_hashes!(and_proof);
+    fn and_proof<E: Curve, H: Digest + Clone>() {
+        type P<E, H> = And<DLogProof<E, H>, ECDDHProof<E, H>>;
+        let sk = Scalar::<E>::random();
+        let (ddh_statement, ddh_witness) = ddh_instance::<E>();
+        let statement = (Point::generator() * &sk, ddh_statement);
+
+        let proof =
+            SigmaProof::<E, P<E, H>>::prove(&statement, &(sk, ddh_witness), &mut transcript::<H>());
+        assert!(proof.verify(&statement, &mut transcript::<H>()).is_ok());
+
+        // Proving with invalid witness for one of statements produces invalid proof
+        let (_, wrong_ddh_witness) = ddh_instance::<E>();
+        let proof = SigmaProof::<E, P<E, H>>::prove(
+            &statement,
+            &(Scalar::random(), wrong_ddh_witness),
+            &mut transcript::<H>(),
+        );
+        assert!(proof.verify(&statement, &mut transcript::<H>()).is_err());
+    }
+
+    test_for_all_curves_and_hashes!(or_proof);
+    fn or_proof<E: Curve, H: Digest + Clone>() {
+        type P<E, H> = Or<DLogProof<E, H>, DLogProof<E, H>>;
+        let a = Scalar::<E>::random();
+        let b = Scalar::<E>::random();
+        let statement = (Point::generator() * &a, Point::generator() * &b);
+
+        for witness in [OrWitness::Left(a), OrWitness::Right(b)] {
+            let proof =
+                SigmaProof::<E, P<E, H>>::prove(&statement, &witness, &mut transcript::<H>());
+            assert!(proof.verify(&statement, &mut transcript::<H>()).is_ok());
+
+            let mut another_transcript = transcript::<H>();
+            another_transcript.append_message(b"session_id", b"2");
+            assert!(proof.verify(&statement, &mut another_transcript).is_err());
+        }
+
+        // Witness doesn't match any of the statements
+        let proof = SigmaProof::<E, P<E, H>>::prove(
+            &statement,
+            &OrWitness::Left(Scalar::random()),
+            &mut transcript::<H>(),
+        );
+        assert!(proof.verify(&statement, &mut transcript::<H>()).is_err());
+    }
+
+    test_for_all_curves_and_hashes!(or_n_proof);
+    fn or_n_proof<E: Curve, H: Digest + Clone>() {
+        type P<E, H> = OrN<DLogProof<E, H>>;
+        let secrets: Vec<Scalar<E>> = (0..4).map(|_| Scalar::random()).collect();
+        let statement: Vec<Point<E>> = secrets.iter().map(|s| Point::generator() * s).collect();
+
+        for (index, witness) in secrets.into_iter().enumerate() {
+            let witness = OrNWitness { index, witness };
+            let proof =
+                SigmaProof::<E, P<E, H>>::prove(&statement, &witness, &mut transcript::<H>());
+            assert!(proof.verify(&statement, &mut transcript::<H>()).is_ok());
+            assert!(proof
+                .verify(&statement[..3].to_vec(), &mut transcript::<H>())
+                .is_err());
+        }
+
+        let witness = OrNWitness {
+            index: 2,
+            witness: Scalar::random(),
+        };
+        let proof = SigmaProof::<E, P<E, H>>::prove(&statement, &witness, &mut transcript::<H>());
+        assert!(proof.verify(&statement, &mut transcript::<H>()).is_err());
+    }
+}
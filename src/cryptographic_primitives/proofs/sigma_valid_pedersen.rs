@@ -13,6 +13,8 @@ use crate::cryptographic_primitives::hashing::{Digest, DigestExt};
 use crate::elliptic::curves::{Curve, Point, Scalar};
 use crate::marker::HashChoice;
 
+use super::sigma_protocol::SigmaProtocol;
+use super::transcript::Transcript;
 use super::ProofError;
 
 /// protocol for proving that Pedersen commitment c was constructed correctly which is the same as
@@ -38,27 +40,53 @@ pub struct PedersenProof<E: Curve, H: Digest + Clone> {
 }
 
 impl<E: Curve, H: Digest + Clone> PedersenProof<E, H> {
-    #[allow(clippy::many_single_char_names)]
     pub fn prove(m: &Scalar<E>, r: &Scalar<E>) -> PedersenProof<E, H> {
-        let g = Point::<E>::generator();
-        let h = Point::<E>::base_point2();
-        let s1 = Scalar::random();
-        let s2 = Scalar::random();
-        let a1 = g * &s1;
-        let a2 = h * &s2;
+        Self::prove_with_challenge(m, r, |com, a1, a2| Self::hash_challenge(com, a1, a2))
+    }
+
+    pub fn verify(proof: &PedersenProof<E, H>) -> Result<(), ProofError> {
+        let e = Self::hash_challenge(&proof.com, &proof.a1, &proof.a2);
+        proof.verify_challenge(&e)
+    }
+
+    /// Proves knowledge of `m` and `r` committed in `com = mG + rH`, deriving the challenge from
+    /// the transcript
+    ///
+    /// Statement and commitments are appended to the transcript, so it has to be in the same state
+    /// when [verifying](Self::verify_with_transcript) the proof.
+    pub fn prove_with_transcript(
+        m: &Scalar<E>,
+        r: &Scalar<E>,
+        transcript: &mut Transcript<H>,
+    ) -> PedersenProof<E, H> {
+        Self::prove_with_challenge(m, r, |com, a1, a2| {
+            Self::transcript_challenge(transcript, com, a1, a2)
+        })
+    }
+
+    /// Verifies the proof produced by [prove_with_transcript](Self::prove_with_transcript)
+    pub fn verify_with_transcript(
+        proof: &PedersenProof<E, H>,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let e = Self::transcript_challenge(transcript, &proof.com, &proof.a1, &proof.a2);
+        proof.verify_challenge(&e)
+    }
+
+    fn prove_with_challenge(
+        m: &Scalar<E>,
+        r: &Scalar<E>,
+        challenge: impl FnOnce(&Point<E>, &Point<E>, &Point<E>) -> Scalar<E>,
+    ) -> PedersenProof<E, H> {
         let com: Point<E> = PedersenCommitment::create_commitment_with_user_defined_randomness(
             &m.to_bigint(),
             &r.to_bigint(),
         );
+        let witness = (m.clone(), r.clone());
 
-        let e = H::new()
-            .chain_points([&g.to_point(), h, &com, &a1, &a2])
-            .result_scalar();
-
-        let em = &e * m;
-        let z1 = &s1 + em;
-        let er = &e * r;
-        let z2 = &s2 + er;
+        let ((a1, a2), secret) = Self::commit(&com, &witness);
+        let e = challenge(&com, &a1, &a2);
+        let (z1, z2) = Self::respond(&com, &witness, secret, &e);
 
         PedersenProof {
             e,
@@ -71,27 +99,120 @@ impl<E: Curve, H: Digest + Clone> PedersenProof<E, H> {
         }
     }
 
-    pub fn verify(proof: &PedersenProof<E, H>) -> Result<(), ProofError> {
+    fn verify_challenge(&self, e: &Scalar<E>) -> Result<(), ProofError> {
+        Self::verify_response(
+            &self.com,
+            &(self.a1.clone(), self.a2.clone()),
+            e,
+            &(self.z1.clone(), self.z2.clone()),
+        )
+    }
+
+    fn hash_challenge(com: &Point<E>, a1: &Point<E>, a2: &Point<E>) -> Scalar<E> {
         let g = Point::<E>::generator();
         let h = Point::<E>::base_point2();
+        H::new()
+            .chain_points([&g.to_point(), h, com, a1, a2])
+            .result_scalar()
+    }
+
+    fn transcript_challenge(
+        transcript: &mut Transcript<H>,
+        com: &Point<E>,
+        a1: &Point<E>,
+        a2: &Point<E>,
+    ) -> Scalar<E> {
+        transcript.append_message(b"proof", b"curv/pedersen-proof");
+        transcript.append_point(b"g", &Point::<E>::generator().to_point());
+        transcript.append_point(b"h", Point::<E>::base_point2());
+        transcript.append_point(b"com", com);
+        transcript.append_point(b"a1", a1);
+        transcript.append_point(b"a2", a2);
+        transcript.challenge_scalar(b"challenge")
+    }
+}
 
-        let e = H::new()
-            .chain_points([&g.to_point(), h, &proof.com, &proof.a1, &proof.a2])
-            .result_scalar();
+/// Pedersen commitment opening as a [sigma protocol](SigmaProtocol): statement is `c`, witness is
+/// `(m, r)`, commitment is `(A1, A2)`, response is `(z1, z2)`
+impl<E: Curve, H: Digest + Clone> SigmaProtocol<E> for PedersenProof<E, H> {
+    type Statement = Point<E>;
+    type Witness = (Scalar<E>, Scalar<E>);
+    type Commitment = (Point<E>, Point<E>);
+    type CommitmentSecret = (Scalar<E>, Scalar<E>);
+    type Response = (Scalar<E>, Scalar<E>);
 
-        let z1g = g * &proof.z1;
-        let z2h = h * &proof.z2;
+    fn commit(
+        _com: &Point<E>,
+        _witness: &(Scalar<E>, Scalar<E>),
+    ) -> ((Point<E>, Point<E>), (Scalar<E>, Scalar<E>)) {
+        let g = Point::<E>::generator();
+        let h = Point::<E>::base_point2();
+        let s1 = Scalar::random();
+        let s2 = Scalar::random();
+        let a1 = g * &s1;
+        let a2 = h * &s2;
+        ((a1, a2), (s1, s2))
+    }
+
+    fn respond(
+        _com: &Point<E>,
+        (m, r): &(Scalar<E>, Scalar<E>),
+        (s1, s2): (Scalar<E>, Scalar<E>),
+        e: &Scalar<E>,
+    ) -> (Scalar<E>, Scalar<E>) {
+        let em = e * m;
+        let z1 = s1 + em;
+        let er = e * r;
+        let z2 = s2 + er;
+        (z1, z2)
+    }
+
+    fn verify_response(
+        com: &Point<E>,
+        (a1, a2): &(Point<E>, Point<E>),
+        e: &Scalar<E>,
+        (z1, z2): &(Scalar<E>, Scalar<E>),
+    ) -> Result<(), ProofError> {
+        let g = Point::<E>::generator();
+        let h = Point::<E>::base_point2();
+        let z1g = g * z1;
+        let z2h = h * z2;
         let lhs = &z1g + &z2h;
-        let rhs = &proof.a1 + &proof.a2;
-        let ecom = &proof.com * &e;
+        let rhs = a1 + a2;
+        let ecom = com * e;
         let rhs = rhs + &ecom;
-
         if lhs == rhs {
             Ok(())
         } else {
             Err(ProofError)
         }
     }
+
+    fn simulate(com: &Point<E>, e: &Scalar<E>) -> ((Point<E>, Point<E>), (Scalar<E>, Scalar<E>)) {
+        let g = Point::<E>::generator();
+        let h = Point::<E>::base_point2();
+        let z1 = Scalar::random();
+        let z2 = Scalar::random();
+        // Verifier checks only A1 + A2, so A1 is sampled at random and A2 is fixed by the equation
+        let a1 = g * Scalar::random();
+        let a2 = g * &z1 + h * &z2 - com * e - &a1;
+        ((a1, a2), (z1, z2))
+    }
+
+    fn append_statement<T: Digest + Clone>(transcript: &mut Transcript<T>, com: &Point<E>) {
+        transcript.append_message(b"protocol", b"pedersen");
+        transcript.append_point(b"g", &Point::<E>::generator().to_point());
+        transcript.append_point(b"h", Point::<E>::base_point2());
+        transcript.append_point(b"com", com);
+    }
+
+    fn append_commitment<T: Digest + Clone>(
+        transcript: &mut Transcript<T>,
+        (a1, a2): &(Point<E>, Point<E>),
+    ) {
+        transcript.append_point(b"a1", a1);
+        transcript.append_point(b"a2", a2);
+    }
 }
 
 #[cfg(test)]
@@ -105,4 +226,19 @@ mod tests {
         let pedersen_proof = PedersenProof::<E, H>::prove(&m, &r);
         PedersenProof::verify(&pedersen_proof).expect("error pedersen");
     }
+
+    crate::test_for_all_curves_and_hashes!(test_pedersen_proof_with_transcript);
+    fn test_pedersen_proof_with_transcript<E: Curve, H: Digest + Clone>() {
+        let transcript = |session_id: &[u8]| {
+            let mut t = Transcript::<H>::new(b"test");
+            t.append_message(b"session_id", session_id);
+            t
+        };
+        let m = Scalar::random();
+        let r = Scalar::random();
+        let proof = PedersenProof::<E, H>::prove_with_transcript(&m, &r, &mut transcript(b"1"));
+        assert!(PedersenProof::verify_with_transcript(&proof, &mut transcript(b"1")).is_ok());
+        assert!(PedersenProof::verify_with_transcript(&proof, &mut transcript(b"2")).is_err());
+        assert!(PedersenProof::verify(&proof).is_err());
+    }
 }
@@ -13,6 +13,8 @@ use crate::cryptographic_primitives::hashing::{Digest, DigestExt};
 use crate::elliptic::curves::{Curve, Point, Scalar};
 use crate::marker::HashChoice;
 
+use super::sigma_protocol::SigmaProtocol;
+use super::transcript::Transcript;
 use super::ProofError;
 
 /// protocol for proving that Pedersen commitment c was constructed correctly which is the same as
@@ -36,27 +38,58 @@ pub struct PedersenBlindingProof<E: Curve, H: Digest + Clone> {
 }
 
 impl<E: Curve, H: Digest + Clone> PedersenBlindingProof<E, H> {
-    #[allow(clippy::many_single_char_names)]
     //TODO: add self verification to prover proof
     pub fn prove(m: &Scalar<E>, r: &Scalar<E>) -> PedersenBlindingProof<E, H> {
-        let h = Point::<E>::base_point2();
-        let s = Scalar::<E>::random();
-        let a = h * &s;
+        Self::prove_with_challenge(m, r, |com, a| Self::hash_challenge(m, com, a))
+    }
+
+    pub fn verify(proof: &PedersenBlindingProof<E, H>) -> Result<(), ProofError> {
+        let e = Self::hash_challenge(&proof.m, &proof.com, &proof.a);
+        proof.verify_challenge(&e)
+    }
+
+    /// Proves that `com = mG + rH` for public `m`, deriving the challenge from the transcript
+    ///
+    /// Statement and commitment are appended to the transcript, so it has to be in the same state
+    /// when [verifying](Self::verify_with_transcript) the proof.
+    pub fn prove_with_transcript(
+        m: &Scalar<E>,
+        r: &Scalar<E>,
+        transcript: &mut Transcript<H>,
+    ) -> PedersenBlindingProof<E, H> {
+        Self::prove_with_challenge(m, r, |com, a| {
+            Self::transcript_challenge(transcript, m, com, a)
+        })
+    }
+
+    /// Verifies the proof produced by [prove_with_transcript](Self::prove_with_transcript)
+    pub fn verify_with_transcript(
+        proof: &PedersenBlindingProof<E, H>,
+        transcript: &mut Transcript<H>,
+    ) -> Result<(), ProofError> {
+        let e = Self::transcript_challenge(transcript, &proof.m, &proof.com, &proof.a);
+        proof.verify_challenge(&e)
+    }
+
+    fn prove_with_challenge(
+        m: &Scalar<E>,
+        r: &Scalar<E>,
+        challenge: impl FnOnce(&Point<E>, &Point<E>) -> Scalar<E>,
+    ) -> PedersenBlindingProof<E, H> {
         let com: Point<E> = PedersenCommitment::create_commitment_with_user_defined_randomness(
             &m.to_bigint(),
             &r.to_bigint(),
         );
-        let g = Point::<E>::generator();
-        let e = H::new()
-            .chain_points([g.as_point(), h, &com, &a])
-            .chain_scalar(m)
-            .result_scalar();
+        let statement = (m.clone(), com);
+
+        let (a, s) = Self::commit(&statement, r);
+        let e = challenge(&statement.1, &a);
+        let z = Self::respond(&statement, r, s, &e);
 
-        let er = &e * r;
-        let z = &s + &er;
+        let (m, com) = statement;
         PedersenBlindingProof {
             e,
-            m: m.clone(),
+            m,
             a,
             com,
             z,
@@ -64,27 +97,104 @@ impl<E: Curve, H: Digest + Clone> PedersenBlindingProof<E, H> {
         }
     }
 
-    pub fn verify(proof: &PedersenBlindingProof<E, H>) -> Result<(), ProofError> {
+    fn verify_challenge(&self, e: &Scalar<E>) -> Result<(), ProofError> {
+        Self::verify_response(&(self.m.clone(), self.com.clone()), &self.a, e, &self.z)
+    }
+
+    fn hash_challenge(m: &Scalar<E>, com: &Point<E>, a: &Point<E>) -> Scalar<E> {
         let g = Point::<E>::generator();
         let h = Point::<E>::base_point2();
-        let e = H::new()
-            .chain_points([g.as_point(), h, &proof.com, &proof.a])
-            .chain_scalar(&proof.m)
-            .result_scalar();
-
-        let zh = h * &proof.z;
-        let mg = g * &proof.m;
-        let emg = mg * &e;
-        let lhs = zh + emg;
-        let ecom = &proof.com * &e;
-        let rhs = ecom + &proof.a;
+        H::new()
+            .chain_points([g.as_point(), h, com, a])
+            .chain_scalar(m)
+            .result_scalar()
+    }
+
+    fn transcript_challenge(
+        transcript: &mut Transcript<H>,
+        m: &Scalar<E>,
+        com: &Point<E>,
+        a: &Point<E>,
+    ) -> Scalar<E> {
+        transcript.append_message(b"proof", b"curv/pedersen-blinding-proof");
+        transcript.append_point(b"g", &Point::<E>::generator().to_point());
+        transcript.append_point(b"h", Point::<E>::base_point2());
+        transcript.append_point(b"com", com);
+        transcript.append_scalar(b"m", m);
+        transcript.append_point(b"a", a);
+        transcript.challenge_scalar(b"challenge")
+    }
+}
+
+/// Pedersen commitment blinding as a [sigma protocol](SigmaProtocol): statement is `(m, c)`,
+/// witness is `r`, commitment is `A`, response is `z`
+impl<E: Curve, H: Digest + Clone> SigmaProtocol<E> for PedersenBlindingProof<E, H> {
+    type Statement = (Scalar<E>, Point<E>);
+    type Witness = Scalar<E>;
+    type Commitment = Point<E>;
+    type CommitmentSecret = Scalar<E>;
+    type Response = Scalar<E>;
 
+    fn commit(_statement: &(Scalar<E>, Point<E>), _r: &Scalar<E>) -> (Point<E>, Scalar<E>) {
+        let h = Point::<E>::base_point2();
+        let s = Scalar::<E>::random();
+        let a = h * &s;
+        (a, s)
+    }
+
+    fn respond(
+        _statement: &(Scalar<E>, Point<E>),
+        r: &Scalar<E>,
+        s: Scalar<E>,
+        e: &Scalar<E>,
+    ) -> Scalar<E> {
+        let er = e * r;
+        s + &er
+    }
+
+    fn verify_response(
+        (m, com): &(Scalar<E>, Point<E>),
+        a: &Point<E>,
+        e: &Scalar<E>,
+        z: &Scalar<E>,
+    ) -> Result<(), ProofError> {
+        let g = Point::<E>::generator();
+        let h = Point::<E>::base_point2();
+        let zh = h * z;
+        let mg = g * m;
+        let emg = mg * e;
+        let lhs = zh + emg;
+        let ecom = com * e;
+        let rhs = ecom + a;
         if lhs == rhs {
             Ok(())
         } else {
             Err(ProofError)
         }
     }
+
+    fn simulate((m, com): &(Scalar<E>, Point<E>), e: &Scalar<E>) -> (Point<E>, Scalar<E>) {
+        let g = Point::<E>::generator();
+        let h = Point::<E>::base_point2();
+        let z = Scalar::random();
+        let a = h * &z + g * m * e - com * e;
+        (a, z)
+    }
+
+    fn append_statement<T: Digest + Clone>(
+        transcript: &mut Transcript<T>,
+        (m, com): &(Scalar<E>, Point<E>),
+    ) {
+        transcript.append_message(b"protocol", b"pedersen-blinding");
+        transcript.append_point(b"g", &Point::<E>::generator().to_point());
+        transcript.append_point(b"h", Point::<E>::base_point2());
+        transcript.append_point(b"com", com);
+        transcript.append_scalar(b"m", m);
+    }
+
+    fn append_commitment<T: Digest + Clone>(transcript: &mut Transcript<T>, a: &Point<E>) {
+        transcript.append_point(b"a", a);
+    }
 }
 
 #[cfg(test)]
@@ -98,4 +208,24 @@ mod tests {
         let pedersen_proof = PedersenBlindingProof::<E, H>::prove(&m, &r);
         PedersenBlindingProof::verify(&pedersen_proof).expect("error pedersen blind");
     }
+
+    crate::test_for_all_curves_and_hashes!(test_pedersen_blind_proof_with_transcript);
+    fn test_pedersen_blind_proof_with_transcript<E: Curve, H: Digest + Clone>() {
+        let transcript = |session_id: &[u8]| {
+            let mut t = Transcript::<H>::new(b"test");
+            t.append_message(b"session_id", session_id);
+            t
+        };
+        let m = Scalar::random();
+        let r = Scalar::random();
+        let proof =
+            PedersenBlindingProof::<E, H>::prove_with_transcript(&m, &r, &mut transcript(b"1"));
+        assert!(
+            PedersenBlindingProof::verify_with_transcript(&proof, &mut transcript(b"1")).is_ok()
+        );
+        assert!(
+            PedersenBlindingProof::verify_with_transcript(&proof, &mut transcript(b"2")).is_err()
+        );
+        assert!(PedersenBlindingProof::verify(&proof).is_err());
+    }
 }
@@ -0,0 +1,207 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use digest::Digest;
+
+use crate::arithmetic::*;
+use crate::cryptographic_primitives::hashing::DigestExt;
+use crate::elliptic::curves::{Curve, Point, Scalar};
+
+/// Transcript of public-coin protocol used to derive Fiat-Shamir challenges
+///
+/// Inspired by [Merlin]: prover and verifier append labelled messages (statement, commitments,
+/// session context) to the transcript, and then extract challenges from it. Challenge depends
+/// on everything appended before, including previous challenges, so a proof is bound to the
+/// transcript it was produced with and cannot be replayed in a different context.
+///
+/// Every message is framed with its label and length before being absorbed by the hash function,
+/// so distinct sequences of messages never produce the same hash input.
+///
+/// ## Example
+/// Binding DLog proof to a session:
+///
+/// ```rust
+/// use curv::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+/// use curv::cryptographic_primitives::proofs::transcript::Transcript;
+/// use curv::elliptic::curves::{Scalar, Secp256k1};
+/// use sha2::Sha256;
+///
+/// let session_id = b"session #1";
+///
+/// // Prover
+/// let mut transcript = Transcript::<Sha256>::new(b"MY-PROTOCOL-V1");
+/// transcript.append_message(b"session_id", session_id);
+/// transcript.append_u64(b"party_index", 1);
+/// let sk = Scalar::<Secp256k1>::random();
+/// let proof = DLogProof::prove_with_transcript(&sk, &mut transcript);
+///
+/// // Verifier
+/// let mut transcript = Transcript::<Sha256>::new(b"MY-PROTOCOL-V1");
+/// transcript.append_message(b"session_id", session_id);
+/// transcript.append_u64(b"party_index", 1);
+/// assert!(DLogProof::verify_with_transcript(&proof, &mut transcript).is_ok());
+///
+/// // Proof is not valid in another session
+/// let mut transcript = Transcript::<Sha256>::new(b"MY-PROTOCOL-V1");
+/// transcript.append_message(b"session_id", b"session #2");
+/// transcript.append_u64(b"party_index", 1);
+/// assert!(DLogProof::verify_with_transcript(&proof, &mut transcript).is_err());
+/// ```
+///
+/// [Merlin]: https://merlin.cool
+pub struct Transcript<H: Digest + Clone> {
+    hasher: H,
+}
+
+impl<H: Digest + Clone> Transcript<H> {
+    /// Starts a new transcript of protocol identified by `label`
+    pub fn new(label: &'static [u8]) -> Self {
+        let mut transcript = Self { hasher: H::new() };
+        transcript.append_message(b"curv-transcript-v1", label);
+        transcript
+    }
+
+    /// Appends a labelled message to the transcript
+    pub fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        let label_len = u32::try_from(label.len()).expect("label is too long");
+        let message_len = u64::try_from(message.len()).expect("message is too long");
+        self.hasher.update(label_len.to_be_bytes());
+        self.hasher.update(label);
+        self.hasher.update(message_len.to_be_bytes());
+        self.hasher.update(message);
+    }
+
+    /// Appends a labelled integer to the transcript
+    pub fn append_u64(&mut self, label: &'static [u8], n: u64) {
+        self.append_message(label, &n.to_be_bytes())
+    }
+
+    /// Appends a labelled bigint to the transcript
+    pub fn append_bigint(&mut self, label: &'static [u8], n: &BigInt) {
+        self.append_message(label, &n.to_bytes())
+    }
+
+    /// Appends a labelled point (in compressed form) to the transcript
+    pub fn append_point<E: Curve>(&mut self, label: &'static [u8], point: &Point<E>) {
+        self.append_message(label, &point.to_bytes(true))
+    }
+
+    /// Appends a labelled list of points to the transcript
+    pub fn append_points<'p, E: Curve>(
+        &mut self,
+        label: &'static [u8],
+        points: impl IntoIterator<Item = &'p Point<E>>,
+    ) {
+        let points = points.into_iter().collect::<Vec<_>>();
+        self.append_u64(label, points.len() as u64);
+        for point in points {
+            self.append_point(label, point)
+        }
+    }
+
+    /// Appends a labelled scalar to the transcript
+    pub fn append_scalar<E: Curve>(&mut self, label: &'static [u8], scalar: &Scalar<E>) {
+        self.append_message(label, &scalar.to_bytes())
+    }
+
+    /// Appends a labelled list of scalars to the transcript
+    pub fn append_scalars<'s, E: Curve>(
+        &mut self,
+        label: &'static [u8],
+        scalars: impl IntoIterator<Item = &'s Scalar<E>>,
+    ) {
+        let scalars = scalars.into_iter().collect::<Vec<_>>();
+        self.append_u64(label, scalars.len() as u64);
+        for scalar in scalars {
+            self.append_scalar(label, scalar)
+        }
+    }
+
+    /// Extracts a challenge from the transcript
+    ///
+    /// Challenge is derived from everything that was appended to the transcript before, and then
+    /// it's appended to the transcript itself, so subsequent challenges depend on it.
+    pub fn challenge_scalar<E: Curve>(&mut self, label: &'static [u8]) -> Scalar<E> {
+        self.append_message(b"challenge", label);
+        let challenge = self.hasher.clone().result_scalar_wide::<E>();
+        self.append_scalar(label, &challenge);
+        challenge
+    }
+}
+
+impl<H: Digest + Clone> Clone for Transcript<H> {
+    fn clone(&self) -> Self {
+        Self {
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+impl<H: Digest + Clone> fmt::Debug for Transcript<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Transcript {{ .. }}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves_and_hashes;
+
+    test_for_all_curves_and_hashes!(transcript_is_deterministic);
+    fn transcript_is_deterministic<E: Curve, H: Digest + Clone>() {
+        let transcript = |message: &[u8]| {
+            let mut t = Transcript::<H>::new(b"test");
+            t.append_point(b"point", &Point::<E>::generator().to_point());
+            t.append_message(b"message", message);
+            t
+        };
+        let c1 = transcript(b"hello").challenge_scalar::<E>(b"c");
+        let c2 = transcript(b"hello").challenge_scalar::<E>(b"c");
+        let c3 = transcript(b"hello!").challenge_scalar::<E>(b"c");
+        assert_eq!(c1, c2);
+        assert_ne!(c1, c3);
+    }
+
+    test_for_all_curves_and_hashes!(transcript_frames_messages);
+    fn transcript_frames_messages<E: Curve, H: Digest + Clone>() {
+        let mut t1 = Transcript::<H>::new(b"test");
+        t1.append_message(b"a", b"bc");
+        let mut t2 = Transcript::<H>::new(b"test");
+        t2.append_message(b"ab", b"c");
+        assert_ne!(
+            t1.challenge_scalar::<E>(b"c"),
+            t2.challenge_scalar::<E>(b"c")
+        );
+
+        let mut t1 = Transcript::<H>::new(b"test");
+        t1.append_message(b"a", b"b");
+        t1.append_message(b"a", b"");
+        let mut t2 = Transcript::<H>::new(b"test");
+        t2.append_message(b"a", b"");
+        t2.append_message(b"a", b"b");
+        assert_ne!(
+            t1.challenge_scalar::<E>(b"c"),
+            t2.challenge_scalar::<E>(b"c")
+        );
+    }
+
+    test_for_all_curves_and_hashes!(challenges_depend_on_previous_challenges);
+    fn challenges_depend_on_previous_challenges<E: Curve, H: Digest + Clone>() {
+        let mut t1 = Transcript::<H>::new(b"test");
+        let c1 = t1.challenge_scalar::<E>(b"c");
+        let c2 = t1.challenge_scalar::<E>(b"c");
+        assert_ne!(c1, c2);
+
+        let mut t2 = Transcript::<H>::new(b"test");
+        assert_eq!(t2.challenge_scalar::<E>(b"c"), c1);
+        assert_eq!(t2.challenge_scalar::<E>(b"c"), c2);
+    }
+}
@@ -0,0 +1,990 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Dealerless distributed key generation
+//!
+//! Every party acts as a dealer: it shares a random secret among all parties, and the joint secret
+//! key is the sum of secrets of all qualified dealers. Nobody learns the joint secret key, every
+//! party ends up with a share of it, and all parties agree on the aggregated [VerifiableSS] that
+//! can be used with the rest of the library (e.g. [threshold signing]).
+//!
+//! The protocol is Pedersen's DKG with complaints handling as described in "Secure Distributed
+//! Key Generation for Discrete-Log Based Cryptosystems" by Gennaro, Jarecki, Krawczyk and Rabin
+//! (GJKR):
+//! 1. Every party broadcasts commitments to its polynomial, and privately sends shares to other
+//!    parties.
+//! 2. Every party broadcasts complaints against dealers whose shares don't match commitments.
+//! 3. Dealers answer complaints by revealing disputed shares. Dealers that received more than `t`
+//!    complaints or failed to answer them are disqualified.
+//! 4. In [Pedersen mode](CommitmentMode::Pedersen), qualified dealers open Feldman commitments to
+//!    their polynomials, parties broadcast shares that don't match the openings, and polynomials of
+//!    exposed dealers are reconstructed from shares revealed by all parties. This takes two or three
+//!    extra rounds.
+//! 5. Parties jointly compute a proof of knowledge of the joint secret key, so the resulting
+//!    [VerifiableSS] passes [validate_share](VerifiableSS::validate_share).
+//!
+//! Protocol tolerates up to `t` malicious parties if `n ≥ 2t+1`: misbehaving dealers are either
+//! disqualified or their contribution is recovered, so honest parties always finish with the same
+//! output. Protocol requires a broadcast channel and private point-to-point channels.
+//!
+//! [Dkg] is a state machine. Every round, each party broadcasts a single message (and, in the first
+//! round, also sends a private message to every other party), then it collects messages of the
+//! round from other parties and passes them to [Dkg::proceed]. Messages that are missing or
+//! malformed are treated as misbehaviour of their sender.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::dkg::{CommitmentMode, Dkg, Step};
+//! use curv::elliptic::curves::Secp256k1;
+//! use sha2::Sha256;
+//!
+//! let (t, n) = (1, 3);
+//! let mut parties = vec![];
+//! let mut messages = vec![];
+//! for i in 1..=n {
+//!     let (party, msgs) = Dkg::<Secp256k1, Sha256>::new(t, n, i, CommitmentMode::Pedersen).unwrap();
+//!     parties.push(party);
+//!     messages.extend(msgs);
+//! }
+//!
+//! let mut outputs = vec![];
+//! while outputs.is_empty() {
+//!     let mut next_messages = vec![];
+//!     for party in &mut parties {
+//!         let incoming: Vec<_> = messages
+//!             .iter()
+//!             .filter(|msg| msg.is_for(party.index()))
+//!             .cloned()
+//!             .collect();
+//!         match party.proceed(&incoming).unwrap() {
+//!             Step::Messages(msgs) => next_messages.extend(msgs),
+//!             Step::Finished(output) => outputs.push(output),
+//!         }
+//!     }
+//!     messages = next_messages;
+//! }
+//!
+//! for output in &outputs {
+//!     assert_eq!(output.public_key(), outputs[0].public_key());
+//!     assert!(output.vss.validate_share(&output.secret_share, output.index).is_ok());
+//! }
+//! ```
+//!
+//! [threshold signing]: crate::cryptographic_primitives::signatures::frost
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::{fmt, iter, mem};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::{
+    ShamirSecretSharing, VerifiableSS,
+};
+use crate::cryptographic_primitives::secret_sharing::pedersen_vss::PedersenVSS;
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Point, Scalar};
+use crate::marker::HashChoice;
+
+/// Commitments used by dealers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommitmentMode {
+    /// Dealers commit to coefficients of their polynomials as `a_k G` ([VerifiableSS])
+    ///
+    /// Commitments reveal public keys of dealers before disqualification, so malicious parties
+    /// can bias distribution of the joint public key by choosing whether to get disqualified.
+    Feldman,
+    /// Dealers commit to coefficients of their polynomials as `a_k G + b_k H`, where `H` is
+    /// [Point::base_point2] ([PedersenVSS])
+    ///
+    /// Commitments reveal nothing about secrets until the set of qualified dealers is fixed, so
+    /// the joint public key is uniformly distributed. Takes two or three extra rounds.
+    Pedersen,
+}
+
+/// Commitments to dealer's secret polynomial
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub enum Commitments<E: Curve, H: Digest + Clone> {
+    Feldman(VerifiableSS<E, H>),
+    Pedersen(PedersenVSS<E>),
+}
+
+/// Shares dealt by `dealer` to `receiver`
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DealtShare<E: Curve> {
+    pub dealer: u16,
+    pub receiver: u16,
+    /// Share of dealer's secret `f(receiver)`
+    pub share: Scalar<E>,
+    /// Share of blinding polynomial `g(receiver)`, present only in Pedersen mode
+    pub blinding: Option<Scalar<E>>,
+    /// Share of dealer's nonce `r(receiver)`, used to prove knowledge of the joint secret key
+    pub nonce_share: Scalar<E>,
+}
+
+/// Message sent by a party
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Msg<E: Curve, H: Digest + Clone> {
+    pub sender: u16,
+    /// Receiver of a private message, or `None` if message is broadcasted
+    pub receiver: Option<u16>,
+    pub body: MsgBody<E, H>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub enum MsgBody<E: Curve, H: Digest + Clone> {
+    /// Round 1, broadcast: commitments to dealer's secret and nonce polynomials
+    Commitments {
+        secret: Commitments<E, H>,
+        nonce: VerifiableSS<E, H>,
+    },
+    /// Round 1, private: shares dealt to the receiver
+    Share(DealtShare<E>),
+    /// Round 2: dealers whose shares don't match their commitments
+    Complaints(Vec<u16>),
+    /// Round 3: shares revealed by a dealer in response to complaints against it
+    Answers(Vec<DealtShare<E>>),
+    /// Pedersen mode: Feldman commitments to dealer's secret polynomial
+    Opening(VerifiableSS<E, H>),
+    /// Pedersen mode: shares that don't match openings of their dealers
+    Disputes(Vec<DealtShare<E>>),
+    /// Pedersen mode: shares dealt by dealers that were exposed by disputes
+    Reconstruction(Vec<DealtShare<E>>),
+    /// Last round: share of proof of knowledge of the joint secret key
+    ProofShare(Scalar<E>),
+}
+
+/// Result of [Dkg::proceed]
+#[derive(Debug)]
+pub enum Step<E: Curve, H: Digest + Clone> {
+    /// Messages that need to be sent before proceeding to the next round
+    Messages(Vec<Msg<E, H>>),
+    /// Protocol is completed
+    Finished(DkgOutput<E, H>),
+}
+
+/// Share of the joint secret key
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DkgOutput<E: Curve, H: Digest + Clone> {
+    /// Index of the party
+    pub index: u16,
+    /// Share of the joint secret key `x(index)`
+    pub secret_share: Scalar<E>,
+    /// Sum of VSS of all qualified dealers
+    pub vss: VerifiableSS<E, H>,
+    /// Indexes of dealers that contributed to the joint secret key
+    pub qualified: Vec<u16>,
+}
+
+/// State of a party in distributed key generation protocol
+pub struct Dkg<E: Curve, H: Digest + Clone> {
+    parameters: ShamirSecretSharing,
+    index: u16,
+    mode: CommitmentMode,
+    /// Feldman VSS of own secret, it's kept private until opening in Pedersen mode
+    vss: VerifiableSS<E, H>,
+    dealt_shares: Vec<DealtShare<E>>,
+    /// Dealers that are not disqualified (yet)
+    dealers: BTreeMap<u16, Dealer<E, H>>,
+    round: Round<E>,
+}
+
+struct Dealer<E: Curve, H: Digest + Clone> {
+    commitments: Commitments<E, H>,
+    nonce: VerifiableSS<E, H>,
+    /// Valid share dealt to this party, `None` if it's missing or invalid
+    share: Option<DealtShare<E>>,
+    /// Feldman commitments to dealer's secret polynomial, `None` if dealer failed to open them
+    opening: Option<Vec<Point<E>>>,
+}
+
+enum Round<E: Curve> {
+    Commitments,
+    Complaints {
+        own: Vec<u16>,
+    },
+    Answers {
+        complaints: BTreeMap<u16, BTreeSet<u16>>,
+    },
+    Opening,
+    Disputes {
+        own: Vec<DealtShare<E>>,
+    },
+    Reconstruction {
+        exposed: Vec<u16>,
+    },
+    Confirmation(Confirmation<E>),
+    Finished,
+}
+
+struct Confirmation<E: Curve> {
+    commitments: Vec<Point<E>>,
+    nonce_commitments: Vec<Point<E>>,
+    challenge: Scalar<E>,
+    secret_share: Scalar<E>,
+    proof_share: Scalar<E>,
+}
+
+impl<E: Curve, H: Digest + Clone> Dkg<E, H> {
+    /// Starts the protocol for party `index` (`1..=n`), returns messages of the first round
+    ///
+    /// Returns error if `t ≥ n` or index is out of range.
+    pub fn new(
+        t: u16,
+        n: u16,
+        index: u16,
+        mode: CommitmentMode,
+    ) -> Result<(Self, Vec<Msg<E, H>>), DkgError> {
+        if t >= n || index == 0 || index > n {
+            return Err(DkgError::InvalidParameters);
+        }
+        let secret = Scalar::random();
+        let (vss, commitments, shares, blinding_shares) = match mode {
+            CommitmentMode::Feldman => {
+                let (vss, shares) = VerifiableSS::<E, H>::share(t, n, &secret);
+                let commitments = Commitments::Feldman(vss.clone());
+                (vss, commitments, shares.to_vec(), None)
+            }
+            CommitmentMode::Pedersen => {
+                let (pedersen_vss, shares) = PedersenVSS::<E>::share(t, n, &secret);
+                (
+                    shares.to_feldman(),
+                    Commitments::Pedersen(pedersen_vss),
+                    shares.to_vec(),
+                    Some(shares.blinding_shares().to_vec()),
+                )
+            }
+        };
+        let (nonce_vss, nonce_shares) = VerifiableSS::<E, H>::share(t, n, &Scalar::random());
+        let dealt_shares: Vec<_> = (1..=n)
+            .zip(shares.iter().zip(nonce_shares.iter()))
+            .map(|(receiver, (share, nonce_share))| DealtShare {
+                dealer: index,
+                receiver,
+                share: share.clone(),
+                blinding: blinding_shares
+                    .as_ref()
+                    .map(|b| b[usize::from(receiver) - 1].clone()),
+                nonce_share: nonce_share.clone(),
+            })
+            .collect();
+
+        let mut messages = vec![Msg {
+            sender: index,
+            receiver: None,
+            body: MsgBody::Commitments {
+                secret: commitments.clone(),
+                nonce: nonce_vss.clone(),
+            },
+        }];
+        messages.extend(
+            dealt_shares
+                .iter()
+                .filter(|share| share.receiver != index)
+                .map(|share| Msg {
+                    sender: index,
+                    receiver: Some(share.receiver),
+                    body: MsgBody::Share(share.clone()),
+                }),
+        );
+
+        let own_dealer = Dealer {
+            opening: match mode {
+                CommitmentMode::Feldman => Some(vss.commitments.clone()),
+                CommitmentMode::Pedersen => None,
+            },
+            commitments,
+            nonce: nonce_vss,
+            share: Some(dealt_shares[usize::from(index) - 1].clone()),
+        };
+        let party = Self {
+            parameters: vss.parameters.clone(),
+            index,
+            mode,
+            vss,
+            dealt_shares,
+            dealers: iter::once((index, own_dealer)).collect(),
+            round: Round::Commitments,
+        };
+        Ok((party, messages))
+    }
+
+    /// Index of the party
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Processes messages received from other parties in the current round
+    ///
+    /// Returns either messages of the next round, or the protocol output. Messages that don't
+    /// belong to the current round, or are not addressed to this party are ignored. Party can't
+    /// proceed after an error.
+    pub fn proceed(&mut self, messages: &[Msg<E, H>]) -> Result<Step<E, H>, DkgError> {
+        let n = self.parameters.share_count;
+        let messages: Vec<&Msg<E, H>> = messages
+            .iter()
+            .filter(|msg| msg.is_for(self.index) && msg.sender >= 1 && msg.sender <= n)
+            .collect();
+        match mem::replace(&mut self.round, Round::Finished) {
+            Round::Commitments => Ok(self.handle_commitments(&messages)),
+            Round::Complaints { own } => Ok(self.handle_complaints(&messages, own)),
+            Round::Answers { complaints } => self.handle_answers(&messages, complaints),
+            Round::Opening => Ok(self.handle_openings(&messages)),
+            Round::Disputes { own } => Ok(self.handle_disputes(&messages, own)),
+            Round::Reconstruction { exposed } => self.handle_reconstruction(&messages, exposed),
+            Round::Confirmation(confirmation) => self.handle_proof_shares(&messages, confirmation),
+            Round::Finished => Err(DkgError::ProtocolFinished),
+        }
+    }
+
+    fn handle_commitments(&mut self, messages: &[&Msg<E, H>]) -> Step<E, H> {
+        let commitments = collect(messages, false, |body| match body {
+            MsgBody::Commitments { secret, nonce } => Some((secret, nonce)),
+            _ => None,
+        });
+        let shares = collect(messages, true, |body| match body {
+            MsgBody::Share(share) => Some(share),
+            _ => None,
+        });
+
+        let mut complaints = vec![];
+        for (dealer, (secret, nonce)) in commitments {
+            let well_formed = is_well_formed(&self.parameters, nonce)
+                && match (self.mode, secret) {
+                    (CommitmentMode::Feldman, Commitments::Feldman(vss)) => {
+                        is_well_formed(&self.parameters, vss)
+                    }
+                    (CommitmentMode::Pedersen, Commitments::Pedersen(vss)) => {
+                        vss.parameters == self.parameters
+                            && vss.commitments.len() == usize::from(self.parameters.threshold) + 1
+                    }
+                    _ => false,
+                };
+            if !well_formed {
+                continue;
+            }
+
+            let mut entry = Dealer {
+                commitments: secret.clone(),
+                nonce: nonce.clone(),
+                share: None,
+                opening: match secret {
+                    Commitments::Feldman(vss) => Some(vss.commitments.clone()),
+                    Commitments::Pedersen(_) => None,
+                },
+            };
+            match shares.get(&dealer) {
+                Some(share)
+                    if share.dealer == dealer
+                        && share.receiver == self.index
+                        && entry.verify_share(share) =>
+                {
+                    entry.share = Some((*share).clone())
+                }
+                _ => complaints.push(dealer),
+            }
+            self.dealers.insert(dealer, entry);
+        }
+
+        self.round = Round::Complaints {
+            own: complaints.clone(),
+        };
+        self.broadcast(MsgBody::Complaints(complaints))
+    }
+
+    fn handle_complaints(&mut self, messages: &[&Msg<E, H>], own: Vec<u16>) -> Step<E, H> {
+        let received = collect(messages, false, |body| match body {
+            MsgBody::Complaints(complaints) => Some(complaints.as_slice()),
+            _ => None,
+        });
+
+        // dealer -> parties complaining against the dealer
+        let mut complaints = BTreeMap::<u16, BTreeSet<u16>>::new();
+        for (complainer, dealers) in received
+            .into_iter()
+            .chain(iter::once((self.index, &own[..])))
+        {
+            for &dealer in dealers {
+                complaints.entry(dealer).or_default().insert(complainer);
+            }
+        }
+        let answers = complaints
+            .get(&self.index)
+            .into_iter()
+            .flatten()
+            .map(|&complainer| self.dealt_shares[usize::from(complainer) - 1].clone())
+            .collect();
+
+        self.round = Round::Answers { complaints };
+        self.broadcast(MsgBody::Answers(answers))
+    }
+
+    fn handle_answers(
+        &mut self,
+        messages: &[&Msg<E, H>],
+        complaints: BTreeMap<u16, BTreeSet<u16>>,
+    ) -> Result<Step<E, H>, DkgError> {
+        let answers = collect(messages, false, |body| match body {
+            MsgBody::Answers(answers) => Some(answers),
+            _ => None,
+        });
+
+        let mut disqualified = vec![];
+        for (&dealer, entry) in self.dealers.iter_mut() {
+            let complainers = match complaints.get(&dealer) {
+                Some(complainers) => complainers,
+                None => continue,
+            };
+            if complainers.len() > usize::from(self.parameters.threshold) {
+                disqualified.push(dealer);
+                continue;
+            }
+            if dealer == self.index {
+                continue;
+            }
+            for &complainer in complainers {
+                let answer = answers.get(&dealer).and_then(|answers| {
+                    answers
+                        .iter()
+                        .find(|share| share.dealer == dealer && share.receiver == complainer)
+                });
+                match answer {
+                    Some(share) if entry.verify_share(share) => {
+                        if complainer == self.index {
+                            entry.share = Some(share.clone())
+                        }
+                    }
+                    _ => {
+                        disqualified.push(dealer);
+                        break;
+                    }
+                }
+            }
+        }
+        for dealer in disqualified {
+            self.dealers.remove(&dealer);
+        }
+        if self.dealers.is_empty() {
+            return Err(DkgError::NoQualifiedParties);
+        }
+
+        match self.mode {
+            CommitmentMode::Feldman => Ok(self.start_confirmation()),
+            CommitmentMode::Pedersen => {
+                if let Some(entry) = self.dealers.get_mut(&self.index) {
+                    entry.opening = Some(self.vss.commitments.clone());
+                }
+                self.round = Round::Opening;
+                Ok(self.broadcast(MsgBody::Opening(self.vss.clone())))
+            }
+        }
+    }
+
+    fn handle_openings(&mut self, messages: &[&Msg<E, H>]) -> Step<E, H> {
+        let openings = collect(messages, false, |body| match body {
+            MsgBody::Opening(vss) => Some(vss),
+            _ => None,
+        });
+
+        let mut disputes = vec![];
+        for (&dealer, entry) in self.dealers.iter_mut() {
+            if dealer == self.index {
+                continue;
+            }
+            let opening = match openings.get(&dealer) {
+                Some(vss) if is_well_formed(&self.parameters, vss) => vss,
+                _ => continue,
+            };
+            entry.opening = Some(opening.commitments.clone());
+            let share = entry
+                .share
+                .as_ref()
+                .expect("share of qualified dealer is known");
+            if opening.validate_share(&share.share, self.index).is_err() {
+                disputes.push(share.clone());
+            }
+        }
+
+        self.round = Round::Disputes {
+            own: disputes.clone(),
+        };
+        self.broadcast(MsgBody::Disputes(disputes))
+    }
+
+    fn handle_disputes(&mut self, messages: &[&Msg<E, H>], own: Vec<DealtShare<E>>) -> Step<E, H> {
+        let disputes = collect(messages, false, |body| match body {
+            MsgBody::Disputes(disputes) => Some(disputes.as_slice()),
+            _ => None,
+        });
+
+        // Dealers that didn't open their commitments are exposed without disputes
+        let mut exposed: BTreeSet<u16> = self
+            .dealers
+            .iter()
+            .filter(|(_, entry)| entry.opening.is_none())
+            .map(|(&dealer, _)| dealer)
+            .collect();
+        for share in disputes.values().copied().flatten().chain(&own) {
+            let entry = match self.dealers.get(&share.dealer) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            if let Some(opening) = &entry.opening {
+                let matches_opening = Point::generator() * &share.share
+                    == VerifiableSS::<E, H>::point_commitment(opening, share.receiver);
+                if entry.verify_share(share) && !matches_opening {
+                    exposed.insert(share.dealer);
+                }
+            }
+        }
+        if exposed.is_empty() {
+            return self.start_confirmation();
+        }
+
+        let revealed = exposed
+            .iter()
+            .map(|dealer| {
+                self.dealers[dealer]
+                    .share
+                    .clone()
+                    .expect("share of qualified dealer is known")
+            })
+            .collect();
+        self.round = Round::Reconstruction {
+            exposed: exposed.into_iter().collect(),
+        };
+        self.broadcast(MsgBody::Reconstruction(revealed))
+    }
+
+    fn handle_reconstruction(
+        &mut self,
+        messages: &[&Msg<E, H>],
+        exposed: Vec<u16>,
+    ) -> Result<Step<E, H>, DkgError> {
+        let revealed = collect(messages, false, |body| match body {
+            MsgBody::Reconstruction(shares) => Some(shares),
+            _ => None,
+        });
+
+        let required = usize::from(self.parameters.threshold) + 1;
+        for dealer in exposed {
+            let entry = self
+                .dealers
+                .get_mut(&dealer)
+                .expect("exposed dealer is qualified");
+            let own_share = entry
+                .share
+                .as_ref()
+                .expect("share of qualified dealer is known");
+            let points: Vec<(u16, Scalar<E>)> = revealed
+                .iter()
+                .filter_map(|(&sender, shares)| {
+                    shares.iter().find(|share| {
+                        share.dealer == dealer
+                            && share.receiver == sender
+                            && entry.verify_share(share)
+                    })
+                })
+                .chain(iter::once(own_share))
+                .take(required)
+                .map(|share| (share.receiver, share.share.clone()))
+                .collect();
+            if points.len() < required {
+                return Err(DkgError::ReconstructionFailed(dealer));
+            }
+            let (xs, ys): (Vec<Scalar<E>>, Vec<Scalar<E>>) = points
+                .into_iter()
+                .map(|(x, y)| (Scalar::from(x), y))
+                .unzip();
+            let g = Point::<E>::generator();
+            let polynomial = Polynomial::interpolate(&xs, &ys);
+            entry.opening = Some(polynomial.coefficients().iter().map(|c| g * c).collect());
+        }
+
+        Ok(self.start_confirmation())
+    }
+
+    fn start_confirmation(&mut self) -> Step<E, H> {
+        let coefficients_count = usize::from(self.parameters.threshold) + 1;
+        let mut commitments = vec![Point::zero(); coefficients_count];
+        let mut nonce_commitments = vec![Point::zero(); coefficients_count];
+        let mut secret_share = Scalar::zero();
+        let mut nonce_share = Scalar::zero();
+        for entry in self.dealers.values() {
+            let opening = entry.opening.as_ref().expect("qualified dealer is opened");
+            for (sum, commitment) in commitments.iter_mut().zip(opening) {
+                *sum = &*sum + commitment;
+            }
+            for (sum, commitment) in nonce_commitments.iter_mut().zip(&entry.nonce.commitments) {
+                *sum = &*sum + commitment;
+            }
+            let share = entry
+                .share
+                .as_ref()
+                .expect("share of qualified dealer is known");
+            secret_share = secret_share + &share.share;
+            nonce_share = nonce_share + &share.nonce_share;
+        }
+
+        // Schnorr proof `(R, z)` of knowledge of the joint secret key `x`, where `z = r - c x`, is
+        // computed by interpolating shares `z_i = r_i - c x_i`
+        let challenge = DLogProof::<E, H>::hash_challenge(&commitments[0], &nonce_commitments[0]);
+        let proof_share = nonce_share - &challenge * &secret_share;
+        self.round = Round::Confirmation(Confirmation {
+            commitments,
+            nonce_commitments,
+            challenge,
+            secret_share,
+            proof_share: proof_share.clone(),
+        });
+        self.broadcast(MsgBody::ProofShare(proof_share))
+    }
+
+    fn handle_proof_shares(
+        &mut self,
+        messages: &[&Msg<E, H>],
+        confirmation: Confirmation<E>,
+    ) -> Result<Step<E, H>, DkgError> {
+        let proof_shares = collect(messages, false, |body| match body {
+            MsgBody::ProofShare(proof_share) => Some(proof_share),
+            _ => None,
+        });
+
+        let required = usize::from(self.parameters.threshold) + 1;
+        let valid_shares: Vec<(u16, &Scalar<E>)> = proof_shares
+            .into_iter()
+            .chain(iter::once((self.index, &confirmation.proof_share)))
+            .filter(|(i, z_i)| {
+                let x_i = VerifiableSS::<E, H>::point_commitment(&confirmation.commitments, *i);
+                let r_i =
+                    VerifiableSS::<E, H>::point_commitment(&confirmation.nonce_commitments, *i);
+                Point::generator() * *z_i + x_i * &confirmation.challenge == r_i
+            })
+            .take(required)
+            .collect();
+        if valid_shares.len() < required {
+            return Err(DkgError::NotEnoughProofShares {
+                required,
+                received: valid_shares.len(),
+            });
+        }
+        let xs: Vec<Scalar<E>> = valid_shares.iter().map(|(i, _)| Scalar::from(*i)).collect();
+        let challenge_response = (0..)
+            .zip(&valid_shares)
+            .map(|(j, (_, z_j))| Polynomial::lagrange_basis(&Scalar::zero(), j, &xs) * *z_j)
+            .sum();
+
+        let proof = DLogProof {
+            pk: confirmation.commitments[0].clone(),
+            pk_t_rand_commitment: confirmation.nonce_commitments[0].clone(),
+            challenge_response,
+            hash_choice: HashChoice::new(),
+        };
+        Ok(Step::Finished(DkgOutput {
+            index: self.index,
+            secret_share: confirmation.secret_share,
+            vss: VerifiableSS {
+                parameters: self.parameters.clone(),
+                commitments: confirmation.commitments,
+                proof,
+            },
+            qualified: self.dealers.keys().copied().collect(),
+        }))
+    }
+
+    fn broadcast(&self, body: MsgBody<E, H>) -> Step<E, H> {
+        Step::Messages(vec![Msg {
+            sender: self.index,
+            receiver: None,
+            body,
+        }])
+    }
+}
+
+impl<E: Curve, H: Digest + Clone> Dealer<E, H> {
+    /// Checks shares against dealer's commitments
+    fn verify_share(&self, share: &DealtShare<E>) -> bool {
+        let share_is_valid = match (&self.commitments, &share.blinding) {
+            (Commitments::Feldman(vss), None) => {
+                vss.validate_share(&share.share, share.receiver).is_ok()
+            }
+            (Commitments::Pedersen(vss), Some(blinding)) => vss
+                .validate_share(&share.share, blinding, share.receiver)
+                .is_ok(),
+            _ => false,
+        };
+        share_is_valid
+            && self
+                .nonce
+                .validate_share(&share.nonce_share, share.receiver)
+                .is_ok()
+    }
+}
+
+impl<E: Curve, H: Digest + Clone> Msg<E, H> {
+    /// Checks whether the message needs to be delivered to party `index`, i.e. it's either a
+    /// broadcast message or a private message addressed to the party, and it's not sent by the
+    /// party itself
+    pub fn is_for(&self, index: u16) -> bool {
+        self.sender != index && self.receiver.is_none_or(|receiver| receiver == index)
+    }
+}
+
+impl<E: Curve, H: Digest + Clone> DkgOutput<E, H> {
+    /// Joint public key
+    pub fn public_key(&self) -> &Point<E> {
+        &self.vss.commitments[0]
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum DkgError {
+    #[error("threshold must be less than number of parties, party index must be in range 1..=n")]
+    InvalidParameters,
+    #[error("all parties are disqualified")]
+    NoQualifiedParties,
+    #[error("not enough valid shares to reconstruct polynomial of party {0}")]
+    ReconstructionFailed(u16),
+    #[error("expected at least {required} valid proof shares, received {received}")]
+    NotEnoughProofShares { required: usize, received: usize },
+    #[error("protocol is already finished")]
+    ProtocolFinished,
+}
+
+/// Collects messages of the same kind from distinct senders
+///
+/// A sender that sent more than one such message is ignored as it violates broadcast channel
+/// assumption.
+fn collect<'m, E, H, T>(
+    messages: &[&'m Msg<E, H>],
+    private: bool,
+    extract: impl Fn(&'m MsgBody<E, H>) -> Option<T>,
+) -> BTreeMap<u16, T>
+where
+    E: Curve,
+    H: Digest + Clone,
+{
+    let mut collected = BTreeMap::new();
+    for msg in messages {
+        if msg.receiver.is_some() != private {
+            continue;
+        }
+        if let Some(value) = extract(&msg.body) {
+            collected
+                .entry(msg.sender)
+                .and_modify(|v: &mut Option<T>| *v = None)
+                .or_insert(Some(value));
+        }
+    }
+    collected
+        .into_iter()
+        .filter_map(|(sender, value)| Some((sender, value?)))
+        .collect()
+}
+
+fn is_well_formed<E: Curve, H: Digest + Clone>(
+    parameters: &ShamirSecretSharing,
+    vss: &VerifiableSS<E, H>,
+) -> bool {
+    vss.parameters == *parameters
+        && vss.commitments.len() == usize::from(parameters.threshold) + 1
+        && vss.commitments[0] == vss.proof.pk
+        && DLogProof::verify(&vss.proof).is_ok()
+}
+
+impl<E: Curve> fmt::Debug for DealtShare<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(
+            f,
+            "DealtShare {{ dealer: {}, receiver: {}, .. }}",
+            self.dealer, self.receiver
+        )
+    }
+}
+
+impl<E: Curve, H: Digest + Clone> fmt::Debug for DkgOutput<E, H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        f.debug_struct("DkgOutput")
+            .field("index", &self.index)
+            .field("public_key", self.public_key())
+            .field("qualified", &self.qualified)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sha2::Sha256;
+
+    use crate::elliptic::curves::Secp256k1;
+    use crate::test_for_all_curves;
+
+    type Outputs<E> = Vec<Result<DkgOutput<E, Sha256>, DkgError>>;
+
+    /// Runs the protocol delivering messages between parties until every party either finishes or
+    /// fails, `tamper` can modify messages of every round before they're delivered
+    fn simulate<E: Curve>(
+        t: u16,
+        n: u16,
+        mode: CommitmentMode,
+        mut tamper: impl FnMut(&mut Vec<Msg<E, Sha256>>),
+    ) -> (Vec<Dkg<E, Sha256>>, Outputs<E>) {
+        let mut parties = vec![];
+        let mut messages = vec![];
+        for i in 1..=n {
+            let (party, msgs) = Dkg::new(t, n, i, mode).unwrap();
+            parties.push(party);
+            messages.extend(msgs);
+        }
+
+        let mut outputs: Vec<Option<Result<_, _>>> = vec![None; usize::from(n)];
+        while outputs.iter().any(Option::is_none) {
+            tamper(&mut messages);
+            let mut next_messages = vec![];
+            for (party, output) in parties.iter_mut().zip(&mut outputs) {
+                if output.is_some() {
+                    continue;
+                }
+                let incoming: Vec<_> = messages
+                    .iter()
+                    .filter(|msg| msg.is_for(party.index))
+                    .cloned()
+                    .collect();
+                match party.proceed(&incoming) {
+                    Ok(Step::Messages(msgs)) => next_messages.extend(msgs),
+                    Ok(Step::Finished(result)) => *output = Some(Ok(result)),
+                    Err(err) => *output = Some(Err(err)),
+                }
+            }
+            messages = next_messages;
+        }
+        (parties, outputs.into_iter().flatten().collect())
+    }
+
+    /// Checks that honest parties succeeded with consistent outputs, and the joint secret key is
+    /// a sum of secrets of qualified dealers
+    fn check_outputs<E: Curve>(
+        parties: &[Dkg<E, Sha256>],
+        outputs: &Outputs<E>,
+        honest: &[u16],
+        qualified: &[u16],
+    ) {
+        let expected_public_key: Point<E> = qualified
+            .iter()
+            .map(|&i| &parties[usize::from(i) - 1].vss.commitments[0])
+            .sum();
+        let first = outputs[usize::from(honest[0]) - 1].as_ref().unwrap();
+        for &i in honest {
+            let output = outputs[usize::from(i) - 1].as_ref().unwrap();
+            assert_eq!(output.index, i);
+            assert_eq!(output.qualified, qualified);
+            assert_eq!(output.public_key(), &expected_public_key);
+            assert_eq!(output.vss.commitments, first.vss.commitments);
+            assert!(output
+                .vss
+                .validate_share(&output.secret_share, output.index)
+                .is_ok());
+        }
+    }
+
+    test_for_all_curves!(feldman_dkg);
+    fn feldman_dkg<E: Curve>() {
+        let (parties, outputs) = simulate::<E>(2, 5, CommitmentMode::Feldman, |_| ());
+        check_outputs(&parties, &outputs, &[1, 2, 3, 4, 5], &[1, 2, 3, 4, 5]);
+    }
+
+    test_for_all_curves!(pedersen_dkg);
+    fn pedersen_dkg<E: Curve>() {
+        let (parties, outputs) = simulate::<E>(2, 5, CommitmentMode::Pedersen, |_| ());
+        check_outputs(&parties, &outputs, &[1, 2, 3, 4, 5], &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn complaints_are_resolved() {
+        for &mode in &[CommitmentMode::Feldman, CommitmentMode::Pedersen] {
+            // Party 1 sends an invalid share to party 2, but reveals the correct one when party 2
+            // complains. Party 3 sends invalid shares to parties 4 and 5 and refuses to answer
+            // complaints.
+            let (parties, outputs) = simulate::<Secp256k1>(2, 5, mode, |messages| {
+                for msg in messages.iter_mut() {
+                    match (&mut msg.body, msg.sender, msg.receiver) {
+                        (MsgBody::Share(share), 1, Some(2))
+                        | (MsgBody::Share(share), 3, Some(4..=5)) => share.share = Scalar::random(),
+                        (MsgBody::Answers(answers), 3, None) => answers.clear(),
+                        _ => (),
+                    }
+                }
+            });
+            check_outputs(&parties, &outputs, &[1, 2, 4, 5], &[1, 2, 4, 5]);
+        }
+    }
+
+    #[test]
+    fn dealer_with_too_many_complaints_is_disqualified() {
+        // Party 1 deals invalid shares to three parties, that's more than threshold
+        let (parties, outputs) = simulate::<Secp256k1>(2, 5, CommitmentMode::Feldman, |messages| {
+            for msg in messages.iter_mut() {
+                if let (MsgBody::Share(share), 1, Some(3..=5)) =
+                    (&mut msg.body, msg.sender, msg.receiver)
+                {
+                    share.nonce_share = Scalar::random()
+                }
+            }
+        });
+        check_outputs(&parties, &outputs, &[2, 3, 4, 5], &[2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn inconsistent_opening_is_reconstructed() {
+        // Party 1 opens commitments to a different polynomial after qualification, party 2 opens
+        // nothing. Their secrets are reconstructed from shares, so they still contribute to the
+        // joint secret key.
+        let (parties, outputs) =
+            simulate::<Secp256k1>(2, 5, CommitmentMode::Pedersen, |messages| {
+                messages.retain(|msg| !matches!((&msg.body, msg.sender), (MsgBody::Opening(_), 2)));
+                for msg in messages.iter_mut() {
+                    if let (MsgBody::Opening(vss), 1) = (&mut msg.body, msg.sender) {
+                        *vss = VerifiableSS::share(2, 5, &Scalar::random()).0
+                    }
+                }
+            });
+        check_outputs(&parties, &outputs, &[3, 4, 5], &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn invalid_proof_shares_are_ignored() {
+        let (parties, outputs) = simulate::<Secp256k1>(2, 5, CommitmentMode::Feldman, |messages| {
+            for msg in messages.iter_mut() {
+                if let (MsgBody::ProofShare(z), 1..=2) = (&mut msg.body, msg.sender) {
+                    *z = Scalar::random()
+                }
+            }
+        });
+        check_outputs(&parties, &outputs, &[3, 4, 5], &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        for &(t, n, i) in &[(3, 3, 1), (1, 3, 0), (1, 3, 4)] {
+            assert_eq!(
+                Dkg::<Secp256k1, Sha256>::new(t, n, i, CommitmentMode::Feldman).err(),
+                Some(DkgError::InvalidParameters)
+            );
+        }
+    }
+}
@@ -254,11 +254,17 @@ impl<E: Curve, H: Digest + Clone> VerifiableSS<E, H> {
     }
 
     pub fn get_point_commitment(&self, index: u16) -> Point<E> {
+        Self::point_commitment(&self.commitments, index)
+    }
+
+    /// Computes `Σ C_k index^k` for commitments `C_k` to polynomial coefficients
+    pub(crate) fn point_commitment(commitments: &[Point<E>], index: u16) -> Point<E> {
         let index_fe = Scalar::from(index);
-        let mut comm_iterator = self.commitments.iter().rev();
-        let head = comm_iterator.next().unwrap();
-        let tail = comm_iterator;
-        tail.fold(head.clone(), |acc, x| x + acc * &index_fe)
+        let powers: Vec<Scalar<E>> =
+            std::iter::successors(Some(Scalar::from(1)), |p| Some(p * &index_fe))
+                .take(commitments.len())
+                .collect();
+        Point::multiscalar_mul(&powers, commitments)
     }
 
     //compute \lambda_{index,S}, a lagrangian coefficient that change the (t,n) scheme to (|S|,|S|)
@@ -0,0 +1,375 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Hierarchical threshold secret sharing
+//!
+//! Based on Tamir Tassa. 2007. Hierarchical Threshold Secret Sharing. Journal of Cryptology 20,
+//! 237–264.
+//!
+//! Parties are split into levels `0..=m`, level `0` being the most privileged one. Access
+//! structure is given by increasing thresholds `0 < k_0 < k_1 < ... < k_m`: a set of parties is
+//! authorized iff, for every level `l`, it contains at least `k_l` parties of levels `0..=l`. For
+//! instance, thresholds `[2, 3]` mean "at least 2 executives (level 0), and 3 parties in total".
+//!
+//! Dealer samples polynomial `f` of degree `k_m - 1` with `f(0) = secret`. Party with index `i` at
+//! level `l` receives the derivative `f^{(k_{l-1})}(i)` (where `k_{-1} = 0`), so parties of lower
+//! levels alone don't have enough information to reconstruct the secret. Reconstruction is
+//! Birkhoff interpolation, i.e. solving a linear system for coefficients of `f`.
+//!
+//! Feldman-style commitments `a_j G` to coefficients of `f` allow every party to validate its
+//! share. Party indexes must be assigned monotonically (levels of parties `1, 2, ..., n` are
+//! non-decreasing), which guarantees that Birkhoff interpolation is well-posed for every
+//! authorized set with overwhelming probability.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::hierarchical::HierarchicalVSS;
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! // Parties 1-3 are executives, parties 4-5 are ops. Reconstruction requires
+//! // 2 executives and 1 more party.
+//! let secret = Scalar::<Secp256k1>::random();
+//! let (vss, shares) =
+//!     HierarchicalVSS::<_, Sha256>::share(&[2, 3], &[0, 0, 0, 1, 1], &secret).unwrap();
+//! assert!(vss.validate_share(&shares[3], 4).is_ok());
+//!
+//! assert!(vss.is_authorized(&[1, 2, 4]));
+//! assert!(!vss.is_authorized(&[1, 4, 5]));
+//! let reconstructed = vss.reconstruct(&[1, 2, 4], &[
+//!     shares[0].clone(),
+//!     shares[1].clone(),
+//!     shares[3].clone(),
+//! ]);
+//! assert_eq!(reconstructed, Ok(secret));
+//! ```
+
+use std::iter;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Point, Scalar};
+use crate::ErrorSS::{self, VerifyShareError};
+
+/// Parameters of hierarchical sharing: cumulative thresholds of levels, and level of every party
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct HierarchicalSecretSharing {
+    /// Strictly increasing thresholds `k_0 < k_1 < ... < k_m`, `k_0 > 0`
+    pub thresholds: Vec<u16>,
+    /// `levels[i-1]` is the level of party `i`, levels must be non-decreasing
+    pub levels: Vec<u16>,
+}
+
+/// Hierarchical VSS: commitments to coefficients of the secret polynomial
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct HierarchicalVSS<E: Curve, H: Digest + Clone> {
+    pub parameters: HierarchicalSecretSharing,
+    pub commitments: Vec<Point<E>>,
+    pub proof: DLogProof<E, H>,
+}
+
+impl HierarchicalSecretSharing {
+    /// Degree of the secret polynomial `k_m - 1`
+    pub fn degree(&self) -> u16 {
+        self.thresholds.last().map_or(0, |k_m| k_m - 1)
+    }
+
+    /// Order of derivative received by party `index` (`1..=n`)
+    ///
+    /// ## Panics
+    /// Panics if index is out of range
+    pub fn derivative_order(&self, index: u16) -> u16 {
+        match self.levels[usize::from(index) - 1] {
+            0 => 0,
+            level => self.thresholds[usize::from(level) - 1],
+        }
+    }
+
+    /// Checks whether given set of distinct parties satisfies the access structure
+    pub fn is_authorized(&self, parties: &[u16]) -> bool {
+        let n = self.levels.len();
+        let valid = parties
+            .iter()
+            .enumerate()
+            .all(|(k, &i)| i >= 1 && usize::from(i) <= n && !parties[..k].contains(&i));
+        valid
+            && (0..).zip(&self.thresholds).all(|(l, &k_l)| {
+                let count = parties
+                    .iter()
+                    .filter(|&&i| self.levels[usize::from(i) - 1] <= l)
+                    .count();
+                count >= usize::from(k_l)
+            })
+    }
+
+    fn validate(&self) -> Result<(), HierarchicalSSError> {
+        let thresholds_increase = self.thresholds.windows(2).all(|w| w[0] < w[1]);
+        let levels_increase = self.levels.windows(2).all(|w| w[0] <= w[1]);
+        let levels_in_range = self
+            .levels
+            .iter()
+            .all(|&l| usize::from(l) < self.thresholds.len());
+        let valid = !self.thresholds.is_empty()
+            && self.thresholds[0] > 0
+            && thresholds_increase
+            && levels_increase
+            && levels_in_range
+            && self.levels.len() <= usize::from(u16::MAX)
+            && self.is_authorized(&(1..=self.levels.len() as u16).collect::<Vec<_>>());
+        if valid {
+            Ok(())
+        } else {
+            Err(HierarchicalSSError::InvalidParameters)
+        }
+    }
+}
+
+impl<E: Curve, H: Digest + Clone> HierarchicalVSS<E, H> {
+    /// Shares a secret among parties at given levels
+    ///
+    /// Returns share of party `i` at index `i-1`. Returns error if parameters are inconsistent or
+    /// the set of all parties isn't authorized.
+    pub fn share(
+        thresholds: &[u16],
+        levels: &[u16],
+        secret: &Scalar<E>,
+    ) -> Result<(Self, Vec<Scalar<E>>), HierarchicalSSError> {
+        let parameters = HierarchicalSecretSharing {
+            thresholds: thresholds.to_vec(),
+            levels: levels.to_vec(),
+        };
+        parameters.validate()?;
+
+        let polynomial = Polynomial::<E>::sample_exact_with_fixed_const_term(
+            parameters.degree(),
+            secret.clone(),
+        );
+        let coefficients = polynomial.coefficients();
+        let shares = (1..=levels.len() as u16)
+            .map(|i| {
+                birkhoff_row(i, parameters.derivative_order(i), coefficients.len())
+                    .iter()
+                    .zip(coefficients)
+                    .map(|(r_j, a_j)| r_j * a_j)
+                    .sum()
+            })
+            .collect();
+
+        let g = Point::<E>::generator();
+        let commitments = coefficients.iter().map(|a_j| g * a_j).collect();
+        let vss = HierarchicalVSS {
+            parameters,
+            commitments,
+            proof: DLogProof::prove(secret),
+        };
+        Ok((vss, shares))
+    }
+
+    /// Checks whether given set of distinct parties satisfies the access structure
+    pub fn is_authorized(&self, parties: &[u16]) -> bool {
+        self.parameters.is_authorized(parties)
+    }
+
+    /// Validates share of party `index` against commitments
+    pub fn validate_share(&self, share: &Scalar<E>, index: u16) -> Result<(), ErrorSS> {
+        if self.commitments.first() != Some(&self.proof.pk)
+            || DLogProof::verify(&self.proof).is_err()
+            || index == 0
+            || usize::from(index) > self.parameters.levels.len()
+        {
+            return Err(VerifyShareError);
+        }
+        let row = birkhoff_row::<E>(
+            index,
+            self.parameters.derivative_order(index),
+            self.commitments.len(),
+        );
+        if Point::generator() * share == Point::multiscalar_mul(&row, &self.commitments) {
+            Ok(())
+        } else {
+            Err(VerifyShareError)
+        }
+    }
+
+    /// Reconstructs the secret from shares of the given parties
+    ///
+    /// Returns error if the parties don't satisfy the access structure, or Birkhoff interpolation
+    /// problem for the given set of parties doesn't have a unique solution.
+    pub fn reconstruct(
+        &self,
+        parties: &[u16],
+        shares: &[Scalar<E>],
+    ) -> Result<Scalar<E>, HierarchicalSSError> {
+        assert_eq!(parties.len(), shares.len());
+        if !self.is_authorized(parties) {
+            return Err(HierarchicalSSError::Unauthorized);
+        }
+        let columns = usize::from(self.parameters.degree()) + 1;
+        let rows = parties
+            .iter()
+            .zip(shares)
+            .map(|(&i, share)| {
+                let mut row = birkhoff_row(i, self.parameters.derivative_order(i), columns);
+                row.push(share.clone());
+                row
+            })
+            .collect();
+        let coefficients = solve(rows, columns).ok_or(HierarchicalSSError::SingularSystem)?;
+        Ok(coefficients[0].clone())
+    }
+}
+
+/// Coefficients `r_j` such that `f^{(k)}(x) = Σ r_j a_j` for polynomial `f(X) = Σ a_j X^j`,
+/// i.e. `r_j = j! / (j-k)! x^{j-k}` for `j ≥ k` and `0` otherwise
+fn birkhoff_row<E: Curve>(x: u16, k: u16, len: usize) -> Vec<Scalar<E>> {
+    let k = usize::from(k);
+    let x = Scalar::<E>::from(x);
+    let powers = iter::successors(Some(Scalar::<E>::from(1)), |p| Some(p * &x));
+    iter::repeat_with(Scalar::zero)
+        .take(k)
+        .chain((k..len).zip(powers).map(|(j, x_pow)| {
+            let falling_factorial: Scalar<E> =
+                (j - k + 1..=j).map(|m| Scalar::from(m as u64)).product();
+            falling_factorial * x_pow
+        }))
+        .take(len)
+        .collect()
+}
+
+/// Solves linear system given by augmented matrix `rows` with `columns` unknowns by Gaussian
+/// elimination, returns `None` if the solution isn't unique
+fn solve<E: Curve>(mut rows: Vec<Vec<Scalar<E>>>, columns: usize) -> Option<Vec<Scalar<E>>> {
+    for column in 0..columns {
+        let pivot = (column..rows.len()).find(|&r| !rows[r][column].is_zero())?;
+        rows.swap(column, pivot);
+        let inv = rows[column][column].invert()?;
+        let pivot_row: Vec<Scalar<E>> = rows[column].iter().map(|v| v * &inv).collect();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r == column || row[column].is_zero() {
+                continue;
+            }
+            let factor = row[column].clone();
+            for (v, p) in row.iter_mut().zip(&pivot_row) {
+                *v = &*v - p * &factor;
+            }
+        }
+        rows[column] = pivot_row;
+    }
+    Some(
+        rows.into_iter()
+            .take(columns)
+            .map(|row| row[columns].clone())
+            .collect(),
+    )
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum HierarchicalSSError {
+    #[error("thresholds must be positive and strictly increasing, levels must be non-decreasing and in range, all parties together must be authorized")]
+    InvalidParameters,
+    #[error("parties don't satisfy the access structure")]
+    Unauthorized,
+    #[error("birkhoff interpolation problem doesn't have a unique solution")]
+    SingularSystem,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves_and_hashes;
+
+    test_for_all_curves_and_hashes!(test_hierarchical_sharing);
+
+    fn test_hierarchical_sharing<E: Curve, H: Digest + Clone>() {
+        let secret = Scalar::random();
+        let levels = [0, 0, 1, 1, 1, 2, 2];
+        let (vss, shares) = HierarchicalVSS::<E, H>::share(&[1, 3, 4], &levels, &secret).unwrap();
+
+        for (i, share) in (1..).zip(&shares) {
+            assert!(vss.validate_share(share, i).is_ok());
+        }
+        assert!(vss.validate_share(&shares[0], 2).is_err());
+
+        for parties in [
+            &[1, 3, 4, 6][..],
+            &[2, 3, 4, 5],
+            &[1, 2, 3, 7],
+            &[7, 5, 4, 1, 3],
+        ] {
+            let party_shares: Vec<_> = parties
+                .iter()
+                .map(|&i| shares[usize::from(i) - 1].clone())
+                .collect();
+            assert_eq!(vss.reconstruct(parties, &party_shares), Ok(secret.clone()));
+        }
+    }
+
+    #[test]
+    fn access_structure() {
+        let parameters = HierarchicalSecretSharing {
+            thresholds: vec![2, 3],
+            levels: vec![0, 0, 0, 1, 1],
+        };
+        assert_eq!(parameters.degree(), 2);
+        assert_eq!(parameters.derivative_order(1), 0);
+        assert_eq!(parameters.derivative_order(5), 2);
+
+        assert!(parameters.is_authorized(&[1, 2, 3]));
+        assert!(parameters.is_authorized(&[1, 3, 5]));
+        assert!(!parameters.is_authorized(&[1, 4, 5]));
+        assert!(!parameters.is_authorized(&[1, 2]));
+        assert!(!parameters.is_authorized(&[1, 2, 2]));
+        assert!(!parameters.is_authorized(&[1, 2, 6]));
+    }
+
+    #[test]
+    fn derivatives_are_shared() {
+        use crate::elliptic::curves::Secp256k1;
+
+        let coefficients: Vec<Scalar<Secp256k1>> = (1..=4).map(Scalar::from).collect();
+        // f(X) = 1 + 2X + 3X^2 + 4X^3, f''(X) = 6 + 24X, f''(2) = 54
+        let row = birkhoff_row::<Secp256k1>(2, 2, 4);
+        let value: Scalar<_> = row.iter().zip(&coefficients).map(|(r, a)| r * a).sum();
+        assert_eq!(value, Scalar::from(54));
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        use crate::elliptic::curves::Secp256k1;
+        use sha2::Sha256;
+
+        let secret = Scalar::<Secp256k1>::random();
+        for (thresholds, levels) in [
+            (&[2, 2][..], &[0, 0, 1][..]),
+            (&[0, 2], &[0, 0, 1]),
+            (&[2, 3], &[0, 1, 0]),
+            (&[2, 3], &[0, 0, 2]),
+            (&[2, 4], &[0, 0, 1]),
+            (&[], &[]),
+        ] {
+            assert_eq!(
+                HierarchicalVSS::<_, Sha256>::share(thresholds, levels, &secret).err(),
+                Some(HierarchicalSSError::InvalidParameters)
+            );
+        }
+
+        let (vss, shares) =
+            HierarchicalVSS::<_, Sha256>::share(&[2, 3], &[0, 0, 1, 1], &secret).unwrap();
+        assert_eq!(
+            vss.reconstruct(
+                &[1, 3, 4],
+                &[shares[0].clone(), shares[2].clone(), shares[3].clone()]
+            ),
+            Err(HierarchicalSSError::Unauthorized)
+        );
+    }
+}
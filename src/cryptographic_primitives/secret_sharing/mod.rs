@@ -5,7 +5,17 @@
     License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
 */
 
+pub mod dkg;
 pub mod feldman_vss;
+pub mod hierarchical;
+pub mod packed;
+pub mod pedersen_vss;
 mod polynomial;
+pub mod pvss;
+pub mod reed_solomon;
+pub mod repair;
+pub mod resharing;
+pub mod threshold_elgamal;
+pub mod weighted;
 
 pub use polynomial::{Polynomial, PolynomialDegree};
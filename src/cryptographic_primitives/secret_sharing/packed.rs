@@ -0,0 +1,437 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Packed secret sharing
+//!
+//! Based on Matthew Franklin and Moti Yung. 1992. Communication Complexity of Secure Computation.
+//! In Proceedings of STOC '92, 699–710.
+//!
+//! Shares a vector of `k` secrets with a single polynomial `f` of degree `t + k - 1`: secret `s_i`
+//! is embedded at point `-i` (`f(0) = s_0, f(-1) = s_1, ...`), and party `j` receives `f(j)`. Any
+//! `t` shares reveal nothing about the secrets, and any `t + k` shares determine all of them. So
+//! every party receives a single scalar instead of `k` scalars, at the cost of bigger gap between
+//! privacy and reconstruction thresholds.
+//!
+//! Sharing is linear: adding (or scaling) shares of two packed sharings share-wise yields a packed
+//! sharing of element-wise sums (scaled secrets). Commitments to the resulting sharing are computed
+//! by adding (scaling) [PackedVSS].
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::packed::PackedVSS;
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//!
+//! let secrets: Vec<_> = (0..3).map(|_| Scalar::<Secp256k1>::random()).collect();
+//! let (vss, shares) = PackedVSS::share(1, 5, &secrets).unwrap();
+//! assert!(vss.validate_share(&shares[0], 1).is_ok());
+//!
+//! // t + k = 4 shares are required
+//! let reconstructed = vss.reconstruct(&[1, 2, 4, 5], &[
+//!     shares[0].clone(),
+//!     shares[1].clone(),
+//!     shares[3].clone(),
+//!     shares[4].clone(),
+//! ]);
+//! assert_eq!(reconstructed, Ok(secrets));
+//! ```
+
+use std::convert::TryFrom;
+use std::{fmt, iter, ops};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::secret_sharing::reed_solomon::{self, DecodingError};
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Point, Scalar};
+use crate::ErrorSS::{self, VerifyShareError};
+
+/// Parameters of packed sharing
+///
+/// Parameters are validated by [new](Self::new), both at construction and at deserialization.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedParameters")]
+pub struct PackedSecretSharing {
+    threshold: u16,
+    secrets_count: u16,
+    share_count: u16,
+}
+
+#[derive(Deserialize)]
+struct UncheckedParameters {
+    threshold: u16,
+    secrets_count: u16,
+    share_count: u16,
+}
+
+impl TryFrom<UncheckedParameters> for PackedSecretSharing {
+    type Error = PackedSSError;
+    fn try_from(parameters: UncheckedParameters) -> Result<Self, Self::Error> {
+        Self::new(
+            parameters.threshold,
+            parameters.secrets_count,
+            parameters.share_count,
+        )
+    }
+}
+
+/// Feldman-style commitments `a_j G` to coefficients of the packed polynomial
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PackedVSS<E: Curve> {
+    pub parameters: PackedSecretSharing,
+    pub commitments: Vec<Point<E>>,
+}
+
+/// Packed shares produced by [PackedVSS::share]
+///
+/// Share of party `i` is at index `i-1`.
+#[derive(Clone)]
+pub struct PackedShares<E: Curve> {
+    shares: Vec<Scalar<E>>,
+    polynomial: Polynomial<E>,
+}
+
+impl PackedSecretSharing {
+    /// Constructs parameters of sharing `k` secrets among `n` parties with privacy threshold `t`
+    ///
+    /// Returns error if `k` is zero, or `t + k > n`.
+    pub fn new(t: u16, k: u16, n: u16) -> Result<Self, PackedSSError> {
+        if k == 0 || t.checked_add(k).is_none_or(|t_k| t_k > n) {
+            return Err(PackedSSError::InvalidParameters);
+        }
+        Ok(Self {
+            threshold: t,
+            secrets_count: k,
+            share_count: n,
+        })
+    }
+
+    /// Privacy threshold `t`: any `t` shares reveal nothing about the secrets
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Number of packed secrets `k`
+    pub fn secrets_count(&self) -> u16 {
+        self.secrets_count
+    }
+
+    /// Number of parties `n`
+    pub fn share_count(&self) -> u16 {
+        self.share_count
+    }
+
+    /// Degree of the packed polynomial `t + k - 1`
+    pub fn degree(&self) -> u16 {
+        self.threshold + self.secrets_count - 1
+    }
+
+    /// Number of shares required to reconstruct the secrets `t + k`
+    pub fn reconstruct_limit(&self) -> u16 {
+        self.threshold + self.secrets_count
+    }
+
+    /// Point at which `i`-th secret is embedded
+    pub fn secret_point<E: Curve>(i: u16) -> Scalar<E> {
+        -Scalar::from(i)
+    }
+}
+
+impl<E: Curve> PackedVSS<E> {
+    /// Shares `secrets` among `n` parties with privacy threshold `t`
+    ///
+    /// Returns error if `secrets` is empty, or `t + secrets.len() > n`.
+    pub fn share(
+        t: u16,
+        n: u16,
+        secrets: &[Scalar<E>],
+    ) -> Result<(Self, PackedShares<E>), PackedSSError> {
+        let k = u16::try_from(secrets.len()).map_err(|_| PackedSSError::InvalidParameters)?;
+        let parameters = PackedSecretSharing::new(t, k, n)?;
+
+        // Polynomial passes through secrets at points `0, -1, ..., -(k-1)` and through random
+        // values at points `-k, ..., -(k+t-1)`
+        let xs: Vec<Scalar<E>> = (0..k + t).map(PackedSecretSharing::secret_point).collect();
+        let ys: Vec<Scalar<E>> = secrets
+            .iter()
+            .cloned()
+            .chain(iter::repeat_with(Scalar::random).take(usize::from(t)))
+            .collect();
+        let polynomial = Polynomial::interpolate(&xs, &ys);
+        let shares = polynomial.evaluate_many_bigint(1..=n).collect();
+
+        let g = Point::<E>::generator();
+        let commitments = polynomial.coefficients().iter().map(|a| g * a).collect();
+        Ok((
+            PackedVSS {
+                parameters,
+                commitments,
+            },
+            PackedShares { shares, polynomial },
+        ))
+    }
+
+    /// Validates share of party `index` against commitments
+    pub fn validate_share(&self, share: &Scalar<E>, index: u16) -> Result<(), ErrorSS> {
+        self.validate_share_public(&(Point::generator() * share), index)
+    }
+
+    /// Validates public share `share * G` of party `index` against commitments
+    pub fn validate_share_public(&self, ss_point: &Point<E>, index: u16) -> Result<(), ErrorSS> {
+        if *ss_point == self.get_point_commitment(&Scalar::from(index)) {
+            Ok(())
+        } else {
+            Err(VerifyShareError)
+        }
+    }
+
+    /// Computes `f(x) G` from commitments
+    pub fn get_point_commitment(&self, x: &Scalar<E>) -> Point<E> {
+        let powers: Vec<Scalar<E>> = iter::successors(Some(Scalar::from(1)), |p| Some(p * x))
+            .take(self.commitments.len())
+            .collect();
+        Point::multiscalar_mul(&powers, &self.commitments)
+    }
+
+    /// Commitments `s_i G` to the packed secrets
+    pub fn secrets_commitments(&self) -> Vec<Point<E>> {
+        (0..self.parameters.secrets_count)
+            .map(|i| self.get_point_commitment(&PackedSecretSharing::secret_point(i)))
+            .collect()
+    }
+
+    /// Reconstructs all packed secrets from shares of parties `indices`
+    ///
+    /// Requires at least `t + k` shares, assumes that all of them are valid.
+    pub fn reconstruct(
+        &self,
+        indices: &[u16],
+        shares: &[Scalar<E>],
+    ) -> Result<Vec<Scalar<E>>, PackedSSError> {
+        assert_eq!(indices.len(), shares.len());
+        let required = usize::from(self.parameters.reconstruct_limit());
+        if shares.len() < required {
+            return Err(PackedSSError::NotEnoughShares {
+                required,
+                received: shares.len(),
+            });
+        }
+        let xs: Vec<Scalar<E>> = indices[..required]
+            .iter()
+            .map(|&i| Scalar::from(i))
+            .collect();
+        let secrets = (0..self.parameters.secrets_count)
+            .map(|i| {
+                let x = PackedSecretSharing::secret_point(i);
+                (0..)
+                    .zip(&shares[..required])
+                    .map(|(j, y_j)| Polynomial::lagrange_basis(&x, j, &xs) * y_j)
+                    .sum()
+            })
+            .collect();
+        Ok(secrets)
+    }
+
+    /// Reconstructs all packed secrets, correcting corrupted shares
+    ///
+    /// Returns secrets and indices of corrupted shares. Up to `(shares.len() - t - k) / 2`
+    /// corrupted shares can be corrected, see [reed_solomon].
+    pub fn reconstruct_robust(
+        &self,
+        indices: &[u16],
+        shares: &[Scalar<E>],
+    ) -> Result<(Vec<Scalar<E>>, Vec<u16>), PackedSSError> {
+        assert_eq!(indices.len(), shares.len());
+        let xs: Vec<Scalar<E>> = indices.iter().map(|&i| Scalar::from(i)).collect();
+        let (polynomial, corrupted) = reed_solomon::decode(self.parameters.degree(), &xs, shares)?;
+        let secrets = (0..self.parameters.secrets_count)
+            .map(|i| polynomial.evaluate(&PackedSecretSharing::secret_point(i)))
+            .collect();
+        let corrupted = corrupted.into_iter().map(|i| indices[i]).collect();
+        Ok((secrets, corrupted))
+    }
+}
+
+impl<E: Curve> PackedShares<E> {
+    /// Polynomial that was used to derive shares
+    pub fn polynomial(&self) -> &Polynomial<E> {
+        &self.polynomial
+    }
+}
+
+/// Adds commitments of two packed sharings with the same parameters
+///
+/// Resulting commitments correspond to the sharing of element-wise sums of secrets, where every
+/// party's share is the sum of its shares.
+///
+/// ## Panics
+/// Panics if sharings have different parameters
+impl<E: Curve> ops::Add for &PackedVSS<E> {
+    type Output = PackedVSS<E>;
+    fn add(self, other: Self) -> Self::Output {
+        assert_eq!(
+            self.parameters, other.parameters,
+            "packed sharings have different parameters"
+        );
+        PackedVSS {
+            parameters: self.parameters.clone(),
+            commitments: self
+                .commitments
+                .iter()
+                .zip(&other.commitments)
+                .map(|(a, b)| a + b)
+                .collect(),
+        }
+    }
+}
+
+/// Scales commitments of packed sharing
+///
+/// Resulting commitments correspond to the sharing of scaled secrets, where every party's share is
+/// scaled by the same factor.
+impl<E: Curve> ops::Mul<&Scalar<E>> for &PackedVSS<E> {
+    type Output = PackedVSS<E>;
+    fn mul(self, scalar: &Scalar<E>) -> Self::Output {
+        PackedVSS {
+            parameters: self.parameters.clone(),
+            commitments: self.commitments.iter().map(|c| c * scalar).collect(),
+        }
+    }
+}
+
+impl<E: Curve> fmt::Debug for PackedShares<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(f, "PackedShares{{ ... }}")
+    }
+}
+
+impl<E: Curve> ops::Deref for PackedShares<E> {
+    type Target = [Scalar<E>];
+    fn deref(&self) -> &Self::Target {
+        &self.shares
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PackedSSError {
+    #[error("at least one secret must be shared, and t + k must not exceed n")]
+    InvalidParameters,
+    #[error("expected at least {required} shares, received {received}")]
+    NotEnoughShares { required: usize, received: usize },
+    #[error("robust reconstruction failed: {0}")]
+    Decoding(#[from] DecodingError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic::curves::Secp256k1;
+    use crate::test_for_all_curves;
+
+    test_for_all_curves!(test_packed_sharing);
+
+    fn test_packed_sharing<E: Curve>() {
+        let secrets: Vec<Scalar<E>> = (0..4).map(|_| Scalar::random()).collect();
+        let (vss, shares) = PackedVSS::share(2, 7, &secrets).unwrap();
+        assert_eq!(vss.parameters.degree(), 5);
+        assert_eq!(shares.polynomial().degree(), 5.into());
+
+        for (i, share) in (1..).zip(shares.iter()) {
+            assert!(vss.validate_share(share, i).is_ok());
+        }
+        assert!(vss.validate_share(&shares[0], 2).is_err());
+
+        let g = Point::generator();
+        let expected: Vec<_> = secrets.iter().map(|s| g * s).collect();
+        assert_eq!(vss.secrets_commitments(), expected);
+
+        let indices = [7, 1, 3, 4, 6, 2];
+        let selected: Vec<_> = indices
+            .iter()
+            .map(|&i| shares[usize::from(i) - 1].clone())
+            .collect();
+        assert_eq!(vss.reconstruct(&indices, &selected), Ok(secrets.clone()));
+        assert_eq!(
+            vss.reconstruct(&indices[..5], &selected[..5]),
+            Err(PackedSSError::NotEnoughShares {
+                required: 6,
+                received: 5
+            })
+        );
+    }
+
+    test_for_all_curves!(test_linearity);
+
+    fn test_linearity<E: Curve>() {
+        let secrets1: Vec<Scalar<E>> = (0..3).map(|_| Scalar::random()).collect();
+        let secrets2: Vec<Scalar<E>> = (0..3).map(|_| Scalar::random()).collect();
+        let (vss1, shares1) = PackedVSS::share(1, 5, &secrets1).unwrap();
+        let (vss2, shares2) = PackedVSS::share(1, 5, &secrets2).unwrap();
+
+        let factor = Scalar::random();
+        let vss = &(&vss1 * &factor) + &vss2;
+        let shares: Vec<_> = shares1
+            .iter()
+            .zip(shares2.iter())
+            .map(|(s1, s2)| s1 * &factor + s2)
+            .collect();
+        for (i, share) in (1..).zip(&shares) {
+            assert!(vss.validate_share(share, i).is_ok());
+        }
+
+        let expected: Vec<_> = secrets1
+            .iter()
+            .zip(&secrets2)
+            .map(|(s1, s2)| s1 * &factor + s2)
+            .collect();
+        assert_eq!(vss.reconstruct(&[1, 2, 3, 4], &shares[..4]), Ok(expected));
+    }
+
+    #[test]
+    fn robust_reconstruction() {
+        let secrets: Vec<Scalar<Secp256k1>> = (0..2).map(|_| Scalar::random()).collect();
+        let (vss, shares) = PackedVSS::share(1, 7, &secrets).unwrap();
+        let mut shares = shares.to_vec();
+        shares[5] = Scalar::random();
+        shares[2] = Scalar::random();
+
+        let (reconstructed, corrupted) = vss
+            .reconstruct_robust(&[1, 2, 3, 4, 5, 6, 7], &shares)
+            .unwrap();
+        assert_eq!(reconstructed, secrets);
+        assert_eq!(corrupted, vec![3, 6]);
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        let secrets: Vec<Scalar<Secp256k1>> = (0..3).map(|_| Scalar::random()).collect();
+        assert_eq!(
+            PackedVSS::share(3, 5, &secrets).err(),
+            Some(PackedSSError::InvalidParameters)
+        );
+        assert_eq!(
+            PackedVSS::<Secp256k1>::share(1, 5, &[]).err(),
+            Some(PackedSSError::InvalidParameters)
+        );
+
+        for json in [
+            r#"{"threshold":1,"secrets_count":0,"share_count":5}"#,
+            r#"{"threshold":3,"secrets_count":3,"share_count":5}"#,
+            r#"{"threshold":65535,"secrets_count":1,"share_count":65535}"#,
+        ] {
+            assert!(serde_json::from_str::<PackedSecretSharing>(json).is_err());
+        }
+        let parameters = PackedSecretSharing::new(2, 3, 5).unwrap();
+        let json = serde_json::to_string(&parameters).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PackedSecretSharing>(&json).unwrap(),
+            parameters
+        );
+    }
+}
@@ -0,0 +1,302 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! Pedersen VSS, based on Torben Pryds Pedersen. 1991. Non-Interactive and Information-Theoretic
+//! Secure Verifiable Secret Sharing. In Advances in Cryptology — CRYPTO '91, 129–140
+//!
+//! Unlike [Feldman VSS](super::feldman_vss), dealer commits to coefficients of its secret
+//! polynomial `f(X) = Σ a_k X^k` as `C_k = a_k G + b_k H`, where `b_k` are coefficients of a random
+//! blinding polynomial `g(X)` and `H` is [Point::base_point2]. Commitments are perfectly hiding, so
+//! they reveal nothing about the secret (not even `secret * G`). Every party receives a pair of
+//! shares `(f(i), g(i))`.
+//!
+//! Protocols that eventually need to reveal `secret * G` (e.g. [GJKR DKG](super::dkg)) can
+//! [open](PedersenShares::to_feldman) the commitments by publishing Feldman VSS of the same
+//! polynomial. Parties then check their shares against it via [VerifiableSS::validate_share].
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::pedersen_vss::PedersenVSS;
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! let secret = Scalar::<Secp256k1>::random();
+//! let (vss, shares) = PedersenVSS::share(1, 3, &secret);
+//! for (i, (share, blinding)) in (1..).zip(shares.iter().zip(shares.blinding_shares())) {
+//!     assert!(vss.validate_share(share, blinding, i).is_ok());
+//! }
+//!
+//! let feldman = shares.to_feldman::<Sha256>();
+//! assert!(feldman.validate_share(&shares[0], 1).is_ok());
+//! assert_eq!(vss.reconstruct(&[0, 2], &[shares[0].clone(), shares[2].clone()]), secret);
+//! ```
+
+use std::num::NonZeroU16;
+use std::{fmt, iter, ops};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::{
+    ShamirSecretSharing, VerifiableSS,
+};
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Point, Scalar};
+use crate::ErrorSS::{self, VerifyShareError};
+
+/// Public commitments `C_k = a_k G + b_k H` to the dealer's polynomial
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PedersenVSS<E: Curve> {
+    pub parameters: ShamirSecretSharing,
+    pub commitments: Vec<Point<E>>,
+}
+
+/// Shared secret produced by [PedersenVSS::share]
+///
+/// Party `i` needs to receive both `shares[i-1]` and `blinding_shares()[i-1]`. Dealer keeps
+/// polynomials only to [open](Self::to_feldman) the commitments later, if protocol requires it.
+#[derive(Clone)]
+pub struct PedersenShares<E: Curve> {
+    shares: Vec<Scalar<E>>,
+    blinding_shares: Vec<Scalar<E>>,
+    polynomial: Polynomial<E>,
+    blinding_polynomial: Polynomial<E>,
+}
+
+impl<E: Curve> PedersenVSS<E> {
+    pub fn reconstruct_limit(&self) -> u16 {
+        self.parameters.threshold + 1
+    }
+
+    /// Shares a secret among parties with indexes `1..=n`
+    pub fn share(t: u16, n: u16, secret: &Scalar<E>) -> (Self, PedersenShares<E>) {
+        Self::share_at_indices(t, n, secret, (1..=n).map(|i| NonZeroU16::new(i).unwrap()))
+    }
+
+    /// Shares a secret among parties with user defined indexes
+    ///
+    /// NOTE: The caller should make sure that `t`, `n` and the contents of `indices` can't be
+    /// controlled by a malicious party.
+    pub fn share_at_indices<I>(
+        t: u16,
+        n: u16,
+        secret: &Scalar<E>,
+        indices: I,
+    ) -> (Self, PedersenShares<E>)
+    where
+        I: IntoIterator<Item = NonZeroU16>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        assert!(t < n);
+        let indices: Vec<u16> = indices.into_iter().map(NonZeroU16::get).collect();
+        assert_eq!(usize::from(n), indices.len());
+
+        let polynomial = Polynomial::<E>::sample_exact_with_fixed_const_term(t, secret.clone());
+        let blinding_polynomial = Polynomial::<E>::sample_exact(t);
+        let shares = polynomial
+            .evaluate_many_bigint(indices.iter().copied())
+            .collect();
+        let blinding_shares = blinding_polynomial
+            .evaluate_many_bigint(indices.iter().copied())
+            .collect();
+
+        let g = Point::<E>::generator();
+        let h = Point::<E>::base_point2();
+        let commitments = polynomial
+            .coefficients()
+            .iter()
+            .zip(blinding_polynomial.coefficients())
+            .map(|(a_k, b_k)| g * a_k + h * b_k)
+            .collect();
+
+        (
+            PedersenVSS {
+                parameters: ShamirSecretSharing {
+                    threshold: t,
+                    share_count: n,
+                },
+                commitments,
+            },
+            PedersenShares {
+                shares,
+                blinding_shares,
+                polynomial,
+                blinding_polynomial,
+            },
+        )
+    }
+
+    /// Checks that `share * G + blinding_share * H` matches commitments at point `index`
+    pub fn validate_share(
+        &self,
+        share: &Scalar<E>,
+        blinding_share: &Scalar<E>,
+        index: u16,
+    ) -> Result<(), ErrorSS> {
+        let g = Point::<E>::generator();
+        let h = Point::<E>::base_point2();
+        if g * share + h * blinding_share == self.get_point_commitment(index) {
+            Ok(())
+        } else {
+            Err(VerifyShareError)
+        }
+    }
+
+    /// Computes `Σ C_k index^k`
+    pub fn get_point_commitment(&self, index: u16) -> Point<E> {
+        let index_fe = Scalar::from(index);
+        let powers: Vec<Scalar<E>> =
+            iter::successors(Some(Scalar::from(1)), |p| Some(p * &index_fe))
+                .take(self.commitments.len())
+                .collect();
+        Point::multiscalar_mul(&powers, &self.commitments)
+    }
+
+    /// Reconstructs the secret, follows conventions of [VerifiableSS::reconstruct]: `indices`
+    /// are zero-based, i.e. share `f(i)` has index `i - 1`
+    pub fn reconstruct(&self, indices: &[u16], shares: &[Scalar<E>]) -> Scalar<E> {
+        assert_eq!(shares.len(), indices.len());
+        assert!(shares.len() >= usize::from(self.reconstruct_limit()));
+        let points = indices
+            .iter()
+            .map(|i| Scalar::from(*i + 1))
+            .collect::<Vec<_>>();
+        (0..)
+            .zip(shares)
+            .map(|(j, share)| Polynomial::lagrange_basis(&Scalar::zero(), j, &points) * share)
+            .sum()
+    }
+}
+
+impl<E: Curve> PedersenShares<E> {
+    /// Shares of the blinding polynomial `g(i)`
+    pub fn blinding_shares(&self) -> &[Scalar<E>] {
+        &self.blinding_shares
+    }
+
+    /// Polynomial that was used to derive secret shares
+    pub fn polynomial(&self) -> &Polynomial<E> {
+        &self.polynomial
+    }
+
+    /// Polynomial that was used to derive blinding shares
+    pub fn blinding_polynomial(&self) -> &Polynomial<E> {
+        &self.blinding_polynomial
+    }
+
+    /// Opens commitments by converting them to Feldman VSS of the same secret polynomial
+    ///
+    /// Shares that were valid against Pedersen commitments remain valid against returned
+    /// [VerifiableSS], unless the dealer is cheating. Publishing the result reveals `secret * G`.
+    pub fn to_feldman<H: Digest + Clone>(&self) -> VerifiableSS<E, H> {
+        let g = Point::<E>::generator();
+        let coefficients = self.polynomial.coefficients();
+        VerifiableSS {
+            parameters: ShamirSecretSharing {
+                threshold: (coefficients.len() - 1) as u16,
+                share_count: self.shares.len() as u16,
+            },
+            commitments: coefficients.iter().map(|a_k| g * a_k).collect(),
+            proof: DLogProof::prove(&coefficients[0]),
+        }
+    }
+}
+
+impl<E: Curve> fmt::Debug for PedersenShares<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(f, "PedersenShares{{ ... }}")
+    }
+}
+
+impl<E: Curve> ops::Deref for PedersenShares<E> {
+    type Target = [Scalar<E>];
+    fn deref(&self) -> &Self::Target {
+        &self.shares
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_for_all_curves, test_for_all_curves_and_hashes};
+
+    test_for_all_curves!(test_secret_sharing_3_out_of_5);
+
+    fn test_secret_sharing_3_out_of_5<E: Curve>() {
+        let secret = Scalar::random();
+        let (vss, shares) = PedersenVSS::<E>::share(3, 5, &secret);
+
+        for (i, (share, blinding)) in (1..).zip(shares.iter().zip(shares.blinding_shares())) {
+            assert!(vss.validate_share(share, blinding, i).is_ok());
+        }
+
+        let shares_vec = vec![
+            shares[0].clone(),
+            shares[1].clone(),
+            shares[2].clone(),
+            shares[4].clone(),
+        ];
+        assert_eq!(vss.reconstruct(&[0, 1, 2, 4], &shares_vec), secret);
+    }
+
+    test_for_all_curves!(test_secret_sharing_at_indices);
+
+    fn test_secret_sharing_at_indices<E: Curve>() {
+        let secret = Scalar::random();
+        let parties = [1, 2, 4, 5, 6];
+        let (vss, shares) = PedersenVSS::<E>::share_at_indices(
+            2,
+            5,
+            &secret,
+            parties.iter().map(|&v| NonZeroU16::new(v).unwrap()),
+        );
+
+        for (&i, (share, blinding)) in parties
+            .iter()
+            .zip(shares.iter().zip(shares.blinding_shares()))
+        {
+            assert!(vss.validate_share(share, blinding, i).is_ok());
+        }
+
+        let shares_vec = vec![shares[0].clone(), shares[3].clone(), shares[4].clone()];
+        assert_eq!(vss.reconstruct(&[0, 4, 5], &shares_vec), secret);
+    }
+
+    test_for_all_curves!(test_invalid_shares_are_rejected);
+
+    fn test_invalid_shares_are_rejected<E: Curve>() {
+        let (vss, shares) = PedersenVSS::<E>::share(2, 4, &Scalar::random());
+        let blinding = shares.blinding_shares();
+
+        assert!(vss.validate_share(&shares[0], &blinding[0], 2).is_err());
+        assert!(vss.validate_share(&shares[0], &blinding[1], 1).is_err());
+        assert!(vss
+            .validate_share(&(&shares[0] + Scalar::from(1)), &blinding[0], 1)
+            .is_err());
+    }
+
+    test_for_all_curves_and_hashes!(test_conversion_to_feldman);
+
+    fn test_conversion_to_feldman<E: Curve, H: Digest + Clone>() {
+        let secret = Scalar::random();
+        let (vss, shares) = PedersenVSS::<E>::share(2, 5, &secret);
+        let feldman = shares.to_feldman::<H>();
+
+        assert_eq!(feldman.parameters, vss.parameters);
+        assert_eq!(feldman.commitments[0], Point::generator() * &secret);
+        for (i, share) in (1..).zip(shares.iter()) {
+            assert!(feldman.validate_share(share, i).is_ok());
+        }
+        assert_eq!(
+            feldman.reconstruct(&[1, 2, 3], &shares[1..4]),
+            vss.reconstruct(&[1, 2, 3], &shares[1..4])
+        );
+    }
+}
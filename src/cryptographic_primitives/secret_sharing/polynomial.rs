@@ -330,6 +330,99 @@ impl<E: Curve> Polynomial<E> {
             .expect("elements in xs are not pairwise distinct");
         num * denum
     }
+
+    /// Interpolates polynomial $f$ of degree at most $n-1$ passing through $n$ points $(x_i, y_i)$
+    ///
+    /// ## Panics
+    /// This function will panic if `xs` and `ys` have different lengths, or elements in `xs` are not
+    /// pairwise distinct
+    ///
+    /// ## Example
+    /// ```rust
+    /// use curv::cryptographic_primitives::secret_sharing::Polynomial;
+    /// use curv::elliptic::curves::{Scalar, Secp256k1};
+    ///
+    /// let f = Polynomial::<Secp256k1>::sample_exact(3);
+    /// let xs: Vec<_> = (1..=4).map(Scalar::from).collect();
+    /// let ys: Vec<_> = f.evaluate_many(&xs).collect();
+    ///
+    /// let g = Polynomial::interpolate(&xs, &ys);
+    /// assert_eq!(g.coefficients(), f.coefficients());
+    /// ```
+    pub fn interpolate(xs: &[Scalar<E>], ys: &[Scalar<E>]) -> Self {
+        assert_eq!(xs.len(), ys.len());
+        let mut coefficients = vec![Scalar::<E>::zero(); xs.len()];
+        for (i, y_i) in ys.iter().enumerate() {
+            // Lagrange basis polynomial `Π_{j≠i} (X - x_j) / (x_i - x_j)`
+            let mut basis = vec![Scalar::<E>::from(1)];
+            let mut denominator = Scalar::<E>::from(1);
+            for (j, x_j) in xs.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                let mut product = vec![Scalar::<E>::zero(); basis.len() + 1];
+                for (k, b_k) in basis.iter().enumerate() {
+                    product[k + 1] = &product[k + 1] + b_k;
+                    product[k] = &product[k] - b_k * x_j;
+                }
+                basis = product;
+                denominator = denominator * (&xs[i] - x_j);
+            }
+            let factor = y_i
+                * denominator
+                    .invert()
+                    .expect("elements in xs are not pairwise distinct");
+            for (c_k, b_k) in coefficients.iter_mut().zip(&basis) {
+                *c_k = &*c_k + b_k * &factor;
+            }
+        }
+        Self::from_coefficients(coefficients)
+    }
+
+    /// Divides $f(x)$ by $g(x)$, returns quotient $q(x)$ and remainder $r(x)$ such that
+    /// $f = q g + r$ and $\deg r < \deg g$
+    ///
+    /// ## Panics
+    /// This function will panic if $g(x) = 0$
+    ///
+    /// ## Example
+    /// ```rust
+    /// use curv::cryptographic_primitives::secret_sharing::Polynomial;
+    /// use curv::elliptic::curves::{Scalar, Secp256k1};
+    ///
+    /// let f = Polynomial::<Secp256k1>::sample_exact(5);
+    /// let g = Polynomial::<Secp256k1>::sample_exact(2);
+    /// let (q, r) = f.div_rem(&g);
+    /// assert!(r.degree() < g.degree());
+    ///
+    /// let x = Scalar::random();
+    /// assert_eq!(f.evaluate(&x), q.evaluate(&x) * g.evaluate(&x) + r.evaluate(&x));
+    /// ```
+    pub fn div_rem(&self, g: &Self) -> (Self, Self) {
+        let g_degree = match g.degree() {
+            PolynomialDegree::Finite(d) => usize::from(d),
+            PolynomialDegree::Infinity => panic!("division by zero polynomial"),
+        };
+        let lead_inv = g.coefficients[g_degree]
+            .invert()
+            .expect("leading coefficient is non-zero");
+
+        let mut remainder = self.coefficients.clone();
+        let quotient_len = (remainder.len() + 1).saturating_sub(g_degree + 1);
+        let mut quotient = vec![Scalar::<E>::zero(); quotient_len];
+        for i in (0..quotient_len).rev() {
+            let factor = &remainder[i + g_degree] * &lead_inv;
+            for (r_k, g_k) in remainder[i..=i + g_degree].iter_mut().zip(&g.coefficients) {
+                *r_k = &*r_k - g_k * &factor;
+            }
+            quotient[i] = factor;
+        }
+        remainder.truncate(g_degree);
+        (
+            Self::from_coefficients(quotient),
+            Self::from_coefficients(remainder),
+        )
+    }
 }
 
 /// Multiplies polynomial `f(x)` at scalar `s`, returning resulting polynomial `g(x) = s * f(x)`
@@ -357,6 +450,38 @@ impl<E: Curve> ops::Mul<&Scalar<E>> for &Polynomial<E> {
     }
 }
 
+/// Multiplies two polynomials `f(x)` and `g(x)` returning resulting polynomial `h(x) = f(x) * g(x)`
+///
+/// ## Example
+///
+/// ```rust
+/// # use curv::cryptographic_primitives::secret_sharing::Polynomial;
+/// use curv::elliptic::curves::{Secp256k1, Scalar};
+///
+/// let f = Polynomial::<Secp256k1>::sample_exact(2);
+/// let g = Polynomial::<Secp256k1>::sample_exact(3);
+/// let h = &f * &g;
+///
+/// let x = Scalar::<Secp256k1>::from(10);
+/// assert_eq!(h.evaluate(&x), f.evaluate(&x) * g.evaluate(&x));
+/// ```
+impl<E: Curve> ops::Mul for &Polynomial<E> {
+    type Output = Polynomial<E>;
+    fn mul(self, g: Self) -> Self::Output {
+        if self.coefficients.is_empty() || g.coefficients.is_empty() {
+            return Polynomial::from_coefficients(vec![]);
+        }
+        let mut coefficients =
+            vec![Scalar::zero(); self.coefficients.len() + g.coefficients.len() - 1];
+        for (i, f_i) in self.coefficients.iter().enumerate() {
+            for (j, g_j) in g.coefficients.iter().enumerate() {
+                coefficients[i + j] = &coefficients[i + j] + f_i * g_j;
+            }
+        }
+        Polynomial::from_coefficients(coefficients)
+    }
+}
+
 /// Adds two polynomial `f(x)` and `g(x)` returning resulting polynomial `h(x) = f(x) + g(x)`
 ///
 /// ## Example
@@ -428,3 +553,20 @@ impl<E: Curve> ops::Sub for &Polynomial<E> {
         Polynomial::from_coefficients(overlapped.chain(tail.into_iter()).collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic::curves::Secp256k1;
+
+    #[test]
+    fn interpolated_polynomial_passes_through_points() {
+        let polynomial = Polynomial::<Secp256k1>::sample_exact(3);
+        let xs: Vec<_> = [2u16, 3, 5, 9].iter().map(|&x| Scalar::from(x)).collect();
+        let ys: Vec<_> = polynomial.evaluate_many(&xs).collect();
+        assert_eq!(
+            Polynomial::interpolate(&xs, &ys).coefficients(),
+            polynomial.coefficients()
+        );
+    }
+}
@@ -0,0 +1,355 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Publicly verifiable secret sharing (PVSS)
+//!
+//! SCRAPE-style PVSS as described in "Efficient Publicly Verifiable Secret Sharing Schemes with
+//! Fast or Delayed Recovery" by Cascudo, David, Garms and Konring, where the dealer proves
+//! correctness of the sharing by [LdeiProof]. Unlike [Feldman VSS](super::feldman_vss), shares
+//! are published encrypted, so anyone (not only the recipients) can check that the dealing is
+//! consistent, and there's no need for private channels between the dealer and the recipients.
+//!
+//! Every recipient `i` has a key pair `(y_i, Y_i = y_i G)`:
+//! 1. Dealer samples polynomial `p` of degree `t` with `p(0) = s`, and [publishes](PubliclyVerifiableSS::share)
+//!    encrypted shares `Ŝ_i = p(i) Y_i` along with [LdeiProof] that `Ŝ_i` lie on a polynomial of
+//!    degree `t` (with respect to bases `Y_i`).
+//! 2. Anyone [verifies](PubliclyVerifiableSS::verify) the whole distribution.
+//! 3. Recipient `i` [decrypts](PubliclyVerifiableSS::decrypt_share) its share `S_i = p(i) G =
+//!    y_i^{-1} Ŝ_i` and publishes it along with [ECDDHProof] that `log_G(Y_i) = log_{S_i}(Ŝ_i)`.
+//! 4. Anyone [reconstructs](PubliclyVerifiableSS::reconstruct) the secret `s G` from `t+1` valid
+//!    decrypted shares by interpolation in the exponent.
+//!
+//! Note that the secret is recovered as a point `s G`, not as a scalar.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::pvss::PubliclyVerifiableSS;
+//! use curv::elliptic::curves::{Point, Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! let secret_keys: Vec<_> = (0..4).map(|_| Scalar::<Secp256k1>::random()).collect();
+//! let public_keys: Vec<_> = secret_keys.iter().map(|y| Point::generator() * y).collect();
+//!
+//! let secret = Scalar::random();
+//! let pvss = PubliclyVerifiableSS::<_, Sha256>::share(1, &public_keys, &secret).unwrap();
+//! assert!(pvss.verify().is_ok());
+//!
+//! let shares: Vec<_> = [2u16, 4]
+//!     .iter()
+//!     .map(|&i| pvss.decrypt_share(i, &secret_keys[usize::from(i) - 1]).unwrap())
+//!     .collect();
+//! assert_eq!(pvss.reconstruct(&shares), Ok(Point::generator() * secret));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::proofs::low_degree_exponent_interpolation::{
+    LdeiProof, LdeiStatement, LdeiWitness,
+};
+use crate::cryptographic_primitives::proofs::sigma_ec_ddh::{
+    ECDDHProof, ECDDHStatement, ECDDHWitness,
+};
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::ShamirSecretSharing;
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Point, Scalar};
+
+/// Published PVSS distribution: encrypted shares along with proof of their consistency
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PubliclyVerifiableSS<E: Curve, H: Digest + Clone> {
+    pub parameters: ShamirSecretSharing,
+    /// Public keys of the recipients, `public_keys[i-1]` belongs to recipient `i`
+    pub public_keys: Vec<Point<E>>,
+    /// Encrypted shares `Ŝ_i = p(i) Y_i`
+    pub encrypted_shares: Vec<Point<E>>,
+    pub proof: LdeiProof<E, H>,
+}
+
+/// Share `S_i = p(i) G` decrypted by recipient `index`, along with proof of correct decryption
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DecryptedShare<E: Curve, H: Digest + Clone> {
+    pub index: u16,
+    pub share: Point<E>,
+    pub proof: ECDDHProof<E, H>,
+}
+
+impl<E: Curve, H: Digest + Clone> PubliclyVerifiableSS<E, H> {
+    /// Shares a secret with polynomial of degree `t` among recipients with given public keys
+    ///
+    /// Returns error if any public key is zero, or `t` is not less than number of recipients.
+    pub fn share(t: u16, public_keys: &[Point<E>], secret: &Scalar<E>) -> Result<Self, PvssError> {
+        if public_keys.len() > usize::from(u16::MAX) {
+            return Err(PvssError::InvalidParameters);
+        }
+        let n = public_keys.len() as u16;
+        if t >= n || public_keys.iter().any(Point::is_zero) {
+            return Err(PvssError::InvalidParameters);
+        }
+
+        let witness = LdeiWitness {
+            w: Polynomial::sample_exact_with_fixed_const_term(t, secret.clone()),
+        };
+        let statement = LdeiStatement::new(
+            &witness,
+            (1..=n).map(Scalar::from).collect(),
+            public_keys.to_vec(),
+            t,
+        )
+        .map_err(|_| PvssError::InvalidParameters)?;
+        let proof =
+            LdeiProof::prove(&witness, &statement).map_err(|_| PvssError::InvalidParameters)?;
+
+        Ok(PubliclyVerifiableSS {
+            parameters: ShamirSecretSharing {
+                threshold: t,
+                share_count: n,
+            },
+            public_keys: statement.g,
+            encrypted_shares: statement.x,
+            proof,
+        })
+    }
+
+    pub fn reconstruct_limit(&self) -> u16 {
+        self.parameters.threshold + 1
+    }
+
+    /// Verifies that encrypted shares lie on a polynomial of degree `t`
+    pub fn verify(&self) -> Result<(), PvssError> {
+        let n = usize::from(self.parameters.share_count);
+        if self.parameters.threshold >= self.parameters.share_count
+            || self.public_keys.len() != n
+            || self.encrypted_shares.len() != n
+            || self.public_keys.iter().any(Point::is_zero)
+        {
+            return Err(PvssError::InvalidParameters);
+        }
+        let statement = LdeiStatement {
+            alpha: (1..=self.parameters.share_count)
+                .map(Scalar::from)
+                .collect(),
+            g: self.public_keys.clone(),
+            x: self.encrypted_shares.clone(),
+            d: self.parameters.threshold,
+        };
+        self.proof
+            .verify(&statement)
+            .map_err(|_| PvssError::InvalidProof)
+    }
+
+    /// Recipient `index` (`1..=n`) decrypts its share with its secret key
+    ///
+    /// Returns error if `secret_key` doesn't match recipient's public key.
+    pub fn decrypt_share(
+        &self,
+        index: u16,
+        secret_key: &Scalar<E>,
+    ) -> Result<DecryptedShare<E, H>, PvssError> {
+        if index == 0 || index > self.parameters.share_count {
+            return Err(PvssError::InvalidParameters);
+        }
+        let i = usize::from(index) - 1;
+        let public_key = &self.public_keys[i];
+        if Point::generator() * secret_key != *public_key {
+            return Err(PvssError::InvalidSecretKey);
+        }
+        let secret_key_inv = secret_key.invert().ok_or(PvssError::InvalidSecretKey)?;
+        let encrypted_share = &self.encrypted_shares[i];
+        let share = encrypted_share * secret_key_inv;
+
+        let statement = ECDDHStatement {
+            g1: Point::generator().to_point(),
+            h1: public_key.clone(),
+            g2: share.clone(),
+            h2: encrypted_share.clone(),
+        };
+        let witness = ECDDHWitness {
+            x: secret_key.clone(),
+        };
+        Ok(DecryptedShare {
+            index,
+            share,
+            proof: ECDDHProof::prove(&witness, &statement),
+        })
+    }
+
+    /// Verifies that decrypted share matches the encrypted one
+    pub fn verify_decrypted_share(&self, share: &DecryptedShare<E, H>) -> Result<(), PvssError> {
+        if share.index == 0 || share.index > self.parameters.share_count {
+            return Err(PvssError::InvalidShares(vec![share.index]));
+        }
+        let i = usize::from(share.index) - 1;
+        let statement = ECDDHStatement {
+            g1: Point::generator().to_point(),
+            h1: self.public_keys[i].clone(),
+            g2: share.share.clone(),
+            h2: self.encrypted_shares[i].clone(),
+        };
+        share
+            .proof
+            .verify(&statement)
+            .map_err(|_| PvssError::InvalidShares(vec![share.index]))
+    }
+
+    /// Verifies decrypted shares and reconstructs the secret `s G`
+    ///
+    /// Requires at least `t+1` shares from distinct recipients. Returns
+    /// [PvssError::InvalidShares] listing all recipients whose shares didn't pass verification.
+    pub fn reconstruct(&self, shares: &[DecryptedShare<E, H>]) -> Result<Point<E>, PvssError> {
+        for (k, share) in shares.iter().enumerate() {
+            if shares[..k].iter().any(|s| s.index == share.index) {
+                return Err(PvssError::DuplicateIndex(share.index));
+            }
+        }
+        let required = usize::from(self.reconstruct_limit());
+        if shares.len() < required {
+            return Err(PvssError::NotEnoughShares {
+                required,
+                received: shares.len(),
+            });
+        }
+        let invalid: Vec<u16> = shares
+            .iter()
+            .filter(|share| self.verify_decrypted_share(share).is_err())
+            .map(|share| share.index)
+            .collect();
+        if !invalid.is_empty() {
+            return Err(PvssError::InvalidShares(invalid));
+        }
+
+        let xs: Vec<Scalar<E>> = shares.iter().map(|s| Scalar::from(s.index)).collect();
+        let lambdas: Vec<Scalar<E>> = (0..)
+            .zip(shares)
+            .map(|(j, _)| Polynomial::lagrange_basis(&Scalar::zero(), j, &xs))
+            .collect();
+        Ok(Point::multiscalar_mul(
+            &lambdas,
+            shares.iter().map(|s| &s.share),
+        ))
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PvssError {
+    #[error("threshold must be less than number of recipients, and public keys must be non-zero")]
+    InvalidParameters,
+    #[error("encrypted shares don't lie on a polynomial of degree t")]
+    InvalidProof,
+    #[error("secret key doesn't match recipient's public key")]
+    InvalidSecretKey,
+    #[error("expected at least {required} decrypted shares, received {received}")]
+    NotEnoughShares { required: usize, received: usize },
+    #[error("recipient {0} provided more than one decrypted share")]
+    DuplicateIndex(u16),
+    #[error("decrypted shares of recipients {0:?} are invalid")]
+    InvalidShares(Vec<u16>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves_and_hashes;
+
+    fn keys<E: Curve>(n: usize) -> (Vec<Scalar<E>>, Vec<Point<E>>) {
+        let secret_keys: Vec<Scalar<E>> = (0..n).map(|_| Scalar::random()).collect();
+        let public_keys = secret_keys.iter().map(|y| Point::generator() * y).collect();
+        (secret_keys, public_keys)
+    }
+
+    test_for_all_curves_and_hashes!(test_pvss);
+
+    fn test_pvss<E: Curve, H: Digest + Clone>() {
+        let (secret_keys, public_keys) = keys::<E>(5);
+        let secret = Scalar::random();
+        let pvss = PubliclyVerifiableSS::<E, H>::share(2, &public_keys, &secret).unwrap();
+        assert!(pvss.verify().is_ok());
+
+        let shares: Vec<DecryptedShare<E, H>> = (1..=5)
+            .zip(&secret_keys)
+            .map(|(i, y_i)| pvss.decrypt_share(i, y_i).unwrap())
+            .collect();
+        for share in &shares {
+            assert!(pvss.verify_decrypted_share(share).is_ok());
+        }
+
+        let expected = Point::generator() * &secret;
+        assert_eq!(pvss.reconstruct(&shares), Ok(expected.clone()));
+        assert_eq!(
+            pvss.reconstruct(&[shares[3].clone(), shares[0].clone(), shares[4].clone()]),
+            Ok(expected)
+        );
+        assert_eq!(
+            pvss.reconstruct(&shares[..2]),
+            Err(PvssError::NotEnoughShares {
+                required: 3,
+                received: 2
+            })
+        );
+        assert_eq!(
+            pvss.decrypt_share(1, &secret_keys[1]).err(),
+            Some(PvssError::InvalidSecretKey)
+        );
+    }
+
+    test_for_all_curves_and_hashes!(test_invalid_distribution_is_rejected);
+
+    fn test_invalid_distribution_is_rejected<E: Curve, H: Digest + Clone>() {
+        let (secret_keys, public_keys) = keys::<E>(4);
+        let pvss = PubliclyVerifiableSS::<E, H>::share(1, &public_keys, &Scalar::random()).unwrap();
+
+        let mut tampered = pvss.clone();
+        tampered.encrypted_shares[2] = &tampered.encrypted_shares[2] + &public_keys[2];
+        assert_eq!(tampered.verify(), Err(PvssError::InvalidProof));
+
+        let mut tampered = pvss.clone();
+        tampered.parameters.threshold = 0;
+        assert_eq!(tampered.verify(), Err(PvssError::InvalidProof));
+
+        let mut tampered = pvss.clone();
+        tampered.public_keys.swap(0, 1);
+        assert_eq!(tampered.verify(), Err(PvssError::InvalidProof));
+
+        let mut shares: Vec<DecryptedShare<E, H>> = (1..=4)
+            .zip(&secret_keys)
+            .map(|(i, y_i)| pvss.decrypt_share(i, y_i).unwrap())
+            .collect();
+        shares[1].share = &shares[1].share + Point::generator();
+        shares[3] = DecryptedShare {
+            index: 4,
+            ..shares[2].clone()
+        };
+        assert_eq!(
+            pvss.reconstruct(&shares),
+            Err(PvssError::InvalidShares(vec![2, 4]))
+        );
+        assert!(pvss.reconstruct(&shares[..1]).is_err());
+        assert!(pvss
+            .reconstruct(&[shares[0].clone(), shares[2].clone()])
+            .is_ok());
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        use crate::elliptic::curves::Secp256k1;
+        use sha2::Sha256;
+
+        let (_, mut public_keys) = keys::<Secp256k1>(3);
+        let secret = Scalar::random();
+        assert_eq!(
+            PubliclyVerifiableSS::<_, Sha256>::share(3, &public_keys, &secret).err(),
+            Some(PvssError::InvalidParameters)
+        );
+        public_keys[1] = Point::zero();
+        assert_eq!(
+            PubliclyVerifiableSS::<_, Sha256>::share(1, &public_keys, &secret).err(),
+            Some(PvssError::InvalidParameters)
+        );
+    }
+}
@@ -0,0 +1,215 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! Robust reconstruction of Shamir shares with Reed–Solomon error correction
+//!
+//! [VerifiableSS::reconstruct] and [lagrange_interpolation_at_zero] take exactly as many shares as
+//! needed and return garbage if any of them is wrong. Shares of a polynomial of degree `t` evaluated
+//! at `n` distinct points form a Reed–Solomon codeword, so if more than `t+1` shares are available,
+//! up to `(n - t - 1) / 2` corrupted shares can be detected and corrected without any commitments.
+//!
+//! Decoding is done by Gao's algorithm: "A New Algorithm for Decoding Reed-Solomon Codes" by
+//! Shuhong Gao, 2003.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::reed_solomon::reconstruct_robust;
+//! use curv::cryptographic_primitives::secret_sharing::Polynomial;
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//!
+//! let secret = Scalar::<Secp256k1>::random();
+//! let f = Polynomial::sample_exact_with_fixed_const_term(2, secret.clone());
+//! let indices = [1, 2, 3, 4, 5, 6, 7];
+//! let mut shares: Vec<_> = f.evaluate_many_bigint(indices.iter().copied()).collect();
+//! shares[4] = Scalar::random();
+//!
+//! let (reconstructed, corrupted) = reconstruct_robust(2, &indices, &shares).unwrap();
+//! assert_eq!(reconstructed, secret);
+//! assert_eq!(corrupted, vec![5]);
+//! ```
+//!
+//! [VerifiableSS::reconstruct]: super::feldman_vss::VerifiableSS::reconstruct
+//! [lagrange_interpolation_at_zero]: super::feldman_vss::VerifiableSS::lagrange_interpolation_at_zero
+
+use thiserror::Error;
+
+use crate::cryptographic_primitives::secret_sharing::{Polynomial, PolynomialDegree};
+use crate::elliptic::curves::{Curve, Scalar};
+
+/// Reconstructs a secret shared with polynomial of degree `t`, correcting corrupted shares
+///
+/// `indices` are points at which the polynomial was evaluated, i.e. `shares[i] = f(indices[i])`
+/// (for shares produced by [VerifiableSS::share](super::feldman_vss::VerifiableSS::share), party
+/// `i` has index `i`). Returns the secret `f(0)` and indices of shares that don't match it.
+///
+/// Up to `(shares.len() - t - 1) / 2` corrupted shares can be corrected.
+///
+/// ## Panics
+/// Panics if `indices` and `shares` have different lengths
+pub fn reconstruct_robust<E: Curve>(
+    t: u16,
+    indices: &[u16],
+    shares: &[Scalar<E>],
+) -> Result<(Scalar<E>, Vec<u16>), DecodingError> {
+    let xs: Vec<Scalar<E>> = indices.iter().map(|&i| Scalar::from(i)).collect();
+    let (polynomial, corrupted) = decode(t, &xs, shares)?;
+    let corrupted = corrupted.into_iter().map(|i| indices[i]).collect();
+    Ok((polynomial.evaluate(&Scalar::zero()), corrupted))
+}
+
+/// Recovers polynomial $f$ of degree at most `degree` given its evaluations `ys[i]` at points
+/// `xs[i]`, some of which may be corrupted
+///
+/// Returns the polynomial and positions (in `xs`) of evaluations that don't match it. Up to
+/// `(xs.len() - degree - 1) / 2` corrupted evaluations can be corrected.
+///
+/// ## Panics
+/// Panics if `xs` and `ys` have different lengths
+pub fn decode<E: Curve>(
+    degree: u16,
+    xs: &[Scalar<E>],
+    ys: &[Scalar<E>],
+) -> Result<(Polynomial<E>, Vec<usize>), DecodingError> {
+    assert_eq!(xs.len(), ys.len());
+    let n = xs.len();
+    let k = usize::from(degree) + 1;
+    if n < k {
+        return Err(DecodingError::NotEnoughShares {
+            required: k,
+            received: n,
+        });
+    }
+    for (i, x_i) in xs.iter().enumerate() {
+        if xs[..i].contains(x_i) {
+            return Err(DecodingError::DuplicateIndex);
+        }
+    }
+
+    // g0(X) = Π (X - x_i)
+    let g0 = xs.iter().fold(
+        Polynomial::from_coefficients(vec![Scalar::from(1)]),
+        |acc, x_i| &acc * &Polynomial::from_coefficients(vec![-x_i, Scalar::from(1)]),
+    );
+    let g1 = Polynomial::interpolate(xs, ys);
+
+    // Partial extended Euclidean algorithm: stop as soon as deg r < (n + k) / 2
+    let (mut r_prev, mut r) = (g0, g1);
+    let (mut v_prev, mut v) = (
+        Polynomial::from_coefficients(vec![]),
+        Polynomial::from_coefficients(vec![Scalar::from(1)]),
+    );
+    while let PolynomialDegree::Finite(d) = r.degree() {
+        if 2 * usize::from(d) < n + k {
+            break;
+        }
+        let (q, remainder) = r_prev.div_rem(&r);
+        let v_next = &v_prev - &(&q * &v);
+        r_prev = std::mem::replace(&mut r, remainder);
+        v_prev = std::mem::replace(&mut v, v_next);
+    }
+
+    // r = f v, where v is the error locator polynomial
+    let (f, remainder) = r.div_rem(&v);
+    let too_high_degree = matches!(f.degree(), PolynomialDegree::Finite(d) if d > degree);
+    if remainder.degree() != PolynomialDegree::Infinity || too_high_degree {
+        return Err(DecodingError::TooManyErrors);
+    }
+    let mut coefficients = f.coefficients().to_vec();
+    coefficients.resize(k, Scalar::zero());
+    let f = Polynomial::from_coefficients(coefficients);
+    let corrupted = xs
+        .iter()
+        .zip(ys)
+        .enumerate()
+        .filter(|(_, (x, y))| f.evaluate(x) != **y)
+        .map(|(i, _)| i)
+        .collect();
+    Ok((f, corrupted))
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum DecodingError {
+    #[error("expected at least {required} shares, received {received}")]
+    NotEnoughShares { required: usize, received: usize },
+    #[error("share indices are not pairwise distinct")]
+    DuplicateIndex,
+    #[error("too many corrupted shares")]
+    TooManyErrors,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    test_for_all_curves!(reconstructs_secret_without_errors);
+
+    fn reconstructs_secret_without_errors<E: Curve>() {
+        let secret = Scalar::random();
+        let f = Polynomial::<E>::sample_exact_with_fixed_const_term(3, secret.clone());
+        let indices: Vec<u16> = (1..=4).collect();
+        let shares: Vec<_> = f.evaluate_many_bigint(indices.iter().copied()).collect();
+
+        let (reconstructed, corrupted) = reconstruct_robust(3, &indices, &shares).unwrap();
+        assert_eq!(reconstructed, secret);
+        assert!(corrupted.is_empty());
+    }
+
+    test_for_all_curves!(corrects_maximal_number_of_errors);
+
+    fn corrects_maximal_number_of_errors<E: Curve>() {
+        let f = Polynomial::<E>::sample_exact(2);
+        let indices = [1u16, 3, 4, 6, 7, 9, 10, 12];
+        let mut shares: Vec<_> = f.evaluate_many_bigint(indices.iter().copied()).collect();
+        // n = 8, k = 3: up to 2 errors can be corrected
+        shares[1] = Scalar::random();
+        shares[6] = &shares[6] + Scalar::from(1);
+
+        let (reconstructed, corrupted) = reconstruct_robust(2, &indices, &shares).unwrap();
+        assert_eq!(reconstructed, f.evaluate(&Scalar::zero()));
+        assert_eq!(corrupted, vec![3, 10]);
+
+        let xs: Vec<Scalar<E>> = indices.iter().map(|&i| Scalar::from(i)).collect();
+        let (decoded, corrupted) = decode(2, &xs, &shares).unwrap();
+        assert_eq!(decoded.coefficients(), f.coefficients());
+        assert_eq!(corrupted, vec![1, 6]);
+    }
+
+    test_for_all_curves!(detects_too_many_errors);
+
+    fn detects_too_many_errors<E: Curve>() {
+        let f = Polynomial::<E>::sample_exact(2);
+        let indices: Vec<u16> = (1..=6).collect();
+        let mut shares: Vec<_> = f.evaluate_many_bigint(indices.iter().copied()).collect();
+        // n = 6, k = 3: only one error can be corrected
+        shares[0] = Scalar::random();
+        shares[1] = Scalar::random();
+
+        assert_eq!(
+            reconstruct_robust(2, &indices, &shares).err(),
+            Some(DecodingError::TooManyErrors)
+        );
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        use crate::elliptic::curves::Secp256k1;
+
+        let shares: Vec<Scalar<Secp256k1>> = (0..3).map(|_| Scalar::random()).collect();
+        assert_eq!(
+            reconstruct_robust(3, &[1, 2, 3], &shares).err(),
+            Some(DecodingError::NotEnoughShares {
+                required: 4,
+                received: 3
+            })
+        );
+        assert_eq!(
+            reconstruct_robust(1, &[1, 2, 1], &shares).err(),
+            Some(DecodingError::DuplicateIndex)
+        );
+    }
+}
@@ -0,0 +1,320 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Repair of a lost share without reconstructing the secret
+//!
+//! Lets `t+1` helpers recompute share `x_r = f(r)` of a party that lost it, without learning
+//! anything about `f` or `x_r` themselves. Based on the repairable threshold scheme from
+//! "Efficient Repairable Threshold Schemes" by Laing and Stinson, as used in FROST's repair
+//! extension:
+//! 1. Every helper `i` [computes](generate_deltas) `Δ_i = λ_i(r) x_i`, where `λ_i(r)` is the
+//!    Lagrange coefficient of helper `i` at point `r`, splits `Δ_i` into random additive masks
+//!    `δ_{i,j}` (one per helper) and privately sends `δ_{i,j}` to helper `j`.
+//! 2. Every helper `j` [sums](aggregate_deltas) received masks `σ_j = Σ_i δ_{i,j}` and privately
+//!    sends `σ_j` to the lost party.
+//! 3. The lost party [recovers](recover_share) `x_r = Σ_j σ_j = Σ_i λ_i(r) x_i` and checks it
+//!    against [VerifiableSS::get_point_commitment].
+//!
+//! Protocol requires private channels between all participants, and all of them must agree on the
+//! set of helpers.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+//! use curv::cryptographic_primitives::secret_sharing::repair::*;
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! let (vss, shares) = VerifiableSS::<Secp256k1, Sha256>::share(1, 3, &Scalar::random());
+//! // Party 2 lost its share, parties 1 and 3 help to repair it
+//! let (helpers, lost) = ([1, 3], 2);
+//!
+//! let mut deltas = vec![];
+//! for &i in &helpers {
+//!     let share = &shares[usize::from(i) - 1];
+//!     deltas.extend(generate_deltas(&vss, &helpers, i, share, lost).unwrap());
+//! }
+//! let sigmas: Vec<_> = helpers
+//!     .iter()
+//!     .map(|&j| {
+//!         let received: Vec<_> = deltas.iter().filter(|d| d.receiver == j).cloned().collect();
+//!         aggregate_deltas(&helpers, j, &received).unwrap()
+//!     })
+//!     .collect();
+//!
+//! let repaired = recover_share(&vss, &helpers, lost, &sigmas).unwrap();
+//! assert_eq!(repaired, shares[1]);
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Scalar};
+
+/// Mask `δ_{i,j}` sent privately by helper `helper` to helper `receiver`
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct RepairDelta<E: Curve> {
+    pub helper: u16,
+    pub receiver: u16,
+    pub delta: Scalar<E>,
+}
+
+/// Sum of masks `σ_j` sent privately by helper `helper` to the lost party
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct RepairSigma<E: Curve> {
+    pub helper: u16,
+    pub sigma: Scalar<E>,
+}
+
+/// Helper `index` computes masked contributions to share of lost party `lost_index`
+///
+/// Returns one mask per helper (including the helper itself), `i`-th mask needs to be sent to
+/// `helpers[i]`.
+pub fn generate_deltas<E: Curve, H: Digest + Clone>(
+    vss: &VerifiableSS<E, H>,
+    helpers: &[u16],
+    index: u16,
+    share: &Scalar<E>,
+    lost_index: u16,
+) -> Result<Vec<RepairDelta<E>>, RepairError> {
+    validate_helpers(vss, helpers, lost_index)?;
+    let position = helpers
+        .iter()
+        .position(|&i| i == index)
+        .ok_or(RepairError::InvalidParameters)?;
+    if vss.validate_share(share, index).is_err() {
+        return Err(RepairError::InvalidShare);
+    }
+
+    let xs: Vec<Scalar<E>> = helpers.iter().map(|&i| Scalar::from(i)).collect();
+    let lambda = Polynomial::lagrange_basis(&Scalar::from(lost_index), position as u16, &xs);
+    let delta = lambda * share;
+
+    let mut masks: Vec<Scalar<E>> = (1..helpers.len()).map(|_| Scalar::random()).collect();
+    let masks_sum: Scalar<E> = masks.iter().sum();
+    masks.push(delta - masks_sum);
+    Ok(helpers
+        .iter()
+        .zip(masks)
+        .map(|(&receiver, delta)| RepairDelta {
+            helper: index,
+            receiver,
+            delta,
+        })
+        .collect())
+}
+
+/// Helper `index` sums masks received from all helpers (including its own)
+pub fn aggregate_deltas<E: Curve>(
+    helpers: &[u16],
+    index: u16,
+    deltas: &[RepairDelta<E>],
+) -> Result<RepairSigma<E>, RepairError> {
+    if !helpers.contains(&index) {
+        return Err(RepairError::InvalidParameters);
+    }
+    let sigma = helpers
+        .iter()
+        .map(|&helper| {
+            let mut received = deltas
+                .iter()
+                .filter(|d| d.helper == helper && d.receiver == index);
+            match (received.next(), received.next()) {
+                (Some(d), None) => Ok(&d.delta),
+                _ => Err(RepairError::MissingContribution(helper)),
+            }
+        })
+        .sum::<Result<Scalar<E>, _>>()?;
+    Ok(RepairSigma {
+        helper: index,
+        sigma,
+    })
+}
+
+/// Lost party `lost_index` recovers its share from sums of masks of all helpers
+///
+/// Returns error if recovered share doesn't match commitments, i.e. some helper misbehaved.
+pub fn recover_share<E: Curve, H: Digest + Clone>(
+    vss: &VerifiableSS<E, H>,
+    helpers: &[u16],
+    lost_index: u16,
+    sigmas: &[RepairSigma<E>],
+) -> Result<Scalar<E>, RepairError> {
+    validate_helpers(vss, helpers, lost_index)?;
+    let share = helpers
+        .iter()
+        .map(|&helper| {
+            let mut received = sigmas.iter().filter(|s| s.helper == helper);
+            match (received.next(), received.next()) {
+                (Some(s), None) => Ok(&s.sigma),
+                _ => Err(RepairError::MissingContribution(helper)),
+            }
+        })
+        .sum::<Result<Scalar<E>, _>>()?;
+    vss.validate_share(&share, lost_index)
+        .map_err(|_| RepairError::InvalidRepairedShare)?;
+    Ok(share)
+}
+
+/// Checks that there are at least `t+1` distinct helpers with valid indexes, and lost party isn't
+/// among them
+fn validate_helpers<E: Curve, H: Digest + Clone>(
+    vss: &VerifiableSS<E, H>,
+    helpers: &[u16],
+    lost_index: u16,
+) -> Result<(), RepairError> {
+    let n = vss.parameters.share_count;
+    let is_valid_index = |i: u16| i >= 1 && i <= n;
+    let distinct = helpers
+        .iter()
+        .enumerate()
+        .all(|(k, i)| !helpers[..k].contains(i));
+    if helpers.len() < usize::from(vss.reconstruct_limit())
+        || !distinct
+        || !is_valid_index(lost_index)
+        || helpers
+            .iter()
+            .any(|&i| !is_valid_index(i) || i == lost_index)
+    {
+        return Err(RepairError::InvalidParameters);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RepairError {
+    #[error("expected at least t+1 distinct helpers with indexes in range 1..=n, not including the lost party")]
+    InvalidParameters,
+    #[error("helper's share doesn't match commitments")]
+    InvalidShare,
+    #[error("contribution of helper {0} is missing or duplicated")]
+    MissingContribution(u16),
+    #[error("repaired share doesn't match commitments")]
+    InvalidRepairedShare,
+}
+
+impl<E: Curve> fmt::Debug for RepairDelta<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(
+            f,
+            "RepairDelta {{ helper: {}, receiver: {}, .. }}",
+            self.helper, self.receiver
+        )
+    }
+}
+
+impl<E: Curve> fmt::Debug for RepairSigma<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(f, "RepairSigma {{ helper: {}, .. }}", self.helper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::Sha256;
+
+    use super::*;
+    use crate::elliptic::curves::Secp256k1;
+    use crate::test_for_all_curves;
+
+    /// Runs repair protocol, letting `tamper` modify masks exchanged between helpers
+    fn repair<E: Curve>(
+        vss: &VerifiableSS<E, Sha256>,
+        shares: &[Scalar<E>],
+        helpers: &[u16],
+        lost_index: u16,
+        tamper: impl FnOnce(&mut Vec<RepairDelta<E>>),
+    ) -> Result<Scalar<E>, RepairError> {
+        let mut deltas = vec![];
+        for &i in helpers {
+            let share = &shares[usize::from(i) - 1];
+            deltas.extend(generate_deltas(vss, helpers, i, share, lost_index)?);
+        }
+        tamper(&mut deltas);
+        let sigmas = helpers
+            .iter()
+            .map(|&j| {
+                let received: Vec<_> = deltas.iter().filter(|d| d.receiver == j).cloned().collect();
+                aggregate_deltas(helpers, j, &received)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        recover_share(vss, helpers, lost_index, &sigmas)
+    }
+
+    test_for_all_curves!(repairs_lost_share);
+    fn repairs_lost_share<E: Curve>() {
+        let (vss, shares) = VerifiableSS::<E, Sha256>::share(2, 5, &Scalar::random());
+        for (helpers, lost) in [([1, 2, 5], 4), ([5, 3, 2], 1), ([1, 2, 4], 3)] {
+            let repaired = repair(&vss, &shares, &helpers, lost, |_| ()).unwrap();
+            assert_eq!(repaired, shares[usize::from(lost) - 1]);
+        }
+    }
+
+    #[test]
+    fn repairs_lost_share_with_more_helpers() {
+        let (vss, shares) = VerifiableSS::<Secp256k1, Sha256>::share(1, 5, &Scalar::random());
+        let repaired = repair(&vss, &shares, &[1, 3, 4, 5], 2, |_| ()).unwrap();
+        assert_eq!(repaired, shares[1]);
+    }
+
+    #[test]
+    fn masks_hide_contributions() {
+        let (vss, shares) = VerifiableSS::<Secp256k1, Sha256>::share(1, 3, &Scalar::random());
+        let helpers = [1, 3];
+        let deltas = generate_deltas(&vss, &helpers, 1, &shares[0], 2).unwrap();
+        assert_eq!(deltas.len(), 2);
+        let lambda =
+            Polynomial::lagrange_basis(&Scalar::from(2), 0, &[Scalar::from(1), Scalar::from(3)]);
+        let delta: Scalar<_> = deltas.iter().map(|d| &d.delta).sum();
+        assert_eq!(delta, lambda * &shares[0]);
+        assert!(deltas.iter().all(|d| d.delta != delta));
+    }
+
+    #[test]
+    fn misbehaving_helper_is_detected() {
+        let (vss, shares) = VerifiableSS::<Secp256k1, Sha256>::share(2, 5, &Scalar::random());
+        let result = repair(&vss, &shares, &[1, 2, 5], 4, |deltas| {
+            deltas[0].delta = Scalar::random();
+        });
+        assert_eq!(result, Err(RepairError::InvalidRepairedShare));
+
+        let result = repair(&vss, &shares, &[1, 2, 5], 4, |deltas| {
+            deltas.retain(|d| d.helper != 2 || d.receiver != 5);
+        });
+        assert_eq!(result, Err(RepairError::MissingContribution(2)));
+    }
+
+    #[test]
+    fn invalid_helpers_are_rejected() {
+        let (vss, shares) = VerifiableSS::<Secp256k1, Sha256>::share(2, 5, &Scalar::random());
+        for (helpers, lost) in [
+            (&[1, 2][..], 4),
+            (&[1, 2, 2], 4),
+            (&[1, 2, 4], 4),
+            (&[1, 2, 6], 4),
+            (&[1, 2, 3], 0),
+        ] {
+            assert_eq!(
+                repair(&vss, &shares, helpers, lost, |_| ()),
+                Err(RepairError::InvalidParameters)
+            );
+        }
+        assert_eq!(
+            generate_deltas(&vss, &[1, 2, 3], 1, &shares[1], 4),
+            Err(RepairError::InvalidShare)
+        );
+    }
+}
@@ -0,0 +1,393 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Resharing of a secret to a new committee with new threshold
+//!
+//! Moves a secret shared via [VerifiableSS] among `n` parties with threshold `t` to `n'` parties
+//! with threshold `t'` without ever reconstructing it. The public key `secret * G` is preserved,
+//! while all shares and commitments are fresh. Resharing with the same `(t, n)` among the same
+//! parties is a proactive share refresh: shares leaked before the refresh can't be combined with
+//! shares leaked after it.
+//!
+//! Protocol is described in "Verifiable Secret Redistribution for Archive Systems" by Wong, Wang
+//! and Wing:
+//! 1. Every old holder `i` [deals](deal) its share `x_i` via VSS with new parameters `(t', n')`,
+//!    broadcasts the [SubSharing], and privately sends a [SubShare] to every new holder.
+//! 2. Every new holder [combines](combine) sub-shares. Sub-sharings that don't share the old
+//!    holder's share (i.e. the constant commitment doesn't match `x_i G` derived from old
+//!    commitments) are skipped. Remaining sub-shares are interpolated: `x'_j = Σ λ_i s_{i,j}`,
+//!    where `λ_i` are Lagrange coefficients of the old holders at `0`.
+//!
+//! If a sub-share received by a new holder doesn't match the dealer's sub-sharing, [combine]
+//! returns [ReshareError::InvalidSubShares]. New holders need to broadcast such complaints, and
+//! everyone repeats [combine] without sub-sharings of the accused dealers, so all new holders
+//! agree on the set of dealers. At least `t+1` honest old holders are required.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+//! use curv::cryptographic_primitives::secret_sharing::resharing::{combine, deal};
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! // Move the secret from 2-of-3 to 3-of-5 committee
+//! let secret = Scalar::<Secp256k1>::random();
+//! let (old_vss, old_shares) = VerifiableSS::<_, Sha256>::share(1, 3, &secret);
+//!
+//! let mut sub_sharings = vec![];
+//! let mut sub_shares = vec![];
+//! for i in [1u16, 3] {
+//!     let (sub_sharing, shares) = deal(&old_vss, i, &old_shares[usize::from(i) - 1], 2, 5).unwrap();
+//!     sub_sharings.push(sub_sharing);
+//!     sub_shares.extend(shares);
+//! }
+//!
+//! for j in 1..=5 {
+//!     let received: Vec<_> = sub_shares.iter().filter(|s| s.receiver == j).cloned().collect();
+//!     let output = combine(&old_vss, j, 2, 5, &sub_sharings, &received).unwrap();
+//!     assert_eq!(output.public_key(), &old_vss.commitments[0]);
+//!     assert!(output.vss.validate_share(&output.secret_share, j).is_ok());
+//! }
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::{
+    ShamirSecretSharing, VerifiableSS,
+};
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Point, Scalar};
+
+/// Sub-sharing of an old holder's share, broadcasted to all new holders
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SubSharing<E: Curve, H: Digest + Clone> {
+    /// Index of the old holder
+    pub dealer: u16,
+    /// VSS of the old holder's share with new parameters
+    pub vss: VerifiableSS<E, H>,
+}
+
+/// Sub-share dealt by old holder `dealer` to new holder `receiver`, sent privately
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SubShare<E: Curve> {
+    pub dealer: u16,
+    pub receiver: u16,
+    pub share: Scalar<E>,
+}
+
+/// New holder's share of the reshared secret
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ResharingOutput<E: Curve, H: Digest + Clone> {
+    /// Index of the new holder
+    pub index: u16,
+    /// Share of the secret `x'(index)`
+    pub secret_share: Scalar<E>,
+    /// Commitments to the new polynomial, the constant term commitment is unchanged
+    pub vss: VerifiableSS<E, H>,
+    /// Old holders whose sub-sharings were combined
+    pub dealers: Vec<u16>,
+}
+
+/// Old holder `index` deals its share `share` of `old_vss` to `n_new` new holders with threshold
+/// `t_new`
+///
+/// Returns sub-sharing that needs to be broadcasted, and sub-shares that need to be sent
+/// privately: `i`-th sub-share to new holder `i+1`.
+#[allow(clippy::type_complexity)]
+pub fn deal<E: Curve, H: Digest + Clone>(
+    old_vss: &VerifiableSS<E, H>,
+    index: u16,
+    share: &Scalar<E>,
+    t_new: u16,
+    n_new: u16,
+) -> Result<(SubSharing<E, H>, Vec<SubShare<E>>), ReshareError> {
+    if t_new >= n_new {
+        return Err(ReshareError::InvalidParameters);
+    }
+    if old_vss.validate_share(share, index).is_err() {
+        return Err(ReshareError::InvalidShare);
+    }
+    let (vss, shares) = VerifiableSS::<E, H>::share(t_new, n_new, share);
+    let sub_shares = (1..=n_new)
+        .zip(shares.iter())
+        .map(|(receiver, share)| SubShare {
+            dealer: index,
+            receiver,
+            share: share.clone(),
+        })
+        .collect();
+    Ok((SubSharing { dealer: index, vss }, sub_shares))
+}
+
+/// New holder `index` combines sub-shares it received into its share of the secret
+///
+/// `sub_sharings` must be the same for all new holders. Sub-sharings that don't match `old_vss`
+/// or new parameters are skipped. Returns [ReshareError::InvalidSubShares] if any of the remaining
+/// dealers didn't send a valid sub-share to this holder.
+pub fn combine<E: Curve, H: Digest + Clone>(
+    old_vss: &VerifiableSS<E, H>,
+    index: u16,
+    t_new: u16,
+    n_new: u16,
+    sub_sharings: &[SubSharing<E, H>],
+    sub_shares: &[SubShare<E>],
+) -> Result<ResharingOutput<E, H>, ReshareError> {
+    if t_new >= n_new || index == 0 || index > n_new {
+        return Err(ReshareError::InvalidParameters);
+    }
+    let parameters = ShamirSecretSharing {
+        threshold: t_new,
+        share_count: n_new,
+    };
+
+    let mut dealers: Vec<&SubSharing<E, H>> = vec![];
+    for sub_sharing in sub_sharings {
+        let is_valid = sub_sharing.dealer >= 1
+            && sub_sharing.dealer <= old_vss.parameters.share_count
+            && !dealers.iter().any(|d| d.dealer == sub_sharing.dealer)
+            && sub_sharing.vss.parameters == parameters
+            && sub_sharing.vss.commitments.len() == usize::from(t_new) + 1
+            && sub_sharing.vss.commitments[0] == old_vss.get_point_commitment(sub_sharing.dealer)
+            && sub_sharing.vss.commitments[0] == sub_sharing.vss.proof.pk
+            && DLogProof::verify(&sub_sharing.vss.proof).is_ok();
+        if is_valid {
+            dealers.push(sub_sharing)
+        }
+    }
+    let required = usize::from(old_vss.reconstruct_limit());
+    if dealers.len() < required {
+        return Err(ReshareError::NotEnoughDealers {
+            required,
+            received: dealers.len(),
+        });
+    }
+
+    let mut shares = vec![];
+    let mut complaints = vec![];
+    for sub_sharing in &dealers {
+        let share = sub_shares
+            .iter()
+            .find(|s| s.dealer == sub_sharing.dealer && s.receiver == index)
+            .filter(|s| sub_sharing.vss.validate_share(&s.share, index).is_ok());
+        match share {
+            Some(share) => shares.push(&share.share),
+            None => complaints.push(sub_sharing.dealer),
+        }
+    }
+    if !complaints.is_empty() {
+        return Err(ReshareError::InvalidSubShares(complaints));
+    }
+
+    let xs: Vec<Scalar<E>> = dealers.iter().map(|d| Scalar::from(d.dealer)).collect();
+    let lambdas: Vec<Scalar<E>> = (0..)
+        .zip(&dealers)
+        .map(|(i, _)| Polynomial::lagrange_basis(&Scalar::zero(), i, &xs))
+        .collect();
+
+    let secret_share = lambdas.iter().zip(shares).map(|(l, s)| l * s).sum();
+    let commitments = (0..=usize::from(t_new))
+        .map(|k| {
+            let commitments: Vec<Point<E>> = dealers
+                .iter()
+                .map(|d| d.vss.commitments[k].clone())
+                .collect();
+            Point::multiscalar_mul(&lambdas, &commitments)
+        })
+        .collect();
+
+    Ok(ResharingOutput {
+        index,
+        secret_share,
+        vss: VerifiableSS {
+            parameters,
+            commitments,
+            proof: old_vss.proof.clone(),
+        },
+        dealers: dealers.iter().map(|d| d.dealer).collect(),
+    })
+}
+
+impl<E: Curve, H: Digest + Clone> ResharingOutput<E, H> {
+    /// Public key of the reshared secret
+    pub fn public_key(&self) -> &Point<E> {
+        &self.vss.commitments[0]
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ReshareError {
+    #[error("threshold must be less than number of parties, party index must be in range 1..=n")]
+    InvalidParameters,
+    #[error("dealt share doesn't match old commitments")]
+    InvalidShare,
+    #[error("expected at least {required} valid sub-sharings, received {received}")]
+    NotEnoughDealers { required: usize, received: usize },
+    #[error("sub-shares of dealers {0:?} are missing or invalid")]
+    InvalidSubShares(Vec<u16>),
+}
+
+impl<E: Curve> fmt::Debug for SubShare<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(
+            f,
+            "SubShare {{ dealer: {}, receiver: {}, .. }}",
+            self.dealer, self.receiver
+        )
+    }
+}
+
+impl<E: Curve, H: Digest + Clone> fmt::Debug for ResharingOutput<E, H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        f.debug_struct("ResharingOutput")
+            .field("index", &self.index)
+            .field("public_key", self.public_key())
+            .field("dealers", &self.dealers)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::Sha256;
+
+    use super::*;
+    use crate::elliptic::curves::Secp256k1;
+    use crate::test_for_all_curves;
+
+    type Outputs<E> = Vec<Result<ResharingOutput<E, Sha256>, ReshareError>>;
+
+    /// Reshares `secret` from `(t, n)` to `(t_new, n_new)`, letting `tamper` modify messages of
+    /// old holders
+    fn reshare<E: Curve>(
+        secret: &Scalar<E>,
+        (t, n): (u16, u16),
+        (t_new, n_new): (u16, u16),
+        old_holders: &[u16],
+        tamper: impl FnOnce(&mut Vec<SubSharing<E, Sha256>>, &mut Vec<SubShare<E>>),
+    ) -> (VerifiableSS<E, Sha256>, Outputs<E>) {
+        let (old_vss, old_shares) = VerifiableSS::<E, Sha256>::share(t, n, secret);
+        let mut sub_sharings = vec![];
+        let mut sub_shares = vec![];
+        for &i in old_holders {
+            let (sub_sharing, shares) =
+                deal(&old_vss, i, &old_shares[usize::from(i) - 1], t_new, n_new).unwrap();
+            sub_sharings.push(sub_sharing);
+            sub_shares.extend(shares);
+        }
+        tamper(&mut sub_sharings, &mut sub_shares);
+
+        let outputs = (1..=n_new)
+            .map(|j| {
+                let received: Vec<_> = sub_shares
+                    .iter()
+                    .filter(|s| s.receiver == j)
+                    .cloned()
+                    .collect();
+                combine(&old_vss, j, t_new, n_new, &sub_sharings, &received)
+            })
+            .collect();
+        (old_vss, outputs)
+    }
+
+    fn check_outputs<E: Curve>(
+        secret: &Scalar<E>,
+        old_vss: &VerifiableSS<E, Sha256>,
+        outputs: &[ResharingOutput<E, Sha256>],
+    ) {
+        for output in outputs {
+            assert_eq!(output.public_key(), &old_vss.commitments[0]);
+            assert_eq!(output.vss.commitments, outputs[0].vss.commitments);
+            assert!(output
+                .vss
+                .validate_share(&output.secret_share, output.index)
+                .is_ok());
+        }
+        let t_new = outputs[0].vss.parameters.threshold;
+        let indices: Vec<u16> = outputs.iter().map(|o| o.index - 1).collect();
+        let shares: Vec<_> = outputs.iter().map(|o| o.secret_share.clone()).collect();
+        let limit = usize::from(t_new) + 1;
+        assert_eq!(
+            outputs[0]
+                .vss
+                .reconstruct(&indices[..limit], &shares[..limit]),
+            *secret
+        );
+    }
+
+    test_for_all_curves!(committee_change);
+    fn committee_change<E: Curve>() {
+        let secret = Scalar::random();
+        let (old_vss, outputs) = reshare::<E>(&secret, (1, 3), (2, 5), &[1, 3], |_, _| ());
+        let outputs: Vec<_> = outputs.into_iter().map(Result::unwrap).collect();
+        check_outputs(&secret, &old_vss, &outputs);
+    }
+
+    test_for_all_curves!(proactive_refresh);
+    fn proactive_refresh<E: Curve>() {
+        let secret = Scalar::random();
+        let (old_vss, outputs) = reshare::<E>(&secret, (2, 4), (2, 4), &[1, 2, 3, 4], |_, _| ());
+        let outputs: Vec<_> = outputs.into_iter().map(Result::unwrap).collect();
+        check_outputs(&secret, &old_vss, &outputs);
+        assert_ne!(outputs[0].vss.commitments[1], old_vss.commitments[1]);
+    }
+
+    #[test]
+    fn sub_sharing_of_wrong_share_is_skipped() {
+        let secret = Scalar::<Secp256k1>::random();
+        let (old_vss, outputs) = reshare(&secret, (1, 4), (1, 3), &[1, 2, 3], |sub_sharings, _| {
+            // Old holder 2 shares some other value
+            sub_sharings[1].vss = VerifiableSS::share(1, 3, &Scalar::random()).0;
+        });
+        let outputs: Vec<_> = outputs.into_iter().map(Result::unwrap).collect();
+        for output in &outputs {
+            assert_eq!(output.dealers, vec![1, 3]);
+        }
+        check_outputs(&secret, &old_vss, &outputs);
+    }
+
+    #[test]
+    fn invalid_sub_share_is_reported() {
+        let secret = Scalar::<Secp256k1>::random();
+        let (_, outputs) = reshare(&secret, (1, 3), (1, 3), &[1, 2, 3], |_, sub_shares| {
+            for share in sub_shares.iter_mut() {
+                if share.dealer == 3 && share.receiver == 2 {
+                    share.share = Scalar::random()
+                }
+            }
+        });
+        assert!(outputs[0].is_ok());
+        assert_eq!(
+            outputs[1].as_ref().err(),
+            Some(&ReshareError::InvalidSubShares(vec![3]))
+        );
+    }
+
+    #[test]
+    fn not_enough_dealers() {
+        let secret = Scalar::<Secp256k1>::random();
+        let (_, outputs) = reshare(&secret, (2, 4), (1, 3), &[1, 4], |_, _| ());
+        for output in outputs {
+            assert_eq!(
+                output.err(),
+                Some(ReshareError::NotEnoughDealers {
+                    required: 3,
+                    received: 2
+                })
+            );
+        }
+    }
+}
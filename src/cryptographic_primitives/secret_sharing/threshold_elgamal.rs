@@ -0,0 +1,232 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Threshold decryption of [ElGamal](crate::cryptographic_primitives::encryption::elgamal)
+//! ciphertexts
+//!
+//! Secret key `x` is shared via [VerifiableSS], public key is `Y = commitments[0] = x G`. To
+//! decrypt ciphertext `(c1, c2)`:
+//! 1. Every party `i` [publishes](DecryptionShare::new) decryption share `D_i = x_i c1` along with
+//!    [ECDDHProof] that `log_G(X_i) = log_c1(D_i)`, where `X_i = x_i G` is obtained from
+//!    [VerifiableSS::get_point_commitment].
+//! 2. Anyone [combines](combine) `t+1` valid decryption shares: `M = c2 - Σ λ_i D_i`, where `λ_i`
+//!    are Lagrange coefficients at `0`.
+//!
+//! If any of the shares is invalid, [combine] returns [ThresholdDecryptionError::InvalidShares]
+//! listing the parties who misbehaved, so their shares can be excluded.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::encryption::elgamal::{DiscreteLogTable, PublicKey};
+//! use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+//! use curv::cryptographic_primitives::secret_sharing::threshold_elgamal::{
+//!     combine, DecryptionShare,
+//! };
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! // Share decryption key among 3 parties, any 2 of them can decrypt
+//! let (vss, secret_shares) = VerifiableSS::<Secp256k1, Sha256>::share(1, 3, &Scalar::random());
+//! let public_key = PublicKey::from_point(vss.commitments[0].clone()).unwrap();
+//!
+//! let bid = public_key.encrypt_exponent(&Scalar::from(42));
+//!
+//! let shares: Vec<_> = [1u16, 3]
+//!     .iter()
+//!     .map(|&i| DecryptionShare::<_, Sha256>::new(i, &secret_shares[usize::from(i) - 1], &bid))
+//!     .collect();
+//! let plaintext = combine(&vss, &bid, &shares).unwrap();
+//! assert_eq!(DiscreteLogTable::new(100).solve(&plaintext), Some(42));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::encryption::elgamal::Ciphertext;
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::proofs::sigma_ec_ddh::{
+    ECDDHProof, ECDDHStatement, ECDDHWitness,
+};
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Point, Scalar};
+
+/// Partial decryption `D_i = x_i c1` of party `index`, along with proof of its correctness
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DecryptionShare<E: Curve, H: Digest + Clone> {
+    pub index: u16,
+    pub share: Point<E>,
+    pub proof: ECDDHProof<E, H>,
+}
+
+impl<E: Curve, H: Digest + Clone> DecryptionShare<E, H> {
+    /// Party `index` computes decryption share of the ciphertext using its `secret_share`
+    pub fn new(index: u16, secret_share: &Scalar<E>, ciphertext: &Ciphertext<E>) -> Self {
+        let share = &ciphertext.c1 * secret_share;
+        let statement = ECDDHStatement {
+            g1: Point::generator().to_point(),
+            h1: Point::generator() * secret_share,
+            g2: ciphertext.c1.clone(),
+            h2: share.clone(),
+        };
+        let witness = ECDDHWitness {
+            x: secret_share.clone(),
+        };
+        DecryptionShare {
+            index,
+            share,
+            proof: ECDDHProof::prove(&witness, &statement),
+        }
+    }
+
+    /// Verifies that decryption share was computed using the secret share committed in `vss`
+    pub fn verify(
+        &self,
+        vss: &VerifiableSS<E, H>,
+        ciphertext: &Ciphertext<E>,
+    ) -> Result<(), ThresholdDecryptionError> {
+        if self.index == 0 || self.index > vss.parameters.share_count {
+            return Err(ThresholdDecryptionError::InvalidShares(vec![self.index]));
+        }
+        let statement = ECDDHStatement {
+            g1: Point::generator().to_point(),
+            h1: vss.get_point_commitment(self.index),
+            g2: ciphertext.c1.clone(),
+            h2: self.share.clone(),
+        };
+        self.proof
+            .verify(&statement)
+            .map_err(|_| ThresholdDecryptionError::InvalidShares(vec![self.index]))
+    }
+}
+
+/// Verifies decryption shares and combines them into decrypted point `M = c2 - x c1`
+///
+/// Requires at least `t+1` shares from distinct parties. Returns
+/// [ThresholdDecryptionError::InvalidShares] listing all parties whose shares didn't pass
+/// verification.
+pub fn combine<E: Curve, H: Digest + Clone>(
+    vss: &VerifiableSS<E, H>,
+    ciphertext: &Ciphertext<E>,
+    shares: &[DecryptionShare<E, H>],
+) -> Result<Point<E>, ThresholdDecryptionError> {
+    for (k, share) in shares.iter().enumerate() {
+        if shares[..k].iter().any(|s| s.index == share.index) {
+            return Err(ThresholdDecryptionError::DuplicateIndex(share.index));
+        }
+    }
+    let required = usize::from(vss.reconstruct_limit());
+    if shares.len() < required {
+        return Err(ThresholdDecryptionError::NotEnoughShares {
+            required,
+            received: shares.len(),
+        });
+    }
+    let invalid: Vec<u16> = shares
+        .iter()
+        .filter(|share| share.verify(vss, ciphertext).is_err())
+        .map(|share| share.index)
+        .collect();
+    if !invalid.is_empty() {
+        return Err(ThresholdDecryptionError::InvalidShares(invalid));
+    }
+
+    let xs: Vec<Scalar<E>> = shares.iter().map(|s| Scalar::from(s.index)).collect();
+    let lambdas: Vec<Scalar<E>> = (0..)
+        .zip(shares)
+        .map(|(j, _)| Polynomial::lagrange_basis(&Scalar::zero(), j, &xs))
+        .collect();
+    let decryption_key = Point::multiscalar_mul(&lambdas, shares.iter().map(|s| &s.share));
+    Ok(&ciphertext.c2 - decryption_key)
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ThresholdDecryptionError {
+    #[error("expected at least {required} decryption shares, received {received}")]
+    NotEnoughShares { required: usize, received: usize },
+    #[error("party {0} provided more than one decryption share")]
+    DuplicateIndex(u16),
+    #[error("decryption shares of parties {0:?} are invalid")]
+    InvalidShares(Vec<u16>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::encryption::elgamal::PublicKey;
+    use crate::test_for_all_curves_and_hashes;
+
+    test_for_all_curves_and_hashes!(test_threshold_decryption);
+
+    fn test_threshold_decryption<E: Curve, H: Digest + Clone>() {
+        let (vss, secret_shares) = VerifiableSS::<E, H>::share(2, 5, &Scalar::random());
+        let public_key = PublicKey::from_point(vss.commitments[0].clone()).unwrap();
+        let message = Point::generator() * Scalar::random();
+        let ciphertext = public_key.encrypt(&message);
+
+        let shares: Vec<DecryptionShare<E, H>> = (1..=5)
+            .zip(secret_shares.iter())
+            .map(|(i, x_i)| DecryptionShare::new(i, x_i, &ciphertext))
+            .collect();
+        for share in &shares {
+            assert!(share.verify(&vss, &ciphertext).is_ok());
+        }
+
+        assert_eq!(combine(&vss, &ciphertext, &shares), Ok(message.clone()));
+        assert_eq!(
+            combine(
+                &vss,
+                &ciphertext,
+                &[shares[4].clone(), shares[0].clone(), shares[2].clone()]
+            ),
+            Ok(message)
+        );
+        assert_eq!(
+            combine(&vss, &ciphertext, &shares[..2]),
+            Err(ThresholdDecryptionError::NotEnoughShares {
+                required: 3,
+                received: 2
+            })
+        );
+        assert_eq!(
+            combine(
+                &vss,
+                &ciphertext,
+                &[shares[0].clone(), shares[1].clone(), shares[0].clone()]
+            ),
+            Err(ThresholdDecryptionError::DuplicateIndex(1))
+        );
+    }
+
+    test_for_all_curves_and_hashes!(test_invalid_shares_are_identified);
+
+    fn test_invalid_shares_are_identified<E: Curve, H: Digest + Clone>() {
+        let (vss, secret_shares) = VerifiableSS::<E, H>::share(1, 4, &Scalar::random());
+        let public_key = PublicKey::from_point(vss.commitments[0].clone()).unwrap();
+        let ciphertext = public_key.encrypt_exponent(&Scalar::from(7));
+
+        let mut shares: Vec<DecryptionShare<E, H>> = (1..=4)
+            .zip(secret_shares.iter())
+            .map(|(i, x_i)| DecryptionShare::new(i, x_i, &ciphertext))
+            .collect();
+        // party 2 uses wrong secret share
+        shares[1] = DecryptionShare::new(2, &secret_shares[2], &ciphertext);
+        // party 4 tampers the decryption share
+        shares[3].share = &shares[3].share + Point::generator();
+        // out of range index
+        let mut foreign = shares[0].clone();
+        foreign.index = 5;
+
+        assert_eq!(
+            combine(&vss, &ciphertext, &shares),
+            Err(ThresholdDecryptionError::InvalidShares(vec![2, 4]))
+        );
+        assert!(foreign.verify(&vss, &ciphertext).is_err());
+        assert!(combine(&vss, &ciphertext, &[shares[0].clone(), shares[2].clone()]).is_ok());
+    }
+}
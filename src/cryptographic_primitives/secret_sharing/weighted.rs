@@ -0,0 +1,304 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Weighted threshold secret sharing
+//!
+//! Every party `i` has a weight `w_i` and receives `w_i` evaluations of a secret polynomial of
+//! degree `t`, i.e. it's treated as `w_i` parties of [Feldman VSS](super::feldman_vss). A set of
+//! parties can reconstruct the secret iff their total weight is at least `t+1`. Party `i` gets
+//! evaluations at consecutive points `W_{i-1}+1, ..., W_{i-1}+w_i`, where `W_i = w_1 + ... + w_i`.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::weighted::WeightedVSS;
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! // CEO share counts double, any 3 votes are required
+//! let secret = Scalar::<Secp256k1>::random();
+//! let (vss, shares) = WeightedVSS::<_, Sha256>::share(2, &[2, 1, 1, 1], &secret).unwrap();
+//! assert!(vss.validate_share(&shares[0], 1).is_ok());
+//!
+//! assert!(vss.is_authorized(&[1, 3]));
+//! assert!(!vss.is_authorized(&[2, 3]));
+//! let reconstructed = vss.reconstruct(&[1, 3], &[shares[0].clone(), shares[2].clone()]);
+//! assert_eq!(reconstructed, Ok(secret));
+//! ```
+
+use std::convert::TryFrom;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Scalar};
+use crate::ErrorSS::{self, VerifyShareError};
+
+/// Parameters of weighted sharing: polynomial degree `t`, and weight of every party
+///
+/// Parameters are validated by [new](Self::new), both at construction and at deserialization.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedParameters")]
+pub struct WeightedSecretSharing {
+    threshold: u16,
+    weights: Vec<u16>,
+}
+
+#[derive(Deserialize)]
+struct UncheckedParameters {
+    threshold: u16,
+    weights: Vec<u16>,
+}
+
+impl TryFrom<UncheckedParameters> for WeightedSecretSharing {
+    type Error = WeightedSSError;
+    fn try_from(parameters: UncheckedParameters) -> Result<Self, Self::Error> {
+        Self::new(parameters.threshold, parameters.weights)
+    }
+}
+
+/// Weighted VSS: Feldman VSS with multiple evaluation points per party
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct WeightedVSS<E: Curve, H: Digest + Clone> {
+    pub parameters: WeightedSecretSharing,
+    /// Feldman VSS with `share_count` equal to the total weight
+    pub vss: VerifiableSS<E, H>,
+}
+
+impl WeightedSecretSharing {
+    /// Constructs parameters of sharing with polynomial of degree `t` among parties with given
+    /// weights
+    ///
+    /// Returns error if any weight is zero, total weight doesn't fit into `u16`, or total weight
+    /// doesn't exceed `t`.
+    pub fn new(t: u16, weights: Vec<u16>) -> Result<Self, WeightedSSError> {
+        let total_weight = checked_sum(&weights).ok_or(WeightedSSError::InvalidParameters)?;
+        if weights.contains(&0) || total_weight <= t {
+            return Err(WeightedSSError::InvalidParameters);
+        }
+        Ok(Self {
+            threshold: t,
+            weights,
+        })
+    }
+
+    /// Polynomial degree `t`
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Weight of every party
+    pub fn weights(&self) -> &[u16] {
+        &self.weights
+    }
+
+    /// Total weight of all parties
+    pub fn total_weight(&self) -> u16 {
+        checked_sum(&self.weights).expect("total weight is checked at construction")
+    }
+
+    /// Points at which polynomial is evaluated for party `index` (`1..=n`)
+    ///
+    /// ## Panics
+    /// Panics if index is out of range
+    pub fn evaluation_points(&self, index: u16) -> Range<u16> {
+        let i = usize::from(index) - 1;
+        let start =
+            checked_sum(&self.weights[..i]).expect("total weight is checked at construction");
+        start + 1..start + 1 + self.weights[i]
+    }
+
+    /// Checks whether given set of distinct parties has enough weight to reconstruct the secret
+    pub fn is_authorized(&self, parties: &[u16]) -> bool {
+        let n = self.weights.len();
+        let valid = parties
+            .iter()
+            .enumerate()
+            .all(|(k, &i)| i >= 1 && usize::from(i) <= n && !parties[..k].contains(&i));
+        let weight: u32 = parties
+            .iter()
+            .filter(|_| valid)
+            .map(|&i| u32::from(self.weights[usize::from(i) - 1]))
+            .sum();
+        valid && weight > u32::from(self.threshold)
+    }
+}
+
+impl<E: Curve, H: Digest + Clone> WeightedVSS<E, H> {
+    /// Shares a secret with polynomial of degree `t` among parties with given weights
+    ///
+    /// Returns `i`-th party's shares at index `i-1`. Returns error if parameters are invalid (see
+    /// [WeightedSecretSharing::new]).
+    #[allow(clippy::type_complexity)]
+    pub fn share(
+        t: u16,
+        weights: &[u16],
+        secret: &Scalar<E>,
+    ) -> Result<(Self, Vec<Vec<Scalar<E>>>), WeightedSSError> {
+        let parameters = WeightedSecretSharing::new(t, weights.to_vec())?;
+        let (vss, shares) = VerifiableSS::<E, H>::share(t, parameters.total_weight(), secret);
+        let shares = (1..)
+            .zip(weights)
+            .map(|(i, _)| {
+                parameters
+                    .evaluation_points(i)
+                    .map(|x| shares[usize::from(x) - 1].clone())
+                    .collect()
+            })
+            .collect();
+        Ok((WeightedVSS { parameters, vss }, shares))
+    }
+
+    /// Checks whether given set of distinct parties has enough weight to reconstruct the secret
+    pub fn is_authorized(&self, parties: &[u16]) -> bool {
+        self.parameters.is_authorized(parties)
+    }
+
+    /// Validates all shares of party `index`
+    pub fn validate_share(&self, shares: &[Scalar<E>], index: u16) -> Result<(), ErrorSS> {
+        if index == 0 || usize::from(index) > self.parameters.weights.len() {
+            return Err(VerifyShareError);
+        }
+        let points = self.parameters.evaluation_points(index);
+        if shares.len() != points.len() {
+            return Err(VerifyShareError);
+        }
+        points
+            .zip(shares)
+            .try_for_each(|(x, share)| self.vss.validate_share(share, x))
+    }
+
+    /// Reconstructs the secret from shares of the given parties
+    ///
+    /// `shares[k]` must contain all shares of party `parties[k]`. Returns error if the parties
+    /// don't satisfy the access structure.
+    pub fn reconstruct(
+        &self,
+        parties: &[u16],
+        shares: &[Vec<Scalar<E>>],
+    ) -> Result<Scalar<E>, WeightedSSError> {
+        assert_eq!(parties.len(), shares.len());
+        if !self.is_authorized(parties) {
+            return Err(WeightedSSError::Unauthorized);
+        }
+        let mut xs = vec![];
+        let mut ys = vec![];
+        for (&i, party_shares) in parties.iter().zip(shares) {
+            let points = self.parameters.evaluation_points(i);
+            if party_shares.len() != points.len() {
+                return Err(WeightedSSError::InvalidShares(i));
+            }
+            xs.extend(points.map(Scalar::from));
+            ys.extend(party_shares.iter().cloned());
+        }
+        Ok((0..)
+            .zip(&ys)
+            .map(|(j, y_j)| Polynomial::lagrange_basis(&Scalar::zero(), j, &xs) * y_j)
+            .sum())
+    }
+}
+
+fn checked_sum(weights: &[u16]) -> Option<u16> {
+    weights.iter().try_fold(0u16, |acc, &w| acc.checked_add(w))
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum WeightedSSError {
+    #[error(
+        "weights must be non-zero, and total weight must be greater than threshold and fit into u16"
+    )]
+    InvalidParameters,
+    #[error("parties don't have enough weight to reconstruct the secret")]
+    Unauthorized,
+    #[error("party {0} provided wrong number of shares")]
+    InvalidShares(u16),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves_and_hashes;
+
+    test_for_all_curves_and_hashes!(test_weighted_sharing);
+
+    fn test_weighted_sharing<E: Curve, H: Digest + Clone>() {
+        let secret = Scalar::random();
+        let weights = [3, 1, 2, 1];
+        let (vss, shares) = WeightedVSS::<E, H>::share(3, &weights, &secret).unwrap();
+
+        for (i, (party_shares, &w)) in (1..).zip(shares.iter().zip(&weights)) {
+            assert_eq!(party_shares.len(), usize::from(w));
+            assert!(vss.validate_share(party_shares, i).is_ok());
+        }
+        assert!(vss.validate_share(&shares[0], 2).is_err());
+
+        for parties in [&[1, 2][..], &[1, 3], &[2, 3, 4], &[4, 3, 1]] {
+            let party_shares: Vec<_> = parties
+                .iter()
+                .map(|&i| shares[usize::from(i) - 1].clone())
+                .collect();
+            assert_eq!(vss.reconstruct(parties, &party_shares), Ok(secret.clone()));
+        }
+    }
+
+    #[test]
+    fn access_structure() {
+        let parameters = WeightedSecretSharing::new(3, vec![3, 1, 2, 1]).unwrap();
+        assert_eq!(parameters.total_weight(), 7);
+        assert_eq!(parameters.evaluation_points(1), 1..4);
+        assert_eq!(parameters.evaluation_points(3), 5..7);
+
+        assert!(parameters.is_authorized(&[1, 4]));
+        assert!(parameters.is_authorized(&[2, 3, 4]));
+        assert!(!parameters.is_authorized(&[1]));
+        assert!(!parameters.is_authorized(&[3, 4]));
+        assert!(!parameters.is_authorized(&[1, 1]));
+        assert!(!parameters.is_authorized(&[1, 5]));
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        use crate::elliptic::curves::Secp256k1;
+        use sha2::Sha256;
+
+        let secret = Scalar::<Secp256k1>::random();
+        for (t, weights) in [(2, &[1, 0, 2][..]), (3, &[1, 1, 1]), (1, &[u16::MAX, 1])] {
+            assert_eq!(
+                WeightedVSS::<_, Sha256>::share(t, weights, &secret).err(),
+                Some(WeightedSSError::InvalidParameters)
+            );
+        }
+
+        for json in [
+            r#"{"threshold":1,"weights":[65535,1]}"#,
+            r#"{"threshold":2,"weights":[1,0,2]}"#,
+            r#"{"threshold":3,"weights":[1,1,1]}"#,
+        ] {
+            assert!(serde_json::from_str::<WeightedSecretSharing>(json).is_err());
+        }
+        let parameters = WeightedSecretSharing::new(3, vec![3, 1, 2, 1]).unwrap();
+        let json = serde_json::to_string(&parameters).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WeightedSecretSharing>(&json).unwrap(),
+            parameters
+        );
+
+        let (vss, shares) = WeightedVSS::<_, Sha256>::share(2, &[2, 1, 1], &secret).unwrap();
+        assert_eq!(
+            vss.reconstruct(&[2, 3], &[shares[1].clone(), shares[2].clone()]),
+            Err(WeightedSSError::Unauthorized)
+        );
+        assert_eq!(
+            vss.reconstruct(&[1, 2], &[shares[0][..1].to_vec(), shares[1].clone()]),
+            Err(WeightedSSError::InvalidShares(1))
+        );
+    }
+}
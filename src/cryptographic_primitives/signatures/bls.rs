@@ -0,0 +1,694 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! BLS signatures over BLS12-381 as specified in [draft-irtf-cfrg-bls-signature]
+//!
+//! Both variants of the scheme are supported:
+//! * [MinPk]: public keys are in G1 (48 bytes), signatures are in G2 (96 bytes)
+//! * [MinSig]: public keys are in G2 (96 bytes), signatures are in G1 (48 bytes)
+//!
+//! Signing and verification follow proof-of-possession scheme (ciphersuites
+//! `BLS_SIG_BLS12381G{1,2}_XMD:SHA-256_SSWU_RO_POP_`). Signatures and public keys can be
+//! aggregated. Before aggregating public keys of different parties, make sure that every party
+//! proved possession of its secret key (see [SecretKey::prove_possession]), otherwise
+//! aggregation is vulnerable to rogue-key attacks.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::signatures::bls::{MinPk, PublicKey, SecretKey, Signature};
+//!
+//! let secret_keys: Vec<SecretKey<MinPk>> = (0..3).map(|_| SecretKey::random()).collect();
+//! let public_keys: Vec<_> = secret_keys.iter().map(|sk| sk.public_key()).collect();
+//!
+//! // Every party proves possession of its secret key
+//! for (sk, pk) in secret_keys.iter().zip(&public_keys) {
+//!     assert!(pk.verify_possession(&sk.prove_possession()).is_ok());
+//! }
+//!
+//! // Parties sign the same message, signatures are aggregated into a single one
+//! let signatures: Vec<_> = secret_keys.iter().map(|sk| sk.sign(b"hello")).collect();
+//! let signature = Signature::aggregate(&signatures).unwrap();
+//! assert!(signature.fast_aggregate_verify(&public_keys, b"hello").is_ok());
+//! ```
+//!
+//! [draft-irtf-cfrg-bls-signature]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::elliptic::curves::bls12_381::Pair;
+use crate::elliptic::curves::{Bls12_381_1, Bls12_381_2, Curve, HashToCurve, Point, Scalar};
+
+/// Variant of BLS signature scheme, defines which groups public keys and signatures belong to
+pub trait BlsVariant {
+    /// Group of public keys
+    type PublicKeyGroup: Curve;
+    /// Group of signatures, shares scalar field with [PublicKeyGroup](Self::PublicKeyGroup)
+    type SignatureGroup: HashToCurve + Curve<Scalar = <Self::PublicKeyGroup as Curve>::Scalar>;
+
+    /// Domain separation tag used to hash messages
+    const SIGNATURE_DST: &'static [u8];
+    /// Domain separation tag used to hash public keys in proofs of possession
+    const POP_DST: &'static [u8];
+
+    /// Checks whether `e(pk_1, sig_1) * ... * e(pk_n, sig_n)` is an identity
+    #[allow(clippy::type_complexity)]
+    fn pairing_product_is_one(
+        pairs: &[(&Point<Self::PublicKeyGroup>, &Point<Self::SignatureGroup>)],
+    ) -> bool;
+}
+
+/// Minimal public key size variant: public keys are in G1, signatures are in G2
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinPk {}
+
+/// Minimal signature size variant: public keys are in G2, signatures are in G1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinSig {}
+
+impl BlsVariant for MinPk {
+    type PublicKeyGroup = Bls12_381_1;
+    type SignatureGroup = Bls12_381_2;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+    fn pairing_product_is_one(pairs: &[(&Point<Bls12_381_1>, &Point<Bls12_381_2>)]) -> bool {
+        Pair::compute_pairing_product(pairs.iter().copied()).is_one()
+    }
+}
+
+impl BlsVariant for MinSig {
+    type PublicKeyGroup = Bls12_381_2;
+    type SignatureGroup = Bls12_381_1;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+    fn pairing_product_is_one(pairs: &[(&Point<Bls12_381_2>, &Point<Bls12_381_1>)]) -> bool {
+        Pair::compute_pairing_product(pairs.iter().map(|(pk, sig)| (*sig, *pk))).is_one()
+    }
+}
+
+/// BLS secret key
+pub struct SecretKey<V: BlsVariant> {
+    scalar: Scalar<V::PublicKeyGroup>,
+}
+
+/// BLS public key `pk = sk * G`
+pub struct PublicKey<V: BlsVariant> {
+    point: Point<V::PublicKeyGroup>,
+}
+
+/// BLS signature `sig = sk * H(msg)`
+pub struct Signature<V: BlsVariant> {
+    point: Point<V::SignatureGroup>,
+}
+
+/// Proof that signer knows secret key of its public key
+///
+/// Proof is a signature of the public key under separate domain separation tag.
+pub struct ProofOfPossession<V: BlsVariant> {
+    signature: Signature<V>,
+}
+
+impl<V: BlsVariant> SecretKey<V> {
+    /// Samples a random secret key
+    pub fn random() -> Self {
+        loop {
+            if let Ok(secret_key) = Self::from_scalar(Scalar::random()) {
+                return secret_key;
+            }
+        }
+    }
+
+    /// Constructs a secret key from a scalar, returns error if it's zero
+    pub fn from_scalar(scalar: Scalar<V::PublicKeyGroup>) -> Result<Self, BlsError> {
+        if scalar.is_zero() {
+            return Err(BlsError::ZeroSecretKey);
+        }
+        Ok(Self { scalar })
+    }
+
+    pub fn as_scalar(&self) -> &Scalar<V::PublicKeyGroup> {
+        &self.scalar
+    }
+
+    pub fn public_key(&self) -> PublicKey<V> {
+        PublicKey {
+            point: Point::generator() * &self.scalar,
+        }
+    }
+
+    /// Signs a message
+    pub fn sign(&self, message: &[u8]) -> Signature<V> {
+        self.sign_with_dst(message, V::SIGNATURE_DST)
+    }
+
+    /// Signs a message hashing it to the curve with custom domain separation tag (`CoreSign`
+    /// operation of the draft)
+    ///
+    /// Use it to implement other schemes of the draft (e.g. basic scheme), otherwise prefer
+    /// [sign](Self::sign).
+    pub fn sign_with_dst(&self, message: &[u8], dst: &[u8]) -> Signature<V> {
+        let scalar = Scalar::<V::SignatureGroup>::from_raw(self.scalar.as_raw().clone());
+        Signature {
+            point: Point::hash_to_curve(message, dst) * scalar,
+        }
+    }
+
+    /// Proves possession of the secret key
+    pub fn prove_possession(&self) -> ProofOfPossession<V> {
+        let public_key = self.public_key().to_bytes();
+        ProofOfPossession {
+            signature: self.sign_with_dst(&public_key, V::POP_DST),
+        }
+    }
+}
+
+impl<V: BlsVariant> PublicKey<V> {
+    /// Constructs a public key from a point, returns error if point is zero
+    pub fn from_point(point: Point<V::PublicKeyGroup>) -> Result<Self, BlsError> {
+        if point.is_zero() {
+            return Err(BlsError::InvalidPublicKey);
+        }
+        Ok(Self { point })
+    }
+
+    pub fn as_point(&self) -> &Point<V::PublicKeyGroup> {
+        &self.point
+    }
+
+    /// Parses compressed public key
+    ///
+    /// Returns error if bytes don't encode a non-zero point of prime order subgroup.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        let point = Point::from_bytes(bytes).or(Err(BlsError::InvalidPublicKey))?;
+        Self::from_point(point)
+    }
+
+    /// Serializes public key in compressed form
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.point.to_bytes(true).to_vec()
+    }
+
+    /// Sums up public keys
+    ///
+    /// Aggregated public key can be used to verify aggregated signature of the same message
+    /// (see [Signature::fast_aggregate_verify]). Public keys must be accompanied by verified
+    /// [proofs of possession](Self::verify_possession).
+    ///
+    /// Returns error if list is empty or if aggregated key is zero.
+    pub fn aggregate(public_keys: &[PublicKey<V>]) -> Result<Self, BlsError> {
+        if public_keys.is_empty() {
+            return Err(BlsError::EmptyAggregation);
+        }
+        let sum = public_keys
+            .iter()
+            .fold(Point::zero(), |sum, public_key| sum + &public_key.point);
+        Self::from_point(sum)
+    }
+
+    /// Verifies proof of possession of the secret key
+    pub fn verify_possession(&self, proof: &ProofOfPossession<V>) -> Result<(), BlsError> {
+        proof
+            .signature
+            .verify_with_dst(self, &self.to_bytes(), V::POP_DST)
+            .or(Err(BlsError::InvalidProofOfPossession))
+    }
+}
+
+impl<V: BlsVariant> Signature<V> {
+    pub fn from_point(point: Point<V::SignatureGroup>) -> Self {
+        Self { point }
+    }
+
+    pub fn as_point(&self) -> &Point<V::SignatureGroup> {
+        &self.point
+    }
+
+    /// Verifies the signature
+    pub fn verify(&self, public_key: &PublicKey<V>, message: &[u8]) -> Result<(), BlsError> {
+        self.verify_with_dst(public_key, message, V::SIGNATURE_DST)
+    }
+
+    /// Verifies the signature of a message hashed to the curve with custom domain separation tag
+    /// (`CoreVerify` operation of the draft)
+    pub fn verify_with_dst(
+        &self,
+        public_key: &PublicKey<V>,
+        message: &[u8],
+        dst: &[u8],
+    ) -> Result<(), BlsError> {
+        let hash = Point::hash_to_curve(message, dst);
+        let minus_generator = -Point::<V::PublicKeyGroup>::generator().to_point();
+        // e(pk, H(m)) = e(G, sig)
+        if V::pairing_product_is_one(&[(&public_key.point, &hash), (&minus_generator, &self.point)])
+        {
+            Ok(())
+        } else {
+            Err(BlsError::InvalidSignature)
+        }
+    }
+
+    /// Sums up signatures
+    ///
+    /// Returns error if list is empty.
+    pub fn aggregate(signatures: &[Signature<V>]) -> Result<Self, BlsError> {
+        if signatures.is_empty() {
+            return Err(BlsError::EmptyAggregation);
+        }
+        let point = signatures
+            .iter()
+            .fold(Point::zero(), |sum, signature| sum + &signature.point);
+        Ok(Self { point })
+    }
+
+    /// Verifies aggregated signature of `messages[i]` signed by `public_keys[i]`
+    ///
+    /// Public keys must be accompanied by verified [proofs of possession](PublicKey::verify_possession).
+    ///
+    /// ## Panics
+    /// Panics if `public_keys` and `messages` have different lengths.
+    pub fn aggregate_verify(
+        &self,
+        public_keys: &[PublicKey<V>],
+        messages: &[&[u8]],
+    ) -> Result<(), BlsError> {
+        assert_eq!(
+            public_keys.len(),
+            messages.len(),
+            "public_keys and messages must have the same length"
+        );
+        if public_keys.is_empty() {
+            return Err(BlsError::EmptyAggregation);
+        }
+        let hashes: Vec<Point<V::SignatureGroup>> = messages
+            .iter()
+            .map(|message| Point::hash_to_curve(message, V::SIGNATURE_DST))
+            .collect();
+        let minus_generator = -Point::<V::PublicKeyGroup>::generator().to_point();
+        let pairs: Vec<_> = public_keys
+            .iter()
+            .map(|public_key| &public_key.point)
+            .zip(&hashes)
+            .chain(std::iter::once((&minus_generator, &self.point)))
+            .collect();
+        if V::pairing_product_is_one(&pairs) {
+            Ok(())
+        } else {
+            Err(BlsError::InvalidSignature)
+        }
+    }
+
+    /// Verifies aggregated signature of the same message signed by all `public_keys`
+    ///
+    /// Public keys must be accompanied by verified [proofs of possession](PublicKey::verify_possession).
+    pub fn fast_aggregate_verify(
+        &self,
+        public_keys: &[PublicKey<V>],
+        message: &[u8],
+    ) -> Result<(), BlsError> {
+        let public_key = PublicKey::aggregate(public_keys).or(Err(BlsError::InvalidSignature))?;
+        self.verify(&public_key, message)
+    }
+
+    /// Parses compressed signature
+    ///
+    /// Returns error if bytes don't encode a point of prime order subgroup.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        let point = Point::from_bytes(bytes).or(Err(BlsError::MalformedSignature))?;
+        Ok(Self { point })
+    }
+
+    /// Serializes signature in compressed form
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.point.to_bytes(true).to_vec()
+    }
+}
+
+impl<V: BlsVariant> ProofOfPossession<V> {
+    /// Parses compressed proof
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        Ok(Self {
+            signature: Signature::from_bytes(bytes)?,
+        })
+    }
+
+    /// Serializes the proof in compressed form
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.signature.to_bytes()
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum BlsError {
+    #[error("public key is not valid")]
+    InvalidPublicKey,
+    #[error("secret key is zero")]
+    ZeroSecretKey,
+    #[error("signature is malformed")]
+    MalformedSignature,
+    #[error("signature is not valid")]
+    InvalidSignature,
+    #[error("proof of possession is not valid")]
+    InvalidProofOfPossession,
+    #[error("nothing to aggregate")]
+    EmptyAggregation,
+}
+
+impl<V: BlsVariant> Clone for SecretKey<V> {
+    fn clone(&self) -> Self {
+        Self {
+            scalar: self.scalar.clone(),
+        }
+    }
+}
+
+impl<V: BlsVariant> fmt::Debug for SecretKey<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretKey {{ .. }}")
+    }
+}
+
+macro_rules! impl_point_wrapper_traits {
+    ($($type:ident { $field:ident }),+) => {$(
+        impl<V: BlsVariant> Clone for $type<V> {
+            fn clone(&self) -> Self {
+                Self {
+                    $field: self.$field.clone(),
+                }
+            }
+        }
+
+        impl<V: BlsVariant> fmt::Debug for $type<V> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_struct(stringify!($type))
+                    .field(stringify!($field), &self.$field)
+                    .finish()
+            }
+        }
+
+        impl<V: BlsVariant> PartialEq for $type<V> {
+            fn eq(&self, other: &Self) -> bool {
+                self.$field == other.$field
+            }
+        }
+
+        impl<V: BlsVariant> Eq for $type<V> {}
+    )+};
+}
+
+impl_point_wrapper_traits!(
+    PublicKey { point },
+    Signature { point },
+    ProofOfPossession { signature }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arithmetic::*;
+
+    struct TestVector {
+        secret_key: &'static str,
+        message: &'static [u8],
+        public_key: &'static str,
+        signature: &'static str,
+        proof_of_possession: Option<&'static str>,
+    }
+
+    fn secret_key<V: BlsVariant>(hex: &str) -> SecretKey<V> {
+        SecretKey::from_scalar(Scalar::from_bigint(&BigInt::from_hex(hex).unwrap())).unwrap()
+    }
+
+    fn check_test_vectors<V: BlsVariant>(vectors: &[TestVector], dst: &[u8]) {
+        for vector in vectors {
+            let secret_key = secret_key::<V>(vector.secret_key);
+            let public_key = secret_key.public_key();
+            assert_eq!(hex::encode(public_key.to_bytes()), vector.public_key);
+            assert_eq!(
+                PublicKey::from_bytes(&hex::decode(vector.public_key).unwrap()).unwrap(),
+                public_key
+            );
+
+            let signature = secret_key.sign_with_dst(vector.message, dst);
+            assert_eq!(hex::encode(signature.to_bytes()), vector.signature);
+            let signature =
+                Signature::<V>::from_bytes(&hex::decode(vector.signature).unwrap()).unwrap();
+            assert!(signature
+                .verify_with_dst(&public_key, vector.message, dst)
+                .is_ok());
+
+            if let Some(pop) = vector.proof_of_possession {
+                assert_eq!(hex::encode(secret_key.prove_possession().to_bytes()), pop);
+                let pop = ProofOfPossession::from_bytes(&hex::decode(pop).unwrap()).unwrap();
+                assert!(public_key.verify_possession(&pop).is_ok());
+            }
+        }
+    }
+
+    /// Basic scheme (`BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_`) test vectors taken from
+    /// [filecoin-project/bls-signatures](https://github.com/filecoin-project/bls-signatures)
+    #[test]
+    fn min_pk_basic_test_vectors() {
+        check_test_vectors::<MinPk>(
+            &[
+                TestVector {
+                    secret_key: "3ce2e976962a07ab68ccfa29194968dbb6c917c041d44bfc1c9f1a671017f70e",
+                    message: b"",
+                    public_key: "b2be11dc8e54ee74dbc07569fd74fe03b5f52ad71cd49a8579b6c6387891f5a20ad980ec2747618c1b9ad35846a68a3e",
+                    signature: "b53cfdf8b488a286df1ed20432e2bbc4e6361003757dfda3a4fd6cd98de95e5513f7c448d70b2681e14547a6ced47e7c10e28432e8abcb34de1dc28f39328fd2a13db12a4c6a30bd17b0e42881a429003e4c24583ba0f29a40fd836cf05e1a40",
+                    proof_of_possession: None,
+                },
+                TestVector {
+                    secret_key: "6de2989580e8210501e005a7e45f645fc525518d4d2acf1b7fce5852d5d3fe5f",
+                    message: b"1234",
+                    public_key: "981de2d88a80a2d7752ecda66443340a789ea62dd68dca6a3a8caf3b6c1e94248a8819a4f6ba554f50f5ccb8bc40e67c",
+                    signature: "84aa59cad078a34c3c1f876e924ee199cd8cf74857cebcad3037561964cfda50dce5f4d0709aa690dae7113b01a9c8c31557f5589c38eb720e86864ff0c4446fba21899d4cd0b2862ec395de1dfdb736bf38ca56d17019b257c5d4dd563bf5b7",
+                    proof_of_possession: None,
+                },
+                TestVector {
+                    secret_key: "16696c2bb32968007ca92ec631286349ec19eddb461605c7eaa1d88e08dd356d",
+                    message: b"4fb3300556a1b2e3978f5da60cb0708b4d41385a6008976a88527d418d21cf9e",
+                    public_key: "a31e3adf1fd542e00c8ae3d4dafea1133c09cd71723584b159cfb78ab5e1bad97f955b891f761c79fa8215b8c53addf7",
+                    signature: "8d4512fabe80f7fb68726794a86b2e20cd04b5787aecfbf43b02c8eb67e42b60b797eba0489c4eb1165e4391f31ee8600af43a3d57e6bfdbb950564916f6b1c57e4ef7fba312fd7db431aaec906fa38acefe90687d3755cb3d10b19901bd0074",
+                    proof_of_possession: None,
+                },
+            ],
+            b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_",
+        )
+    }
+
+    /// Proof-of-possession scheme test vectors taken from `bls/sign` test suite of
+    /// [Ethereum consensus specs](https://github.com/ethereum/consensus-spec-tests), which
+    /// uses `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite of
+    /// [draft-irtf-cfrg-bls-signature](https://datatracker.ietf.org/doc/draft-irtf-cfrg-bls-signature/)
+    #[test]
+    fn min_pk_pop_ethereum_test_vectors() {
+        check_test_vectors::<MinPk>(
+            &[
+                TestVector {
+                    secret_key: "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+                    message: &[0x56; 32],
+                    public_key: "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+                    signature: "882730e5d03f6b42c3abc26d3372625034e1d871b65a8a6b900a56dae22da98abbe1b68f85e49fe7652a55ec3d0591c20767677e33e5cbb1207315c41a9ac03be39c2e7668edc043d6cb1d9fd93033caa8a1c5b0e84bedaeb6c64972503a43eb",
+                    proof_of_possession: None,
+                },
+                TestVector {
+                    secret_key: "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+                    message: &[0xab; 32],
+                    public_key: "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+                    signature: "91347bccf740d859038fcdcaf233eeceb2a436bcaaee9b2aa3bfb70efe29dfb2677562ccbea1c8e061fb9971b0753c240622fab78489ce96768259fc01360346da5b9f579e5da0d941e4c6ba18a0e64906082375394f337fa1af2b7127b0d121",
+                    proof_of_possession: None,
+                },
+                TestVector {
+                    secret_key: "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+                    message: &[0x00; 32],
+                    public_key: "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+                    signature: "b23c46be3a001c63ca711f87a005c200cc550b9429d5f4eb38d74322144f1b63926da3388979e5321012fb1a0526bcd100b5ef5fe72628ce4cd5e904aeaa3279527843fae5ca9ca675f4f51ed8f83bbf7155da9ecc9663100a885d5dc6df96d9",
+                    proof_of_possession: None,
+                },
+            ],
+            MinPk::SIGNATURE_DST,
+        )
+    }
+
+    /// Supplementary proof-of-possession scheme vectors generated with
+    /// [blst](https://github.com/supranational/blst), which cover empty and short messages and
+    /// proofs of possession that the published vectors above don't
+    #[test]
+    fn min_pk_pop_test_vectors() {
+        check_test_vectors::<MinPk>(
+            &[
+                TestVector {
+                    secret_key: "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+                    message: b"",
+                    public_key: "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+                    signature: "b6b4caa2a4bfa3612b79437d0e549aba52551d434315717635f823337431c0e068d47cf616a40a47b81b489e9c73381706355724af3542ae49b16c6341b120b7d664369f9816b3cedce7cc9c4707f514e2865ba2131211de29e09a6e42f686da",
+                    proof_of_possession: Some("b803eb0ed93ea10224a73b6b9c725796be9f5fefd215ef7a5b97234cc956cf6870db6127b7e4d824ec62276078e787db05584ce1adbf076bc0808ca0f15b73d59060254b25393d95dfc7abe3cda566842aaedf50bbb062aae1bbb6ef3b1f77e1"),
+                },
+                TestVector {
+                    secret_key: "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+                    message: b"abc",
+                    public_key: "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+                    signature: "b54d25554d995109164a6ba64db490bcd88e52248e562d327579abdd3d4af5665880b38a5e32824b49efe574d6459389040337dd273526c1f5f932289cc756e03be3353f5bf80e6f6d6b06a1981fde9b9521f7876e44494a8df45c37587e8927",
+                    proof_of_possession: Some("88bb31b27eae23038e14f9d9d1b628a39f5881b5278c3c6f0249f81ba0deb1f68aa5f8847854d6554051aa810fdf1cdb02df4af7a5647b1aa4afb60ec6d446ee17af24a8a50876ffdaf9bf475038ec5f8ebeda1c1c6a3220293e23b13a9a5d26"),
+                },
+                TestVector {
+                    secret_key: "328388aff0d4a5b7dc9205abd374e7e98f3cd9f3418edb4eafda5fb16473d216",
+                    message: b"abcdef0123456789",
+                    public_key: "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f",
+                    signature: "941c3893297e30321fc60f377a0e2684774758405b79cbeaa04b6f107a65eb43cd83a6818c74be0d1c58626ea6c7126305fd0a0216d8b80afc4f35a60540e3592443d4b57e8c7d3c3e7d55e3f0c76c576350ae235e7bb982ab51acbc20d40f2c",
+                    proof_of_possession: Some("88873ea58f5017a33facc9bf04efaf5e2f34f7bc9ce564d0481dd469326c04ef43552f50e99de8a13315dcd37a4fb9ef036d1a54e5febf5d20b6aa488f3e3c917e6a96ce6461f609ec7e0a1fd8950380922e46c3654fa7542436603f833462da"),
+                },
+            ],
+            MinPk::SIGNATURE_DST,
+        )
+    }
+
+    fn sign_verify<V: BlsVariant>() {
+        let secret_key = SecretKey::<V>::random();
+        let public_key = secret_key.public_key();
+        let signature = secret_key.sign(b"hello");
+        assert!(signature.verify(&public_key, b"hello").is_ok());
+        assert_eq!(
+            signature.verify(&public_key, b"hello!"),
+            Err(BlsError::InvalidSignature)
+        );
+        assert_eq!(
+            signature.verify(&SecretKey::random().public_key(), b"hello"),
+            Err(BlsError::InvalidSignature)
+        );
+        // Signature is bound to domain separation tag
+        assert_eq!(
+            secret_key
+                .sign_with_dst(b"hello", V::POP_DST)
+                .verify(&public_key, b"hello"),
+            Err(BlsError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn sign_verify_min_pk() {
+        sign_verify::<MinPk>()
+    }
+
+    #[test]
+    fn sign_verify_min_sig() {
+        sign_verify::<MinSig>()
+    }
+
+    fn aggregate<V: BlsVariant>() {
+        let secret_keys: Vec<SecretKey<V>> = (0..4).map(|_| SecretKey::random()).collect();
+        let public_keys: Vec<_> = secret_keys.iter().map(|sk| sk.public_key()).collect();
+
+        let signatures: Vec<_> = secret_keys.iter().map(|sk| sk.sign(b"hello")).collect();
+        let signature = Signature::aggregate(&signatures).unwrap();
+        assert!(signature
+            .fast_aggregate_verify(&public_keys, b"hello")
+            .is_ok());
+        assert!(signature
+            .verify(&PublicKey::aggregate(&public_keys).unwrap(), b"hello")
+            .is_ok());
+        assert_eq!(
+            signature.fast_aggregate_verify(&public_keys[1..], b"hello"),
+            Err(BlsError::InvalidSignature)
+        );
+        assert_eq!(
+            signature.fast_aggregate_verify(&[], b"hello"),
+            Err(BlsError::InvalidSignature)
+        );
+
+        let messages: Vec<&[u8]> = vec![b"a", b"b", b"c", b"a"];
+        let signatures: Vec<_> = secret_keys
+            .iter()
+            .zip(&messages)
+            .map(|(sk, message)| sk.sign(message))
+            .collect();
+        let signature = Signature::aggregate(&signatures).unwrap();
+        assert!(signature.aggregate_verify(&public_keys, &messages).is_ok());
+        let mut wrong_messages = messages.clone();
+        wrong_messages.swap(0, 1);
+        assert_eq!(
+            signature.aggregate_verify(&public_keys, &wrong_messages),
+            Err(BlsError::InvalidSignature)
+        );
+
+        assert_eq!(
+            Signature::<V>::aggregate(&[]),
+            Err(BlsError::EmptyAggregation)
+        );
+    }
+
+    #[test]
+    fn aggregate_min_pk() {
+        aggregate::<MinPk>()
+    }
+
+    #[test]
+    fn aggregate_min_sig() {
+        aggregate::<MinSig>()
+    }
+
+    fn proof_of_possession<V: BlsVariant>() {
+        let secret_key = SecretKey::<V>::random();
+        let public_key = secret_key.public_key();
+        let proof = secret_key.prove_possession();
+        assert!(public_key.verify_possession(&proof).is_ok());
+        assert_eq!(
+            SecretKey::<V>::random()
+                .public_key()
+                .verify_possession(&proof),
+            Err(BlsError::InvalidProofOfPossession)
+        );
+
+        // Rogue key `pk' = X - pk` lets attacker forge aggregated signature on behalf of both
+        // parties, but attacker can't prove possession of the rogue key
+        let attacker_key = SecretKey::<V>::random();
+        let rogue_key =
+            PublicKey::from_point(attacker_key.public_key().as_point() - public_key.as_point())
+                .unwrap();
+        let forged = attacker_key.sign(b"hello");
+        assert!(forged
+            .fast_aggregate_verify(&[public_key, rogue_key.clone()], b"hello")
+            .is_ok());
+        assert_eq!(
+            rogue_key.verify_possession(&attacker_key.prove_possession()),
+            Err(BlsError::InvalidProofOfPossession)
+        );
+    }
+
+    #[test]
+    fn proof_of_possession_min_pk() {
+        proof_of_possession::<MinPk>()
+    }
+
+    #[test]
+    fn proof_of_possession_min_sig() {
+        proof_of_possession::<MinSig>()
+    }
+
+    #[test]
+    fn zero_keys_are_rejected() {
+        assert_eq!(
+            SecretKey::<MinPk>::from_scalar(Scalar::zero()).unwrap_err(),
+            BlsError::ZeroSecretKey
+        );
+        assert_eq!(
+            PublicKey::<MinPk>::from_point(Point::zero()).unwrap_err(),
+            BlsError::InvalidPublicKey
+        );
+        assert_eq!(
+            PublicKey::<MinSig>::from_bytes(&Point::<Bls12_381_2>::zero().to_bytes(true))
+                .unwrap_err(),
+            BlsError::InvalidPublicKey
+        );
+    }
+}
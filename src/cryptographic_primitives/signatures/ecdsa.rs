@@ -0,0 +1,714 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! ECDSA signatures over secp256k1 and secp256r1
+//!
+//! Signing is deterministic: nonce is derived from the secret key and the message hash as specified
+//! in [RFC 6979]. Produced signatures are always normalized to low-S form (`s <= n/2`), verification
+//! accepts both forms (use [Signature::is_low_s] if you need to reject high-S signatures).
+//!
+//! Signatures can be encoded in strict DER, or in compact form `r || s` (`r || s || v` for
+//! [RecoverableSignature]). Recoverable signature additionally carries recovery id `v` which
+//! allows to [recover](RecoverableSignature::recover_public_key) signer's public key from the
+//! signature and the message.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::signatures::ecdsa::{RecoverableSignature, Signature};
+//! use curv::elliptic::curves::{Point, Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! let secret_key = Scalar::<Secp256k1>::random();
+//! let public_key = Point::generator() * &secret_key;
+//!
+//! let signature = RecoverableSignature::sign::<Sha256>(&secret_key, b"hello");
+//! assert!(signature.signature.verify::<Sha256>(&public_key, b"hello").is_ok());
+//! assert_eq!(signature.recover_public_key::<Sha256>(b"hello").unwrap(), public_key);
+//!
+//! let der = signature.signature.to_der();
+//! assert_eq!(Signature::from_der(&der).unwrap(), signature.signature);
+//! ```
+//!
+//! [RFC 6979]: https://www.rfc-editor.org/rfc/rfc6979
+
+use digest::{BlockInput, FixedOutput, Reset, Update};
+use hmac::{Hmac, Mac, NewMac};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use typenum::Unsigned;
+
+use crate::arithmetic::*;
+use crate::elliptic::curves::{Curve, Point, Scalar, Secp256k1, Secp256r1};
+
+/// Curve on which ECDSA is defined
+///
+/// Implemented for curves with SEC1 point encoding: compressed point is `0x02 | y_parity`
+/// followed by x coordinate, which is relied on by public key recovery.
+pub trait EcdsaCurve: Curve {}
+
+impl EcdsaCurve for Secp256k1 {}
+impl EcdsaCurve for Secp256r1 {}
+
+/// ECDSA signature `(r, s)`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Signature<E: EcdsaCurve> {
+    pub r: Scalar<E>,
+    pub s: Scalar<E>,
+}
+
+/// ECDSA signature along with recovery id
+///
+/// Recovery id `v` encodes nonce point `R`: bit 0 is parity of `R.y`, bit 1 is set if `R.x`
+/// exceeded group order (i.e. `R.x = r + n`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct RecoverableSignature<E: EcdsaCurve> {
+    pub signature: Signature<E>,
+    pub recovery_id: u8,
+}
+
+impl<E: EcdsaCurve> Signature<E> {
+    /// Signs a message hashing it with `H`
+    ///
+    /// ## Panics
+    /// Panics if secret key is zero
+    pub fn sign<H>(secret_key: &Scalar<E>, message: &[u8]) -> Self
+    where
+        H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+    {
+        RecoverableSignature::sign::<H>(secret_key, message).signature
+    }
+
+    /// Signs a message hash
+    ///
+    /// Hash is truncated to bit length of group order. `H` is used to derive the nonce.
+    ///
+    /// ## Panics
+    /// Panics if secret key is zero
+    pub fn sign_prehashed<H>(secret_key: &Scalar<E>, hash: &[u8]) -> Self
+    where
+        H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+    {
+        RecoverableSignature::sign_prehashed::<H>(secret_key, hash).signature
+    }
+
+    /// Verifies the signature of a message hashing it with `H`
+    pub fn verify<H>(&self, public_key: &Point<E>, message: &[u8]) -> Result<(), EcdsaError>
+    where
+        H: Update + FixedOutput + Reset + Default + Clone,
+    {
+        self.verify_prehashed(public_key, &hash_message::<H>(message))
+    }
+
+    /// Verifies the signature of a message hash
+    ///
+    /// Both low-S and high-S signatures are accepted.
+    pub fn verify_prehashed(&self, public_key: &Point<E>, hash: &[u8]) -> Result<(), EcdsaError> {
+        if self.r.is_zero() || self.s.is_zero() || public_key.is_zero() {
+            return Err(EcdsaError::InvalidSignature);
+        }
+        let z = bits2scalar::<E>(hash);
+        let s_inv = self.s.invert().ok_or(EcdsaError::InvalidSignature)?;
+        let u1 = z * &s_inv;
+        let u2 = &self.r * &s_inv;
+        let x = Point::generator() * u1 + public_key * u2;
+        match x.x_coord() {
+            Some(x) if Scalar::<E>::from_bigint(&x) == self.r => Ok(()),
+            _ => Err(EcdsaError::InvalidSignature),
+        }
+    }
+
+    /// Checks whether `s <= n/2`
+    pub fn is_low_s(&self) -> bool {
+        self.s.to_bigint() <= Scalar::<E>::group_order() >> 1
+    }
+
+    /// Returns the signature in low-S form, replacing `s` with `n - s` if needed
+    ///
+    /// Both forms are valid signatures of the same message.
+    pub fn normalize_s(self) -> Self {
+        if self.is_low_s() {
+            self
+        } else {
+            Self {
+                r: self.r,
+                s: -self.s,
+            }
+        }
+    }
+
+    /// Parses strict DER encoding of the signature
+    ///
+    /// Encoding is rejected if it's not minimal (e.g. integers with redundant leading zeroes),
+    /// contains trailing bytes, or if `r` or `s` is not in range `[1; n)`.
+    pub fn from_der(bytes: &[u8]) -> Result<Self, EcdsaError> {
+        let (sequence, rest) = parse_der_element(bytes, 0x30)?;
+        if !rest.is_empty() {
+            return Err(EcdsaError::MalformedSignature);
+        }
+        let (r, sequence) = parse_der_element(sequence, 0x02)?;
+        let (s, sequence) = parse_der_element(sequence, 0x02)?;
+        if !sequence.is_empty() {
+            return Err(EcdsaError::MalformedSignature);
+        }
+        Ok(Self {
+            r: parse_der_integer(r)?,
+            s: parse_der_integer(s)?,
+        })
+    }
+
+    /// Serializes the signature in DER
+    pub fn to_der(&self) -> Vec<u8> {
+        let r = der_integer(&self.r);
+        let s = der_integer(&self.s);
+        let mut content = Vec::with_capacity(r.len() + s.len());
+        content.extend_from_slice(&r);
+        content.extend_from_slice(&s);
+        der_element(0x30, &content)
+    }
+
+    /// Parses compact encoding `r || s`
+    ///
+    /// Returns error if `r` or `s` is not in range `[1; n)`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EcdsaError> {
+        let scalar_len = scalar_len::<E>();
+        if bytes.len() != 2 * scalar_len {
+            return Err(EcdsaError::MalformedSignature);
+        }
+        Ok(Self {
+            r: parse_scalar(&bytes[..scalar_len])?,
+            s: parse_scalar(&bytes[scalar_len..])?,
+        })
+    }
+
+    /// Serializes the signature in compact form `r || s`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.r.to_bytes().to_vec();
+        bytes.extend_from_slice(&self.s.to_bytes());
+        bytes
+    }
+}
+
+impl<E: EcdsaCurve> RecoverableSignature<E> {
+    /// Signs a message hashing it with `H`
+    ///
+    /// ## Panics
+    /// Panics if secret key is zero
+    pub fn sign<H>(secret_key: &Scalar<E>, message: &[u8]) -> Self
+    where
+        H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+    {
+        Self::sign_prehashed::<H>(secret_key, &hash_message::<H>(message))
+    }
+
+    /// Signs a message hash
+    ///
+    /// Hash is truncated to bit length of group order. `H` is used to derive the nonce.
+    ///
+    /// ## Panics
+    /// Panics if secret key is zero
+    pub fn sign_prehashed<H>(secret_key: &Scalar<E>, hash: &[u8]) -> Self
+    where
+        H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+    {
+        assert!(!secret_key.is_zero(), "secret key is zero");
+        let z = bits2scalar::<E>(hash);
+        let mut nonces = Rfc6979Nonces::<H>::new::<E>(secret_key, &z);
+        loop {
+            let k = nonces.next::<E>();
+            let r_point = Point::generator() * &k;
+            let r_x = match r_point.x_coord() {
+                Some(x) => x,
+                None => continue,
+            };
+            let r = Scalar::<E>::from_bigint(&r_x);
+            let s = match k.invert() {
+                Some(k_inv) => k_inv * (&z + &r * secret_key),
+                None => continue,
+            };
+            if r.is_zero() || s.is_zero() {
+                continue;
+            }
+
+            let y_is_odd = r_point.to_bytes(true)[0] == 0x03;
+            let x_overflow = &r_x >= Scalar::<E>::group_order();
+            let recovery_id = (y_is_odd as u8) | ((x_overflow as u8) << 1);
+            return Self {
+                signature: Signature { r, s },
+                recovery_id,
+            }
+            .normalize_s();
+        }
+    }
+
+    /// Returns the signature in low-S form, replacing `s` with `n - s` and adjusting recovery id
+    /// if needed
+    pub fn normalize_s(self) -> Self {
+        if self.signature.is_low_s() {
+            self
+        } else {
+            Self {
+                signature: Signature {
+                    r: self.signature.r,
+                    s: -self.signature.s,
+                },
+                recovery_id: self.recovery_id ^ 1,
+            }
+        }
+    }
+
+    /// Recovers public key of the signer of a message hashing it with `H`
+    pub fn recover_public_key<H>(&self, message: &[u8]) -> Result<Point<E>, EcdsaError>
+    where
+        H: Update + FixedOutput + Reset + Default + Clone,
+    {
+        self.recover_public_key_prehashed(&hash_message::<H>(message))
+    }
+
+    /// Recovers public key of the signer of a message hash
+    ///
+    /// Returns error if recovery id doesn't correspond to any curve point. Note that any
+    /// signature with valid recovery id yields some public key, the signature is valid against
+    /// it by construction, so recovered key needs to be compared with the expected one.
+    pub fn recover_public_key_prehashed(&self, hash: &[u8]) -> Result<Point<E>, EcdsaError> {
+        let Signature { r, s } = &self.signature;
+        if self.recovery_id > 3 || r.is_zero() || s.is_zero() {
+            return Err(EcdsaError::RecoveryFailed);
+        }
+
+        let mut r_x = r.to_bigint();
+        if self.recovery_id & 2 != 0 {
+            r_x += Scalar::<E>::group_order();
+        }
+        let field_len = Point::<E>::generator().to_bytes(true).len() - 1;
+        let r_x = r_x.to_bytes();
+        if r_x.len() > field_len {
+            return Err(EcdsaError::RecoveryFailed);
+        }
+        let mut compressed = vec![0u8; field_len + 1];
+        compressed[0] = 0x02 | (self.recovery_id & 1);
+        compressed[1 + field_len - r_x.len()..].copy_from_slice(&r_x);
+        let r_point = Point::<E>::from_bytes(&compressed).or(Err(EcdsaError::RecoveryFailed))?;
+
+        let z = bits2scalar::<E>(hash);
+        let r_inv = r.invert().ok_or(EcdsaError::RecoveryFailed)?;
+        let public_key = (r_point * s - Point::generator() * z) * r_inv;
+        if public_key.is_zero() {
+            return Err(EcdsaError::RecoveryFailed);
+        }
+        Ok(public_key)
+    }
+
+    /// Parses compact encoding `r || s || v`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EcdsaError> {
+        let (v, signature) = bytes.split_last().ok_or(EcdsaError::MalformedSignature)?;
+        if *v > 3 {
+            return Err(EcdsaError::MalformedSignature);
+        }
+        Ok(Self {
+            signature: Signature::from_bytes(signature)?,
+            recovery_id: *v,
+        })
+    }
+
+    /// Serializes the signature in compact form `r || s || v`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.signature.to_bytes();
+        bytes.push(self.recovery_id);
+        bytes
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum EcdsaError {
+    #[error("signature is malformed")]
+    MalformedSignature,
+    #[error("signature is not valid")]
+    InvalidSignature,
+    #[error("public key cannot be recovered from the signature")]
+    RecoveryFailed,
+}
+
+/// Nonces generator defined in [RFC 6979, section 3.2](https://www.rfc-editor.org/rfc/rfc6979#section-3.2)
+struct Rfc6979Nonces<H>
+where
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    k: Hmac<H>,
+    v: Vec<u8>,
+    first: bool,
+}
+
+impl<H> Rfc6979Nonces<H>
+where
+    H: Update + BlockInput + FixedOutput + Reset + Default + Clone,
+{
+    fn new<E: Curve>(secret_key: &Scalar<E>, z: &Scalar<E>) -> Self {
+        let hash_len = <H as FixedOutput>::OutputSize::USIZE;
+        let x = secret_key.to_bytes();
+        let h = z.to_bytes();
+
+        let mut k = Hmac::<H>::new_from_slice(&vec![0u8; hash_len]).expect("any key length");
+        let mut v = vec![1u8; hash_len];
+        for i in 0..2u8 {
+            k.update(&v);
+            k.update(&[i]);
+            k.update(&x);
+            k.update(&h);
+            k = Self::rekey(k);
+            v = Self::hmac(&k, &v);
+        }
+        Self { k, v, first: true }
+    }
+
+    fn next<E: Curve>(&mut self) -> Scalar<E> {
+        if !self.first {
+            self.k.update(&self.v);
+            self.k.update(&[0]);
+            self.k = Self::rekey(self.k.clone());
+            self.v = Self::hmac(&self.k, &self.v);
+        }
+        self.first = false;
+
+        let q = Scalar::<E>::group_order();
+        loop {
+            let mut t = Vec::with_capacity(scalar_len::<E>());
+            while t.len() < scalar_len::<E>() {
+                self.v = Self::hmac(&self.k, &self.v);
+                t.extend_from_slice(&self.v);
+            }
+            let k = bits2int::<E>(&t);
+            if !NumberTests::is_zero(&k) && &k < q {
+                return Scalar::from_bigint(&k);
+            }
+            self.k.update(&self.v);
+            self.k.update(&[0]);
+            self.k = Self::rekey(self.k.clone());
+            self.v = Self::hmac(&self.k, &self.v);
+        }
+    }
+
+    /// Takes HMAC with absorbed data, and returns HMAC keyed with its output
+    fn rekey(hmac: Hmac<H>) -> Hmac<H> {
+        let key = hmac.finalize().into_bytes();
+        Hmac::<H>::new_from_slice(&key).expect("any key length")
+    }
+
+    fn hmac(key: &Hmac<H>, data: &[u8]) -> Vec<u8> {
+        let mut hmac = key.clone();
+        hmac.update(data);
+        hmac.finalize().into_bytes().to_vec()
+    }
+}
+
+fn hash_message<H: Update + FixedOutput + Reset + Default + Clone>(message: &[u8]) -> Vec<u8> {
+    let mut hasher = H::default();
+    hasher.update(message);
+    hasher.finalize_fixed().to_vec()
+}
+
+/// Length of serialized scalar in bytes
+fn scalar_len<E: Curve>() -> usize {
+    Scalar::<E>::zero().to_bytes().len()
+}
+
+/// Interprets leftmost `qlen` bits of `bytes` as an integer, where `qlen` is bit length of
+/// group order
+fn bits2int<E: Curve>(bytes: &[u8]) -> BigInt {
+    let qlen = Scalar::<E>::group_order().bit_length();
+    let n = BigInt::from_bytes(bytes);
+    let blen = bytes.len() * 8;
+    if blen > qlen {
+        n >> (blen - qlen)
+    } else {
+        n
+    }
+}
+
+fn bits2scalar<E: Curve>(bytes: &[u8]) -> Scalar<E> {
+    Scalar::from_bigint(&bits2int::<E>(bytes))
+}
+
+/// Parses big-endian integer in range `[1; n)`
+fn parse_scalar<E: Curve>(bytes: &[u8]) -> Result<Scalar<E>, EcdsaError> {
+    let n = BigInt::from_bytes(bytes);
+    if NumberTests::is_zero(&n) || &n >= Scalar::<E>::group_order() {
+        return Err(EcdsaError::MalformedSignature);
+    }
+    Ok(Scalar::from_bigint(&n))
+}
+
+/// Parses DER element with expected tag, returns its content and remaining bytes
+fn parse_der_element(bytes: &[u8], tag: u8) -> Result<(&[u8], &[u8]), EcdsaError> {
+    match bytes {
+        [t, len, rest @ ..] if *t == tag && *len < 0x80 => {
+            let len = usize::from(*len);
+            if rest.len() < len {
+                return Err(EcdsaError::MalformedSignature);
+            }
+            Ok(rest.split_at(len))
+        }
+        // Long form is only allowed for lengths that don't fit in a single byte
+        [t, 0x81, len, rest @ ..] if *t == tag && *len >= 0x80 => {
+            let len = usize::from(*len);
+            if rest.len() < len {
+                return Err(EcdsaError::MalformedSignature);
+            }
+            Ok(rest.split_at(len))
+        }
+        _ => Err(EcdsaError::MalformedSignature),
+    }
+}
+
+/// Parses content of DER integer, it must be positive and minimally encoded
+fn parse_der_integer<E: Curve>(bytes: &[u8]) -> Result<Scalar<E>, EcdsaError> {
+    match bytes {
+        [] => Err(EcdsaError::MalformedSignature),
+        [first, ..] if first & 0x80 != 0 => Err(EcdsaError::MalformedSignature),
+        [0, second, ..] if second & 0x80 == 0 => Err(EcdsaError::MalformedSignature),
+        _ => parse_scalar(bytes),
+    }
+}
+
+fn der_element(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![tag];
+    if content.len() >= 0x80 {
+        bytes.push(0x81);
+    }
+    bytes.push(content.len() as u8);
+    bytes.extend_from_slice(content);
+    bytes
+}
+
+fn der_integer<E: Curve>(scalar: &Scalar<E>) -> Vec<u8> {
+    let mut content = scalar.to_bigint().to_bytes();
+    if content.is_empty() || content[0] & 0x80 != 0 {
+        content.insert(0, 0);
+    }
+    der_element(0x02, &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sha2::{Sha256, Sha512};
+    use sha3::{Digest, Keccak256};
+
+    use blobby::Blob5Iterator;
+
+    fn test_wycheproof<E: EcdsaCurve>(data: &[u8]) {
+        let mut checked = 0;
+        for (i, row) in Blob5Iterator::new(data).unwrap().enumerate() {
+            let [wx, wy, msg, sig, pass] = row.unwrap();
+            let pass = match pass {
+                [0] => false,
+                [1] => true,
+                _ => panic!("invalid value for pass flag"),
+            };
+            let public_key =
+                Point::<E>::from_coords(&BigInt::from_bytes(wx), &BigInt::from_bytes(wy))
+                    .unwrap_or_else(|e| panic!("test #{}: invalid public key: {}", i, e));
+
+            let signature = match Signature::<E>::from_der(sig) {
+                Ok(signature) => signature,
+                Err(_) if !pass => continue,
+                Err(e) => panic!("test #{}: failed to parse signature: {}", i, e),
+            };
+            let result = signature.verify::<Sha256>(&public_key, msg);
+            assert_eq!(result.is_ok(), pass, "test #{}: {:?}", i, result);
+            if pass {
+                assert_eq!(Signature::from_der(&signature.to_der()).unwrap(), signature);
+                let recovered = (0..4).any(|recovery_id| {
+                    let signature = RecoverableSignature {
+                        signature: signature.clone(),
+                        recovery_id,
+                    };
+                    signature.recover_public_key::<Sha256>(msg).as_ref() == Ok(&public_key)
+                });
+                assert!(recovered, "test #{}: public key wasn't recovered", i);
+            }
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn wycheproof_secp256k1() {
+        test_wycheproof::<Secp256k1>(include_bytes!(
+            "test_vectors/wycheproof_ecdsa_secp256k1_sha256.blb"
+        ))
+    }
+
+    #[test]
+    fn wycheproof_secp256r1() {
+        test_wycheproof::<Secp256r1>(include_bytes!(
+            "test_vectors/wycheproof_ecdsa_secp256r1_sha256.blb"
+        ))
+    }
+
+    /// Test vectors from [RFC 6979, A.2.5](https://www.rfc-editor.org/rfc/rfc6979#appendix-A.2.5)
+    /// (P-256, SHA-256). Expected signatures are normalized to low-S form.
+    #[test]
+    fn rfc6979_secp256r1() {
+        let secret_key = Scalar::<Secp256r1>::from_bytes(
+            &hex::decode("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721")
+                .unwrap(),
+        )
+        .unwrap();
+        let vectors: &[(&[u8], &str)] = &[
+            (
+                b"sample",
+                "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+                 f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+            ),
+            (
+                b"test",
+                "f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367\
+                 019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083",
+            ),
+        ];
+        for (message, expected) in vectors {
+            let expected = Signature::<Secp256r1>::from_bytes(&hex::decode(expected).unwrap())
+                .unwrap()
+                .normalize_s();
+            let signature = Signature::sign::<Sha256>(&secret_key, message);
+            assert_eq!(signature, expected);
+            assert!(signature.is_low_s());
+        }
+    }
+
+    /// Ethereum-style signature: keccak256 message hash, HMAC-SHA256 nonces. Test vector adapted
+    /// from `k256` crate.
+    #[test]
+    fn recoverable_signature_secp256k1() {
+        let secret_key = Scalar::<Secp256k1>::from_bytes(
+            &hex::decode("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+                .unwrap(),
+        )
+        .unwrap();
+        let message = hex::decode(
+            "e9808504e3b29200831e848094f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca0080018080",
+        )
+        .unwrap();
+        let hash = Keccak256::digest(&message);
+
+        let signature = RecoverableSignature::sign_prehashed::<Sha256>(&secret_key, &hash);
+        let expected = hex::decode(
+            "c9cf86333bcb065d140032ecaab5d9281bde80f21b9687b3e94161de42d51895\
+             727a108a0b8d101465414033c3f705a9c7b826e596766046ee1183dbc8aeaa68\
+             00",
+        )
+        .unwrap();
+        assert_eq!(signature.to_bytes(), expected);
+        assert_eq!(
+            signature.recover_public_key_prehashed(&hash).unwrap(),
+            Point::generator() * &secret_key
+        );
+    }
+
+    fn sign_verify_recover_ecdsa<E: EcdsaCurve>() {
+        let secret_key = Scalar::<E>::random();
+        let public_key = Point::generator() * &secret_key;
+
+        let signature = RecoverableSignature::sign::<Sha512>(&secret_key, b"hello");
+        assert!(signature.signature.is_low_s());
+        assert_eq!(
+            signature,
+            RecoverableSignature::sign::<Sha512>(&secret_key, b"hello")
+        );
+        assert!(signature
+            .signature
+            .verify::<Sha512>(&public_key, b"hello")
+            .is_ok());
+        assert_eq!(
+            signature.signature.verify::<Sha512>(&public_key, b"hello!"),
+            Err(EcdsaError::InvalidSignature)
+        );
+        assert_eq!(
+            signature.recover_public_key::<Sha512>(b"hello").unwrap(),
+            public_key
+        );
+
+        // High-S signature is valid as well, but recovers to the same key only with flipped
+        // recovery id
+        let high_s = RecoverableSignature {
+            signature: Signature {
+                r: signature.signature.r.clone(),
+                s: -&signature.signature.s,
+            },
+            recovery_id: signature.recovery_id ^ 1,
+        };
+        assert!(!high_s.signature.is_low_s());
+        assert!(high_s
+            .signature
+            .verify::<Sha512>(&public_key, b"hello")
+            .is_ok());
+        assert_eq!(
+            high_s.recover_public_key::<Sha512>(b"hello").unwrap(),
+            public_key
+        );
+        assert_eq!(high_s.normalize_s(), signature);
+
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes.len(), 2 * scalar_len::<E>() + 1);
+        assert_eq!(RecoverableSignature::from_bytes(&bytes).unwrap(), signature);
+        let der = signature.signature.to_der();
+        assert_eq!(Signature::from_der(&der).unwrap(), signature.signature);
+    }
+
+    #[test]
+    fn sign_verify_recover_secp256k1() {
+        sign_verify_recover_ecdsa::<Secp256k1>()
+    }
+
+    #[test]
+    fn sign_verify_recover_secp256r1() {
+        sign_verify_recover_ecdsa::<Secp256r1>()
+    }
+
+    #[test]
+    fn encodings_are_strict() {
+        let signature = Signature::<Secp256k1> {
+            r: Scalar::from(1),
+            s: Scalar::from(0x80),
+        };
+        let der = signature.to_der();
+        assert_eq!(der, [0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(Signature::from_der(&der).unwrap(), signature);
+
+        let malformed: &[&[u8]] = &[
+            // trailing byte
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80, 0x00],
+            // redundant leading zero
+            &[0x30, 0x08, 0x02, 0x02, 0x00, 0x01, 0x02, 0x02, 0x00, 0x80],
+            // negative integer
+            &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x80],
+            // zero
+            &[0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01],
+            // non-minimal length
+            &[0x30, 0x81, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80],
+            // truncated
+            &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00],
+        ];
+        for der in malformed {
+            assert_eq!(
+                Signature::<Secp256k1>::from_der(der),
+                Err(EcdsaError::MalformedSignature)
+            );
+        }
+
+        let mut bytes = signature.to_bytes();
+        assert_eq!(Signature::from_bytes(&bytes).unwrap(), signature);
+        bytes[32..].copy_from_slice(&Scalar::<Secp256k1>::group_order().to_bytes());
+        assert_eq!(
+            Signature::<Secp256k1>::from_bytes(&bytes),
+            Err(EcdsaError::MalformedSignature)
+        );
+    }
+}
@@ -0,0 +1,601 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! EdDSA signatures over Ed25519 as specified in [RFC 8032]
+//!
+//! Public keys and signatures are encoded exactly as in RFC 8032, public key encoding matches
+//! [`Point<Ed25519>`] compressed serialization, so keys produced by MPC protocols can be used
+//! directly via [PublicKey::from_point].
+//!
+//! Verification comes in two flavours, see [VerificationMode]. They differ only on signatures
+//! involving points of small order, which can't be produced by honest signers.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::signatures::eddsa::{KeyPair, Signature, VerificationMode};
+//!
+//! let key_pair = KeyPair::random();
+//! let signature = Signature::sign(&key_pair, b"hello");
+//! assert!(signature
+//!     .verify(key_pair.public_key(), b"hello", VerificationMode::Cofactored)
+//!     .is_ok());
+//! ```
+//!
+//! [RFC 8032]: https://www.rfc-editor.org/rfc/rfc8032.html
+
+use std::convert::TryInto;
+
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar as DalekScalar;
+use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
+use sha2::{Digest, Sha512};
+use thiserror::Error;
+
+use crate::cryptographic_primitives::proofs::{batch_verification_weight, BatchVerificationError};
+use crate::elliptic::curves::ed25519::{Ed25519Point, Ed25519Scalar};
+use crate::elliptic::curves::{ECPoint, ECScalar, Ed25519, MismatchedPointOrder, Point, Scalar};
+
+/// Secret scalar and nonce prefix derived from 32 bytes secret key (seed)
+#[derive(Clone, Debug)]
+pub struct ExpandedSecretKey {
+    scalar: Scalar<Ed25519>,
+    prefix: [u8; 32],
+}
+
+impl ExpandedSecretKey {
+    /// Expands a secret key as specified in RFC 8032 (section 5.1.5)
+    ///
+    /// `SHA-512(seed)` is split into two halves: the first one is clamped and interpreted as
+    /// a secret scalar, the second one is used as a prefix for deterministic nonce derivation.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let h = Sha512::digest(seed);
+        let mut scalar_bytes: [u8; 32] = h[..32].try_into().expect("sha512 output is 64 bytes");
+        scalar_bytes[0] &= 248;
+        scalar_bytes[31] &= 127;
+        scalar_bytes[31] |= 64;
+        let prefix = h[32..].try_into().expect("sha512 output is 64 bytes");
+        Self {
+            scalar: from_dalek_scalar(DalekScalar::from_bytes_mod_order(scalar_bytes)),
+            prefix,
+        }
+    }
+
+    /// Constructs expanded secret key from its parts
+    ///
+    /// Useful when secret scalar isn't derived from a seed, e.g. it's a result of distributed key
+    /// generation.
+    pub fn from_parts(scalar: Scalar<Ed25519>, prefix: [u8; 32]) -> Self {
+        Self { scalar, prefix }
+    }
+
+    pub fn scalar(&self) -> &Scalar<Ed25519> {
+        &self.scalar
+    }
+
+    pub fn prefix(&self) -> &[u8; 32] {
+        &self.prefix
+    }
+}
+
+/// Ed25519 public key
+///
+/// Public key might be any point on the curve, including points of small order, that's why it's
+/// not represented as [`Point<Ed25519>`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicKey {
+    compressed: CompressedEdwardsY,
+    point: EdwardsPoint,
+}
+
+impl PublicKey {
+    /// Parses 32 bytes public key
+    ///
+    /// Returns error if bytes are not a canonical encoding of a curve point.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EddsaError> {
+        let (compressed, point) = decode_point(bytes).ok_or(EddsaError::InvalidPublicKey)?;
+        Ok(Self { compressed, point })
+    }
+
+    /// Constructs a public key from a point
+    pub fn from_point(point: &Point<Ed25519>) -> Self {
+        let point = *point.as_raw().underlying_ref();
+        Self {
+            compressed: point.compress(),
+            point,
+        }
+    }
+
+    /// Serializes the public key, encoding is the same as `Point<Ed25519>::to_bytes(true)`
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.compressed.to_bytes()
+    }
+
+    /// Converts public key into [`Point<Ed25519>`]
+    ///
+    /// Returns error if public key has a small order component.
+    pub fn to_point(&self) -> Result<Point<Ed25519>, MismatchedPointOrder> {
+        Point::from_raw(Ed25519Point::from_underlying(self.point))
+    }
+}
+
+/// Expanded secret key along with its public key
+#[derive(Clone, Debug)]
+pub struct KeyPair {
+    secret_key: ExpandedSecretKey,
+    public_key: PublicKey,
+}
+
+impl KeyPair {
+    /// Samples a random secret key
+    pub fn random() -> Self {
+        let seed: [u8; 32] = rand::random();
+        Self::from_seed(&seed)
+    }
+
+    /// Derives a key pair from 32 bytes secret key
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        Self::from_expanded_secret_key(ExpandedSecretKey::from_seed(seed))
+    }
+
+    pub fn from_expanded_secret_key(secret_key: ExpandedSecretKey) -> Self {
+        let public_key = PublicKey::from_point(&(Point::generator() * &secret_key.scalar));
+        Self {
+            secret_key,
+            public_key,
+        }
+    }
+
+    pub fn secret_key(&self) -> &ExpandedSecretKey {
+        &self.secret_key
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+}
+
+/// Defines verification equation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationMode {
+    /// Checks `[8][S]B = [8]R + [8][k]A`
+    ///
+    /// It's the equation that RFC 8032 requires, and the only one that is consistent with
+    /// [batch verification](Signature::batch_verify).
+    Cofactored,
+    /// Checks `[S]B = R + [k]A`
+    ///
+    /// RFC 8032 permits it, but it rejects some signatures that are accepted in cofactored mode.
+    Cofactorless,
+}
+
+/// EdDSA signature `(R, S)`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    r: CompressedEdwardsY,
+    r_point: EdwardsPoint,
+    s: Scalar<Ed25519>,
+}
+
+impl Signature {
+    /// Signs a message
+    ///
+    /// Signing is deterministic: nonce is derived from the prefix of expanded secret key and
+    /// the message.
+    pub fn sign(key_pair: &KeyPair, message: &[u8]) -> Self {
+        let r = from_dalek_scalar(DalekScalar::from_hash(
+            Sha512::new()
+                .chain(key_pair.secret_key.prefix)
+                .chain(message),
+        ));
+        let big_r = Point::generator() * &r;
+        let k = compute_challenge(&big_r, &key_pair.public_key, message);
+        let s = r + k * &key_pair.secret_key.scalar;
+        Self::new(&big_r, s)
+    }
+
+    /// Constructs a signature from its parts
+    pub fn new(r: &Point<Ed25519>, s: Scalar<Ed25519>) -> Self {
+        let r_point = *r.as_raw().underlying_ref();
+        Self {
+            r: r_point.compress(),
+            r_point,
+            s,
+        }
+    }
+
+    /// Verifies the signature
+    pub fn verify(
+        &self,
+        public_key: &PublicKey,
+        message: &[u8],
+        mode: VerificationMode,
+    ) -> Result<(), EddsaError> {
+        let k = challenge(&self.r, &public_key.compressed, message);
+        // [S]B - [k]A - R
+        let diff = EdwardsPoint::vartime_double_scalar_mul_basepoint(
+            &-k,
+            &public_key.point,
+            to_dalek_scalar(&self.s),
+        ) - self.r_point;
+        let valid = match mode {
+            VerificationMode::Cofactored => diff.mul_by_cofactor().is_identity(),
+            VerificationMode::Cofactorless => diff.is_identity(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(EddsaError::InvalidSignature)
+        }
+    }
+
+    /// Verifies a batch of signatures at once, `signatures[i]` is verified against
+    /// `public_keys[i]` and `messages[i]`
+    ///
+    /// Signatures are checked as a random linear combination
+    /// `[8] Σ z_i ([S_i]B - R_i - [k_i]A_i) = O` computed via single multiscalar multiplication,
+    /// so batch verification always follows [cofactored](VerificationMode::Cofactored) equation.
+    /// If the batch is invalid, signatures are verified individually to find out which of them
+    /// failed.
+    ///
+    /// ## Panics
+    /// Panics if `public_keys`, `messages` and `signatures` have different lengths.
+    pub fn batch_verify(
+        public_keys: &[PublicKey],
+        messages: &[&[u8]],
+        signatures: &[Signature],
+    ) -> Result<(), BatchVerificationError> {
+        assert_eq!(
+            public_keys.len(),
+            signatures.len(),
+            "public_keys and signatures must have the same length"
+        );
+        assert_eq!(
+            messages.len(),
+            signatures.len(),
+            "messages and signatures must have the same length"
+        );
+
+        let mut scalars = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut points = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut basepoint_scalar = DalekScalar::zero();
+        for ((public_key, message), signature) in public_keys.iter().zip(messages).zip(signatures) {
+            let z = *to_dalek_scalar(&batch_verification_weight::<Ed25519>());
+            let k = challenge(&signature.r, &public_key.compressed, message);
+            basepoint_scalar += z * to_dalek_scalar(&signature.s);
+            scalars.push(-z);
+            points.push(signature.r_point);
+            scalars.push(-(z * k));
+            points.push(public_key.point);
+        }
+        scalars.push(basepoint_scalar);
+        points.push(curve25519_dalek::constants::ED25519_BASEPOINT_POINT);
+
+        if EdwardsPoint::vartime_multiscalar_mul(scalars, points)
+            .mul_by_cofactor()
+            .is_identity()
+        {
+            return Ok(());
+        }
+        BatchVerificationError::find_invalid_proofs(
+            public_keys.iter().zip(messages).zip(signatures).map(
+                |((public_key, message), signature)| {
+                    signature.verify(public_key, message, VerificationMode::Cofactored)
+                },
+            ),
+        )
+    }
+
+    /// Parses 64 bytes signature
+    ///
+    /// Returns error if `R` is not a canonical encoding of a curve point, or if `S` is not less
+    /// than group order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EddsaError> {
+        if bytes.len() != 64 {
+            return Err(EddsaError::MalformedSignature);
+        }
+        let (r, r_point) = decode_point(&bytes[..32]).ok_or(EddsaError::MalformedSignature)?;
+        let s_bytes = bytes[32..].try_into().expect("length is checked above");
+        let s = DalekScalar::from_canonical_bytes(s_bytes).ok_or(EddsaError::MalformedSignature)?;
+        Ok(Self {
+            r,
+            r_point,
+            s: from_dalek_scalar(s),
+        })
+    }
+
+    /// Serializes the signature as 64 bytes `R || S`
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(self.r.as_bytes());
+        bytes[32..].copy_from_slice(&self.s.to_bytes());
+        bytes
+    }
+
+    /// Encoded nonce point `R`
+    pub fn r_bytes(&self) -> [u8; 32] {
+        self.r.to_bytes()
+    }
+
+    pub fn s(&self) -> &Scalar<Ed25519> {
+        &self.s
+    }
+}
+
+/// Computes challenge `k = SHA-512(R || A || M) mod L`
+///
+/// Exposed for protocols that produce signature in a distributed manner.
+pub fn compute_challenge(
+    r: &Point<Ed25519>,
+    public_key: &PublicKey,
+    message: &[u8],
+) -> Scalar<Ed25519> {
+    let r = r.as_raw().underlying_ref().compress();
+    from_dalek_scalar(challenge(&r, &public_key.compressed, message))
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum EddsaError {
+    #[error("public key is not a valid point encoding")]
+    InvalidPublicKey,
+    #[error("signature is malformed")]
+    MalformedSignature,
+    #[error("signature is not valid")]
+    InvalidSignature,
+}
+
+fn challenge(
+    r: &CompressedEdwardsY,
+    public_key: &CompressedEdwardsY,
+    message: &[u8],
+) -> DalekScalar {
+    DalekScalar::from_hash(
+        Sha512::new()
+            .chain(r.as_bytes())
+            .chain(public_key.as_bytes())
+            .chain(message),
+    )
+}
+
+/// Decodes a point as specified in RFC 8032 (section 5.1.3), rejecting non-canonical encodings
+fn decode_point(bytes: &[u8]) -> Option<(CompressedEdwardsY, EdwardsPoint)> {
+    let compressed = CompressedEdwardsY::from_slice(bytes.get(..32)?);
+    if bytes.len() != 32 {
+        return None;
+    }
+    let point = compressed.decompress()?;
+    // Decompression accepts `y >= p` and `x = 0` with sign bit set, both are forbidden by RFC
+    if point.compress() != compressed {
+        return None;
+    }
+    Some((compressed, point))
+}
+
+fn to_dalek_scalar(scalar: &Scalar<Ed25519>) -> &DalekScalar {
+    scalar.as_raw().underlying_ref()
+}
+
+fn from_dalek_scalar(scalar: DalekScalar) -> Scalar<Ed25519> {
+    Scalar::from_raw(Ed25519Scalar::from_underlying(scalar))
+}
+
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::constants::EIGHT_TORSION;
+
+    use super::*;
+    use crate::arithmetic::*;
+
+    /// Test vectors from RFC 8032 (section 7.1): secret key, public key, message, signature
+    const TEST_VECTORS: &[(&str, &str, &str, &str)] = &[
+        // TEST 1
+        (
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        // TEST 2
+        (
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        // TEST 3
+        (
+            "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac\
+             18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+        // TEST 1024
+        (
+            "f5e5767cf153319517630f226876b86c8160cc583bc013744c6bf255f5cc0ee5",
+            "278117fc144c72340f67d0f2316e8386ceffbf2b2428c9c51fef7c597f1d426e",
+            "08b8b2b733424243760fe426a4b54908632110a66c2f6591eabd3345e3e4eb98\
+             fa6e264bf09efe12ee50f8f54e9f77b1e355f6c50544e23fb1433ddf73be84d8\
+             79de7c0046dc4996d9e773f4bc9efe5738829adb26c81b37c93a1b270b20329d\
+             658675fc6ea534e0810a4432826bf58c941efb65d57a338bbd2e26640f89ffbc\
+             1a858efcb8550ee3a5e1998bd177e93a7363c344fe6b199ee5d02e82d522c4fe\
+             ba15452f80288a821a579116ec6dad2b3b310da903401aa62100ab5d1a36553e\
+             06203b33890cc9b832f79ef80560ccb9a39ce767967ed628c6ad573cb116dbef\
+             efd75499da96bd68a8a97b928a8bbc103b6621fcde2beca1231d206be6cd9ec7\
+             aff6f6c94fcd7204ed3455c68c83f4a41da4af2b74ef5c53f1d8ac70bdcb7ed1\
+             85ce81bd84359d44254d95629e9855a94a7c1958d1f8ada5d0532ed8a5aa3fb2\
+             d17ba70eb6248e594e1a2297acbbb39d502f1a8c6eb6f1ce22b3de1a1f40cc24\
+             554119a831a9aad6079cad88425de6bde1a9187ebb6092cf67bf2b13fd65f270\
+             88d78b7e883c8759d2c4f5c65adb7553878ad575f9fad878e80a0c9ba63bcbcc\
+             2732e69485bbc9c90bfbd62481d9089beccf80cfe2df16a2cf65bd92dd597b07\
+             07e0917af48bbb75fed413d238f5555a7a569d80c3414a8d0859dc65a46128ba\
+             b27af87a71314f318c782b23ebfe808b82b0ce26401d2e22f04d83d1255dc51a\
+             ddd3b75a2b1ae0784504df543af8969be3ea7082ff7fc9888c144da2af58429e\
+             c96031dbcad3dad9af0dcbaaaf268cb8fcffead94f3c7ca495e056a9b47acdb7\
+             51fb73e666c6c655ade8297297d07ad1ba5e43f1bca32301651339e22904cc8c\
+             42f58c30c04aafdb038dda0847dd988dcda6f3bfd15c4b4c4525004aa06eeff8\
+             ca61783aacec57fb3d1f92b0fe2fd1a85f6724517b65e614ad6808d6f6ee34df\
+             f7310fdc82aebfd904b01e1dc54b2927094b2db68d6f903b68401adebf5a7e08\
+             d78ff4ef5d63653a65040cf9bfd4aca7984a74d37145986780fc0b16ac451649\
+             de6188a7dbdf191f64b5fc5e2ab47b57f7f7276cd419c17a3ca8e1b939ae49e4\
+             88acba6b965610b5480109c8b17b80e1b7b750dfc7598d5d5011fd2dcc5600a3\
+             2ef5b52a1ecc820e308aa342721aac0943bf6686b64b2579376504ccc493d97e\
+             6aed3fb0f9cd71a43dd497f01f17c0e2cb3797aa2a2f256656168e6c496afc5f\
+             b93246f6b1116398a346f1a641f3b041e989f7914f90cc2c7fff357876e506b5\
+             0d334ba77c225bc307ba537152f3f1610e4eafe595f6d9d90d11faa933a15ef1\
+             369546868a7f3a45a96768d40fd9d03412c091c6315cf4fde7cb68606937380d\
+             b2eaaa707b4c4185c32eddcdd306705e4dc1ffc872eeee475a64dfac86aba41c\
+             0618983f8741c5ef68d3a101e8a3b8cac60c905c15fc910840b94c00a0b9d0",
+            "0aab4c900501b3e24d7cdf4663326a3a87df5e4843b2cbdb67cbf6e460fec350\
+             aa5371b1508f9f4528ecea23c436d94b5e8fcd4f681e30a6ac00a9704a188a03",
+        ),
+        // TEST SHA(abc)
+        (
+            "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+            "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b589\
+             09351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+        ),
+    ];
+
+    fn decode(hex_str: &str) -> Vec<u8> {
+        let hex_str: String = hex_str.split_whitespace().collect();
+        hex::decode(hex_str).unwrap()
+    }
+
+    #[test]
+    fn rfc8032_test_vectors() {
+        for (secret_key, public_key, message, signature) in TEST_VECTORS {
+            let seed = decode(secret_key).try_into().unwrap();
+            let message = decode(message);
+            let key_pair = KeyPair::from_seed(&seed);
+            assert_eq!(key_pair.public_key().to_bytes()[..], decode(public_key)[..]);
+
+            let sig = Signature::sign(&key_pair, &message);
+            assert_eq!(sig.to_bytes()[..], decode(signature)[..]);
+
+            let public_key = PublicKey::from_bytes(&decode(public_key)).unwrap();
+            let sig = Signature::from_bytes(&decode(signature)).unwrap();
+            for mode in [VerificationMode::Cofactored, VerificationMode::Cofactorless] {
+                assert!(sig.verify(&public_key, &message, mode).is_ok());
+                assert_eq!(
+                    sig.verify(&public_key, b"another message", mode),
+                    Err(EddsaError::InvalidSignature)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn public_key_is_compatible_with_point_serialization() {
+        let key_pair = KeyPair::random();
+        let point = key_pair.public_key().to_point().unwrap();
+        assert_eq!(point, Point::generator() * key_pair.secret_key().scalar());
+        assert_eq!(
+            key_pair.public_key().to_bytes()[..],
+            point.to_bytes(true)[..]
+        );
+        assert_eq!(
+            Point::<Ed25519>::from_bytes(&key_pair.public_key().to_bytes()).unwrap(),
+            point
+        );
+    }
+
+    #[test]
+    fn verification_modes_differ_on_small_order_components() {
+        let key_pair = KeyPair::random();
+        let message = b"message";
+
+        // Nonce point R with a small order component is accepted only in cofactored mode
+        let r = Scalar::<Ed25519>::random();
+        let r_point = *(Point::generator() * &r).as_raw().underlying_ref() + EIGHT_TORSION[1];
+        let r_compressed = r_point.compress();
+        let k = from_dalek_scalar(challenge(
+            &r_compressed,
+            &key_pair.public_key().compressed,
+            message,
+        ));
+        let s = r + k * key_pair.secret_key().scalar();
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(r_compressed.as_bytes());
+        bytes[32..].copy_from_slice(&s.to_bytes());
+        let signature = Signature::from_bytes(&bytes).unwrap();
+
+        assert!(signature
+            .verify(key_pair.public_key(), message, VerificationMode::Cofactored)
+            .is_ok());
+        assert_eq!(
+            signature.verify(
+                key_pair.public_key(),
+                message,
+                VerificationMode::Cofactorless
+            ),
+            Err(EddsaError::InvalidSignature)
+        );
+        assert!(Signature::batch_verify(
+            &[key_pair.public_key().clone()],
+            &[message],
+            &[signature]
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn non_canonical_encodings_are_rejected() {
+        let key_pair = KeyPair::random();
+        let signature = Signature::sign(&key_pair, b"message");
+
+        // S + L
+        let s_plus_l = signature.s().to_bigint() + Scalar::<Ed25519>::group_order();
+        let mut s_bytes = s_plus_l.to_bytes();
+        s_bytes.reverse();
+        s_bytes.resize(32, 0);
+        let mut bytes = signature.to_bytes();
+        bytes[32..].copy_from_slice(&s_bytes);
+        assert_eq!(
+            Signature::from_bytes(&bytes),
+            Err(EddsaError::MalformedSignature)
+        );
+
+        // y = p + 1 is a non-canonical encoding of the point with y = 1
+        let mut y_plus_p = [0xff; 32];
+        y_plus_p[0] = 0xee;
+        y_plus_p[31] = 0x7f;
+        assert!(CompressedEdwardsY(y_plus_p).decompress().is_some());
+        assert_eq!(
+            PublicKey::from_bytes(&y_plus_p),
+            Err(EddsaError::InvalidPublicKey)
+        );
+    }
+
+    #[test]
+    fn batch_verify() {
+        let key_pairs: Vec<_> = (0..10).map(|_| KeyPair::random()).collect();
+        let public_keys: Vec<_> = key_pairs.iter().map(|k| k.public_key().clone()).collect();
+        let messages: Vec<Vec<u8>> = (0..10u8).map(|i| vec![i; i as usize]).collect();
+        let messages: Vec<&[u8]> = messages.iter().map(|m| m.as_slice()).collect();
+        let mut signatures: Vec<_> = key_pairs
+            .iter()
+            .zip(&messages)
+            .map(|(key_pair, message)| Signature::sign(key_pair, message))
+            .collect();
+        assert!(Signature::batch_verify(&[], &[], &[]).is_ok());
+        assert!(Signature::batch_verify(&public_keys, &messages, &signatures).is_ok());
+
+        signatures[2].s = Scalar::random();
+        signatures.swap(5, 6);
+        assert_eq!(
+            Signature::batch_verify(&public_keys, &messages, &signatures),
+            Err(BatchVerificationError {
+                invalid_proofs: vec![2, 5, 6]
+            })
+        );
+    }
+}
@@ -0,0 +1,1002 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! FROST: Flexible Round-Optimized Schnorr Threshold signatures as specified in [RFC 9591]
+//!
+//! Secret key is shared among `n` parties via [VerifiableSS]. Any `t+1` parties can sign a message
+//! in two rounds:
+//! 1. Every signer [commits](KeyShare::commit) to a pair of nonces and sends [SigningCommitments]
+//!    to the coordinator. Coordinator collects commitments and the message into [SigningPackage].
+//! 2. Every signer [signs](KeyShare::sign) the package producing [SignatureShare]. Coordinator
+//!    [aggregates](aggregate) signature shares into a Schnorr [Signature].
+//!
+//! Signature shares can be verified individually against public shares derived from VSS
+//! commitments. Aggregation verifies the resulting signature, and if it's invalid, identifies
+//! signers who sent invalid shares.
+//!
+//! Supported ciphersuites are `FROST(secp256k1, SHA-256)`, `FROST(Ed25519, SHA-512)` (signatures
+//! are valid RFC 8032 Ed25519 signatures) and `FROST(ristretto255, SHA-512)`.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+//! use curv::cryptographic_primitives::signatures::frost::{aggregate, KeyShare, SigningPackage};
+//! use curv::elliptic::curves::{Scalar, Secp256k1};
+//! use sha2::Sha256;
+//!
+//! let secret_key = Scalar::<Secp256k1>::random();
+//! let (vss, shares) = VerifiableSS::<Secp256k1, Sha256>::share(1, 3, &secret_key);
+//! let key_shares: Vec<_> = (1..=3)
+//!     .map(|i| KeyShare::new(i, shares[usize::from(i) - 1].clone(), vss.clone()).unwrap())
+//!     .collect();
+//! let signers = [&key_shares[0], &key_shares[2]];
+//!
+//! // Round 1: signers commit to nonces
+//! let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(|s| s.commit()).unzip();
+//! let package = SigningPackage::new(commitments, b"hello".to_vec()).unwrap();
+//!
+//! // Round 2: signers produce signature shares
+//! let signature_shares: Vec<_> = signers
+//!     .iter()
+//!     .zip(nonces)
+//!     .map(|(s, nonces)| s.sign(nonces, &package).unwrap())
+//!     .collect();
+//!
+//! let signature = aggregate(&vss, &package, &signature_shares).unwrap();
+//! assert!(signature.verify(&vss.commitments[0], b"hello").is_ok());
+//! ```
+//!
+//! [RFC 9591]: https://www.rfc-editor.org/rfc/rfc9591.html
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Sha512};
+use thiserror::Error;
+
+use crate::arithmetic::*;
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::elliptic::curves::{Curve, Ed25519, Point, Ristretto, Scalar, Secp256k1};
+
+/// Curve with FROST ciphersuite defined in RFC 9591
+///
+/// Ciphersuite determines hash functions `H1`-`H5`. Elements and scalars are serialized via
+/// [Point::to_bytes] (compressed) and [Scalar::to_bytes] respectively, which match the
+/// ciphersuites encodings.
+pub trait FrostCurve: Curve {
+    /// Context string of the ciphersuite, e.g. `FROST-secp256k1-SHA256-v1`
+    const CONTEXT_STRING: &'static [u8];
+
+    /// Hashes a message to a scalar using domain separation `tag` (`H1`, `H3`)
+    fn hash_to_scalar(tag: &[u8], message: &[u8]) -> Scalar<Self>;
+
+    /// Hashes a message to the challenge scalar (`H2`)
+    fn hash_challenge(message: &[u8]) -> Scalar<Self> {
+        Self::hash_to_scalar(b"chal", message)
+    }
+
+    /// Hashes a message using domain separation `tag` (`H4`, `H5`)
+    fn hash(tag: &[u8], message: &[u8]) -> Vec<u8>;
+}
+
+impl FrostCurve for Secp256k1 {
+    const CONTEXT_STRING: &'static [u8] = b"FROST-secp256k1-SHA256-v1";
+
+    fn hash_to_scalar(tag: &[u8], message: &[u8]) -> Scalar<Self> {
+        let dst = [Self::CONTEXT_STRING, tag].concat();
+        Scalar::hash_from_bytes::<Sha256>(&dst, message)
+    }
+
+    fn hash(tag: &[u8], message: &[u8]) -> Vec<u8> {
+        Sha256::new()
+            .chain(Self::CONTEXT_STRING)
+            .chain(tag)
+            .chain(message)
+            .finalize()
+            .to_vec()
+    }
+}
+
+impl FrostCurve for Ed25519 {
+    const CONTEXT_STRING: &'static [u8] = b"FROST-ED25519-SHA512-v1";
+
+    fn hash_to_scalar(tag: &[u8], message: &[u8]) -> Scalar<Self> {
+        scalar_from_wide_le(&Self::hash(tag, message))
+    }
+
+    /// Challenge is computed as in RFC 8032 (without context string), so resulting signatures are
+    /// valid Ed25519 signatures
+    fn hash_challenge(message: &[u8]) -> Scalar<Self> {
+        scalar_from_wide_le(&Sha512::digest(message))
+    }
+
+    fn hash(tag: &[u8], message: &[u8]) -> Vec<u8> {
+        Sha512::new()
+            .chain(Self::CONTEXT_STRING)
+            .chain(tag)
+            .chain(message)
+            .finalize()
+            .to_vec()
+    }
+}
+
+impl FrostCurve for Ristretto {
+    const CONTEXT_STRING: &'static [u8] = b"FROST-RISTRETTO255-SHA512-v1";
+
+    fn hash_to_scalar(tag: &[u8], message: &[u8]) -> Scalar<Self> {
+        scalar_from_wide_le(&Self::hash(tag, message))
+    }
+
+    fn hash(tag: &[u8], message: &[u8]) -> Vec<u8> {
+        Sha512::new()
+            .chain(Self::CONTEXT_STRING)
+            .chain(tag)
+            .chain(message)
+            .finalize()
+            .to_vec()
+    }
+}
+
+/// Share of secret key held by party `index`
+#[derive(Clone)]
+pub struct KeyShare<E: FrostCurve, H: Digest + Clone> {
+    index: u16,
+    secret_share: Scalar<E>,
+    vss: VerifiableSS<E, H>,
+}
+
+/// Secret nonces generated by a signer in the first round
+///
+/// Nonces must be used to sign only once, so they're consumed by [KeyShare::sign] and can't be
+/// cloned.
+pub struct SigningNonces<E: FrostCurve> {
+    hiding: Scalar<E>,
+    binding: Scalar<E>,
+    commitments: SigningCommitments<E>,
+}
+
+/// Commitments to signer's nonces, sent to the coordinator in the first round
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SigningCommitments<E: FrostCurve> {
+    /// Index of the signer
+    pub index: u16,
+    /// Commitment to hiding nonce `D_i = d_i G`
+    pub hiding: Point<E>,
+    /// Commitment to binding nonce `E_i = e_i G`
+    pub binding: Point<E>,
+}
+
+/// Message along with commitments of all signers
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SigningPackage<E: FrostCurve> {
+    commitments: Vec<SigningCommitments<E>>,
+    message: Vec<u8>,
+}
+
+/// Signer's share of the signature, sent to the coordinator in the second round
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SignatureShare<E: FrostCurve> {
+    /// Index of the signer
+    pub index: u16,
+    /// Signature share `z_i`
+    pub share: Scalar<E>,
+}
+
+/// Schnorr signature `(R, z)`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Signature<E: FrostCurve> {
+    pub r: Point<E>,
+    pub z: Scalar<E>,
+}
+
+impl<E: FrostCurve, H: Digest + Clone> KeyShare<E, H> {
+    /// Constructs a key share of party `index` (`1..=n`)
+    ///
+    /// Returns error if the share doesn't match VSS commitments.
+    pub fn new(
+        index: u16,
+        secret_share: Scalar<E>,
+        vss: VerifiableSS<E, H>,
+    ) -> Result<Self, FrostError> {
+        if index == 0 || index > vss.parameters.share_count {
+            return Err(FrostError::InvalidIndex(index));
+        }
+        vss.validate_share(&secret_share, index)
+            .or(Err(FrostError::InvalidShare))?;
+        Ok(Self {
+            index,
+            secret_share,
+            vss,
+        })
+    }
+
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    pub fn secret_share(&self) -> &Scalar<E> {
+        &self.secret_share
+    }
+
+    pub fn vss(&self) -> &VerifiableSS<E, H> {
+        &self.vss
+    }
+
+    /// Shared public key
+    pub fn group_public_key(&self) -> &Point<E> {
+        &self.vss.commitments[0]
+    }
+
+    /// Round 1: generates nonces and commitments to them
+    pub fn commit(&self) -> (SigningNonces<E>, SigningCommitments<E>) {
+        let hiding_randomness: [u8; 32] = rand::random();
+        let binding_randomness: [u8; 32] = rand::random();
+        self.commit_with_randomness(&hiding_randomness, &binding_randomness)
+    }
+
+    fn commit_with_randomness(
+        &self,
+        hiding_randomness: &[u8; 32],
+        binding_randomness: &[u8; 32],
+    ) -> (SigningNonces<E>, SigningCommitments<E>) {
+        let hiding = nonce_generate(hiding_randomness, &self.secret_share);
+        let binding = nonce_generate(binding_randomness, &self.secret_share);
+        let commitments = SigningCommitments {
+            index: self.index,
+            hiding: Point::generator() * &hiding,
+            binding: Point::generator() * &binding,
+        };
+        let nonces = SigningNonces {
+            hiding,
+            binding,
+            commitments: commitments.clone(),
+        };
+        (nonces, commitments)
+    }
+
+    /// Round 2: signs the package producing a signature share
+    ///
+    /// Returns error if the package doesn't contain signer's commitments generated along with
+    /// `nonces`, or if there are less than `t+1` signers.
+    pub fn sign(
+        &self,
+        nonces: SigningNonces<E>,
+        package: &SigningPackage<E>,
+    ) -> Result<SignatureShare<E>, FrostError> {
+        package.validate(&self.vss)?;
+        let position = package
+            .position(self.index)
+            .filter(|&j| package.commitments[j] == nonces.commitments)
+            .ok_or(FrostError::MissingCommitments(self.index))?;
+
+        let context = SigningContext::new(self.group_public_key(), package);
+        let lambda = context.lagrange_coefficient(position);
+        let share = nonces.hiding
+            + nonces.binding * &context.binding_factors[position]
+            + lambda * &self.secret_share * &context.challenge;
+        Ok(SignatureShare {
+            index: self.index,
+            share,
+        })
+    }
+}
+
+impl<E: FrostCurve> SigningPackage<E> {
+    /// Constructs a signing package
+    ///
+    /// Commitments are sorted by signers indexes. Returns error if any index is zero or
+    /// appears more than once, or if any commitment is the point at infinity.
+    pub fn new(
+        mut commitments: Vec<SigningCommitments<E>>,
+        message: Vec<u8>,
+    ) -> Result<Self, FrostError> {
+        commitments.sort_by_key(|c| c.index);
+        for (i, c) in commitments.iter().enumerate() {
+            if c.index == 0 {
+                return Err(FrostError::InvalidIndex(0));
+            }
+            if i > 0 && commitments[i - 1].index == c.index {
+                return Err(FrostError::DuplicateIndex(c.index));
+            }
+            if c.hiding.is_zero() || c.binding.is_zero() {
+                return Err(FrostError::InvalidCommitments(c.index));
+            }
+        }
+        Ok(Self {
+            commitments,
+            message,
+        })
+    }
+
+    /// Commitments of signers sorted by their indexes
+    pub fn commitments(&self) -> &[SigningCommitments<E>] {
+        &self.commitments
+    }
+
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    fn position(&self, index: u16) -> Option<usize> {
+        self.commitments
+            .binary_search_by_key(&index, |c| c.index)
+            .ok()
+    }
+
+    fn validate<H: Digest + Clone>(&self, vss: &VerifiableSS<E, H>) -> Result<(), FrostError> {
+        let required = usize::from(vss.reconstruct_limit());
+        if self.commitments.len() < required {
+            return Err(FrostError::NotEnoughSigners {
+                required,
+                received: self.commitments.len(),
+            });
+        }
+        match self
+            .commitments
+            .iter()
+            .find(|c| c.index > vss.parameters.share_count)
+        {
+            Some(c) => Err(FrostError::InvalidIndex(c.index)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<E: FrostCurve> SignatureShare<E> {
+    /// Verifies signature share against public share of the signer derived from VSS commitments
+    pub fn verify<H: Digest + Clone>(
+        &self,
+        vss: &VerifiableSS<E, H>,
+        package: &SigningPackage<E>,
+    ) -> Result<(), FrostError> {
+        package.validate(vss)?;
+        let position = package
+            .position(self.index)
+            .ok_or(FrostError::MissingCommitments(self.index))?;
+        let context = SigningContext::new(&vss.commitments[0], package);
+        self.verify_with_context(vss, package, &context, position)
+    }
+
+    fn verify_with_context<H: Digest + Clone>(
+        &self,
+        vss: &VerifiableSS<E, H>,
+        package: &SigningPackage<E>,
+        context: &SigningContext<E>,
+        position: usize,
+    ) -> Result<(), FrostError> {
+        let commitments = &package.commitments[position];
+        let commitment_share =
+            &commitments.hiding + &commitments.binding * &context.binding_factors[position];
+        let public_share = vss.get_point_commitment(self.index);
+        let lambda = context.lagrange_coefficient(position);
+        if Point::generator() * &self.share
+            == commitment_share + public_share * (lambda * &context.challenge)
+        {
+            Ok(())
+        } else {
+            Err(FrostError::InvalidSignatureShares(vec![self.index]))
+        }
+    }
+}
+
+/// Aggregates signature shares into a signature
+///
+/// Expects exactly one share from every signer in the package. Resulting signature is verified
+/// against the group public key. If it's invalid, every share is verified to identify misbehaving
+/// signers, and their indexes are returned in [FrostError::InvalidSignatureShares].
+pub fn aggregate<E: FrostCurve, H: Digest + Clone>(
+    vss: &VerifiableSS<E, H>,
+    package: &SigningPackage<E>,
+    signature_shares: &[SignatureShare<E>],
+) -> Result<Signature<E>, FrostError> {
+    package.validate(vss)?;
+    let shares = package
+        .commitments
+        .iter()
+        .map(|c| {
+            signature_shares
+                .iter()
+                .find(|s| s.index == c.index)
+                .ok_or(FrostError::MissingSignatureShare(c.index))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(s) = signature_shares
+        .iter()
+        .find(|s| package.position(s.index).is_none())
+    {
+        return Err(FrostError::MissingCommitments(s.index));
+    }
+    if signature_shares.len() != shares.len() {
+        let duplicated = (1..signature_shares.len())
+            .find(|&i| {
+                signature_shares[..i]
+                    .iter()
+                    .any(|s| s.index == signature_shares[i].index)
+            })
+            .map(|i| signature_shares[i].index)
+            .expect("some share must be duplicated");
+        return Err(FrostError::DuplicateIndex(duplicated));
+    }
+
+    let group_public_key = &vss.commitments[0];
+    let context = SigningContext::new(group_public_key, package);
+    let signature = Signature {
+        r: context.group_commitment.clone(),
+        z: shares.iter().map(|s| &s.share).sum(),
+    };
+    if signature.verify(group_public_key, &package.message).is_ok() {
+        return Ok(signature);
+    }
+
+    let culprits: Vec<u16> = shares
+        .iter()
+        .enumerate()
+        .filter(|(position, share)| {
+            share
+                .verify_with_context(vss, package, &context, *position)
+                .is_err()
+        })
+        .map(|(_, share)| share.index)
+        .collect();
+    if culprits.is_empty() {
+        Err(FrostError::InvalidSignature)
+    } else {
+        Err(FrostError::InvalidSignatureShares(culprits))
+    }
+}
+
+impl<E: FrostCurve> Signature<E> {
+    /// Verifies the signature: checks `z G = R + c PK`
+    pub fn verify(&self, public_key: &Point<E>, message: &[u8]) -> Result<(), FrostError> {
+        let challenge = compute_challenge(&self.r, public_key, message);
+        if Point::generator() * &self.z == &self.r + public_key * challenge {
+            Ok(())
+        } else {
+            Err(FrostError::InvalidSignature)
+        }
+    }
+
+    /// Parses signature `R || z`
+    ///
+    /// Returns error if encoding of either `R` or `z` is not canonical, or if `R` is zero.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FrostError> {
+        let point_len = Point::<E>::generator().to_bytes(true).len();
+        if bytes.len() <= point_len {
+            return Err(FrostError::MalformedSignature);
+        }
+        let (r_bytes, z_bytes) = bytes.split_at(point_len);
+        let r = Point::<E>::from_bytes(r_bytes).or(Err(FrostError::MalformedSignature))?;
+        let z = Scalar::<E>::from_bytes(z_bytes).or(Err(FrostError::MalformedSignature))?;
+        if r.is_zero() || r.to_bytes(true)[..] != *r_bytes || z.to_bytes()[..] != *z_bytes {
+            return Err(FrostError::MalformedSignature);
+        }
+        Ok(Self { r, z })
+    }
+
+    /// Serializes signature as `R || z`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.r.to_bytes(true).to_vec();
+        bytes.extend_from_slice(&self.z.to_bytes());
+        bytes
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum FrostError {
+    #[error("party index {0} is out of range")]
+    InvalidIndex(u16),
+    #[error("party {0} appears more than once")]
+    DuplicateIndex(u16),
+    #[error("secret share doesn't match VSS commitments")]
+    InvalidShare,
+    #[error("expected at least {required} signers, received {received}")]
+    NotEnoughSigners { required: usize, received: usize },
+    #[error("signing package doesn't contain commitments of party {0}")]
+    MissingCommitments(u16),
+    #[error("commitments of party {0} contain point at infinity")]
+    InvalidCommitments(u16),
+    #[error("signature share of party {0} is missing")]
+    MissingSignatureShare(u16),
+    #[error("signature shares of parties {0:?} are not valid")]
+    InvalidSignatureShares(Vec<u16>),
+    #[error("signature is not valid")]
+    InvalidSignature,
+    #[error("signature is malformed")]
+    MalformedSignature,
+}
+
+/// Values derived from signing package that are needed to produce and verify signature shares
+struct SigningContext<E: FrostCurve> {
+    xs: Vec<Scalar<E>>,
+    binding_factors: Vec<Scalar<E>>,
+    group_commitment: Point<E>,
+    challenge: Scalar<E>,
+}
+
+impl<E: FrostCurve> SigningContext<E> {
+    fn new(group_public_key: &Point<E>, package: &SigningPackage<E>) -> Self {
+        let encoded_commitments: Vec<u8> = package
+            .commitments
+            .iter()
+            .flat_map(|c| {
+                let mut bytes = Scalar::<E>::from(c.index).to_bytes().to_vec();
+                bytes.extend_from_slice(&c.hiding.to_bytes(true));
+                bytes.extend_from_slice(&c.binding.to_bytes(true));
+                bytes
+            })
+            .collect();
+        let mut rho_input_prefix = group_public_key.to_bytes(true).to_vec();
+        rho_input_prefix.extend(E::hash(b"msg", &package.message));
+        rho_input_prefix.extend(E::hash(b"com", &encoded_commitments));
+
+        let xs: Vec<Scalar<E>> = package
+            .commitments
+            .iter()
+            .map(|c| Scalar::from(c.index))
+            .collect();
+        let binding_factors: Vec<Scalar<E>> = xs
+            .iter()
+            .map(|x| {
+                let mut rho_input = rho_input_prefix.clone();
+                rho_input.extend_from_slice(&x.to_bytes());
+                E::hash_to_scalar(b"rho", &rho_input)
+            })
+            .collect();
+        let group_commitment = package
+            .commitments
+            .iter()
+            .zip(&binding_factors)
+            .map(|(c, rho)| &c.hiding + &c.binding * rho)
+            .sum();
+        let challenge = compute_challenge(&group_commitment, group_public_key, &package.message);
+        Self {
+            xs,
+            binding_factors,
+            group_commitment,
+            challenge,
+        }
+    }
+
+    fn lagrange_coefficient(&self, position: usize) -> Scalar<E> {
+        let j = u16::try_from(position).expect("number of signers fits into u16");
+        Polynomial::lagrange_basis(&Scalar::zero(), j, &self.xs)
+    }
+}
+
+fn compute_challenge<E: FrostCurve>(
+    group_commitment: &Point<E>,
+    group_public_key: &Point<E>,
+    message: &[u8],
+) -> Scalar<E> {
+    let mut challenge_input = group_commitment.to_bytes(true).to_vec();
+    challenge_input.extend_from_slice(&group_public_key.to_bytes(true));
+    challenge_input.extend_from_slice(message);
+    E::hash_challenge(&challenge_input)
+}
+
+fn nonce_generate<E: FrostCurve>(random_bytes: &[u8; 32], secret: &Scalar<E>) -> Scalar<E> {
+    let mut input = random_bytes.to_vec();
+    input.extend_from_slice(&secret.to_bytes());
+    E::hash_to_scalar(b"nonce", &input)
+}
+
+/// Interprets bytes as little-endian integer and reduces it modulo group order
+fn scalar_from_wide_le<E: Curve>(bytes: &[u8]) -> Scalar<E> {
+    let mut bytes = bytes.to_vec();
+    bytes.reverse();
+    Scalar::from_bigint(&BigInt::from_bytes(&bytes).modulus(Scalar::<E>::group_order()))
+}
+
+impl<E: FrostCurve> fmt::Debug for SigningNonces<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        f.debug_struct("SigningNonces")
+            .field("commitments", &self.commitments)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E: FrostCurve, H: Digest + Clone> fmt::Debug for KeyShare<E, H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(f, "KeyShare {{ index: {}, .. }}", self.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use super::*;
+
+    use crate::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+    use crate::cryptographic_primitives::secret_sharing::feldman_vss::ShamirSecretSharing;
+    use crate::cryptographic_primitives::signatures::eddsa;
+
+    struct Signer {
+        index: u16,
+        hiding_nonce_randomness: &'static str,
+        binding_nonce_randomness: &'static str,
+        hiding_nonce: &'static str,
+        binding_nonce: &'static str,
+        binding_factor: &'static str,
+        sig_share: &'static str,
+    }
+
+    /// Test vectors from RFC 9591, Appendix E: `MAX_PARTICIPANTS = 3`, `MIN_PARTICIPANTS = 2`,
+    /// participants 1 and 3 sign message `"test"`
+    struct TestVector {
+        group_secret_key: &'static str,
+        group_public_key: &'static str,
+        share_polynomial_coefficient: &'static str,
+        participant_shares: [&'static str; 3],
+        signers: [Signer; 2],
+        signature: &'static str,
+    }
+
+    fn decode_scalar<E: Curve>(hex: &str) -> Scalar<E> {
+        Scalar::from_bytes(&hex::decode(hex).unwrap()).unwrap()
+    }
+
+    fn check_test_vector<E: FrostCurve>(vector: &TestVector) -> Signature<E> {
+        let message = b"test";
+        let secret_key = decode_scalar::<E>(vector.group_secret_key);
+        let coefficient = decode_scalar::<E>(vector.share_polynomial_coefficient);
+        let vss = VerifiableSS::<E, Sha256> {
+            parameters: ShamirSecretSharing {
+                threshold: 1,
+                share_count: 3,
+            },
+            commitments: vec![
+                Point::generator() * &secret_key,
+                Point::generator() * &coefficient,
+            ],
+            proof: DLogProof::prove(&secret_key),
+        };
+        assert_eq!(
+            hex::encode(&*vss.commitments[0].to_bytes(true)),
+            vector.group_public_key
+        );
+        let key_shares: Vec<_> = (1..=3)
+            .zip(&vector.participant_shares)
+            .map(|(i, share)| KeyShare::new(i, decode_scalar(share), vss.clone()).unwrap())
+            .collect();
+
+        let mut nonces = vec![];
+        let mut commitments = vec![];
+        for signer in &vector.signers {
+            let key_share = &key_shares[usize::from(signer.index) - 1];
+            let (n, c) = key_share.commit_with_randomness(
+                &hex::decode(signer.hiding_nonce_randomness)
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+                &hex::decode(signer.binding_nonce_randomness)
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+            );
+            assert_eq!(n.hiding, decode_scalar(signer.hiding_nonce));
+            assert_eq!(n.binding, decode_scalar(signer.binding_nonce));
+            nonces.push(n);
+            commitments.push(c);
+        }
+        let package = SigningPackage::new(commitments, message.to_vec()).unwrap();
+        let context = SigningContext::new(&vss.commitments[0], &package);
+        for (signer, binding_factor) in vector.signers.iter().zip(&context.binding_factors) {
+            assert_eq!(binding_factor, &decode_scalar(signer.binding_factor));
+        }
+
+        let signature_shares: Vec<_> = vector
+            .signers
+            .iter()
+            .zip(nonces)
+            .map(|(signer, nonces)| {
+                let key_share = &key_shares[usize::from(signer.index) - 1];
+                let share = key_share.sign(nonces, &package).unwrap();
+                assert_eq!(share.share, decode_scalar(signer.sig_share));
+                assert!(share.verify(&vss, &package).is_ok());
+                share
+            })
+            .collect();
+
+        let signature = aggregate(&vss, &package, &signature_shares).unwrap();
+        assert_eq!(hex::encode(signature.to_bytes()), vector.signature);
+        assert_eq!(
+            Signature::from_bytes(&hex::decode(vector.signature).unwrap()).unwrap(),
+            signature
+        );
+        assert!(signature.verify(&vss.commitments[0], message).is_ok());
+        signature
+    }
+
+    #[test]
+    fn rfc9591_ed25519() {
+        let vector = TestVector {
+            group_secret_key: "7b1c33d3f5291d85de664833beb1ad469f7fb6025a0ec78b3a790c6e13a98304",
+            group_public_key: "15d21ccd7ee42959562fc8aa63224c8851fb3ec85a3faf66040d380fb9738673",
+            share_polynomial_coefficient:
+                "178199860edd8c62f5212ee91eff1295d0d670ab4ed4506866bae57e7030b204",
+            participant_shares: [
+                "929dcc590407aae7d388761cddb0c0db6f5627aea8e217f4a033f2ec83d93509",
+                "a91e66e012e4364ac9aaa405fcafd370402d9859f7b6685c07eed76bf409e80d",
+                "d3cb090a075eb154e82fdb4b3cb507f110040905468bb9c46da8bdea643a9a02",
+            ],
+            signers: [
+                Signer {
+                    index: 1,
+                    hiding_nonce_randomness:
+                        "06894e04ee4aceec8619d5f6a0a180e2f47194d2ac306cba586b555e7c48d765",
+                    binding_nonce_randomness:
+                        "40d6f879ff22e22409f7d808fed81f37118e7d3e4af71c0f44c60207553bcbce",
+                    hiding_nonce:
+                        "ef6599dea4010581a72b3018c37c29a4341d7cab0773e8687ca74dcf14009701",
+                    binding_nonce:
+                        "2baadfa0c69aa60d517ad4751de372a73f9d89cfc39026601f18458cdec12605",
+                    binding_factor:
+                        "ff960a65374e216a0918729b153466016664fa980d409bc3f308daa7acb30d0d",
+                    sig_share: "60997f0142e43e8005027fe5ab7447dac00d22c2d7ddd9571a02613ba7d81c08",
+                },
+                Signer {
+                    index: 3,
+                    hiding_nonce_randomness:
+                        "fa5d950626782aade9e33fa781376d4888c2d1de7c37518bc248eb818ed0cdde",
+                    binding_nonce_randomness:
+                        "7459a4d14ded0e365b085271be8dc6600d5b88f3978a2174297ffa32001a5afc",
+                    hiding_nonce:
+                        "80f8d9a4b8f9366e1a0b618107c907cd3ee29fa9bb40b4691cc1bde696240005",
+                    binding_nonce:
+                        "2610b664a5a187b4855e87d2ff485bebdf043dc2f161fcd4854cd01dc0276404",
+                    binding_factor:
+                        "279d48ec56f16d234c09ea62f3d02ab776ee38e03f66b20f939f1316e13df10f",
+                    sig_share: "79390e78bc59699c7af831f8f5fb478ec871a85f561a8641b5670ac4443f720f",
+                },
+            ],
+            signature: "154fb694ee7fcb37bf2381d94488c2a84b03b3352ad085feca81ad26d45852b7\
+                        ecfe971ce4da95c4a95db93ac376b053897fca212ef85f99cf696bffeb178f07",
+        };
+        let signature = check_test_vector::<Ed25519>(&vector);
+
+        // FROST(Ed25519, SHA-512) produces regular Ed25519 signatures
+        let public_key =
+            eddsa::PublicKey::from_bytes(&hex::decode(vector.group_public_key).unwrap()).unwrap();
+        let signature = eddsa::Signature::from_bytes(&signature.to_bytes()).unwrap();
+        assert!(signature
+            .verify(&public_key, b"test", eddsa::VerificationMode::Cofactorless)
+            .is_ok());
+    }
+
+    #[test]
+    fn rfc9591_ristretto255() {
+        check_test_vector::<Ristretto>(&TestVector {
+            group_secret_key: "1b25a55e463cfd15cf14a5d3acc3d15053f08da49c8afcf3ab265f2ebc4f970b",
+            group_public_key: "e2a62f39eede11269e3bd5a7d97554f5ca384f9f6d3dd9c3c0d05083c7254f57",
+            share_polynomial_coefficient:
+                "410f8b744b19325891d73736923525a4f596c805d060dfb9c98009d34e3fec02",
+            participant_shares: [
+                "5c3430d391552f6e60ecdc093ff9f6f4488756aa6cebdbad75a768010b8f830e",
+                "b06fc5eac20b4f6e1b271d9df2343d843e1e1fb03c4cbb673f2872d459ce6f01",
+                "f17e505f0e2581c6acfe54d3846a622834b5e7b50cad9a2109a97ba7a80d5c04",
+            ],
+            signers: [
+                Signer {
+                    index: 1,
+                    hiding_nonce_randomness:
+                        "4366eff4b4676f97822998ec3e5af1388131bffa3db76e54ee19805f0e778f33",
+                    binding_nonce_randomness:
+                        "173a02012818fbb58ed70b2dc620bb22e2d144d8c3953479e213ed7323f0270a",
+                    hiding_nonce:
+                        "b16f611649233ec528d20759e8cf828c12630a3b73996a643e59a5eda63c8b05",
+                    binding_nonce:
+                        "0cfb25a32975cc397e86509cdeb461d83d5021e4101c9554bd0b33b776645e09",
+                    binding_factor:
+                        "812e62f8abce961bd9b3aabe36b6a00e7158611d6b5ccedec98119ae94a20d01",
+                    sig_share: "1f5adbfd775a95ce4c95c7d81b3898d89bdce160adece3168b38dc9367a20502",
+                },
+                Signer {
+                    index: 3,
+                    hiding_nonce_randomness:
+                        "ca93286a68e79bd850839fd4fb4498fbee1bae3829f5dbd9e447860925e556cc",
+                    binding_nonce_randomness:
+                        "598dce485138d86ddecebb5c0cfa112ac114eb35fcded3cf69ad915c7abeb425",
+                    hiding_nonce:
+                        "4388f2f9912a8d2af3e9a655ce20c4eeb4a8c31df626cf628996f770b156b104",
+                    binding_nonce:
+                        "c680e9639176fd17747a915864299a3ad8fbd85883271d85726f311c94ed0604",
+                    binding_factor:
+                        "989e8cca0fbd75655f6eb77b010c45e4368c4fa912dbffb6c06798a42d91cd07",
+                    sig_share: "34c974f623cd0b5563334afc2a395ee86c0638136d6cad74240478c13d4a2101",
+                },
+            ],
+            signature: "fa954853693068803615803a06e2c23a6228f7d6d6b442b72b26696aa776fe75\
+                        532350f49b27a123b0c811d54671f6c008e319741a59918baf3c5455a5ec2603",
+        });
+    }
+
+    #[test]
+    fn rfc9591_secp256k1() {
+        check_test_vector::<Secp256k1>(&TestVector {
+            group_secret_key: "0d004150d27c3bf2a42f312683d35fac7394b1e9e318249c1bfe7f0795a83114",
+            group_public_key: "02f37c34b66ced1fb51c34a90bdae006901f10625cc06c4f64663b0eae87d87b4f",
+            share_polynomial_coefficient:
+                "fbf85eadae3058ea14f19148bb72b45e4399c0b16028acaf0395c9b03c823579",
+            participant_shares: [
+                "08f89ffe80ac94dcb920c26f3f46140bfc7f95b493f8310f5fc1ea2b01f4254c",
+                "04f0feac2edcedc6ce1253b7fab8c86b856a797f44d83d82a385554e6e401984",
+                "00e95d59dd0d46b0e303e500b62b7ccb0e555d49f5b849f5e748c071da8c0dbc",
+            ],
+            signers: [
+                Signer {
+                    index: 1,
+                    hiding_nonce_randomness:
+                        "bda8e748e599187762cff956f03dc6ea13fc8e04491a0427b7e6e78600f41c52",
+                    binding_nonce_randomness:
+                        "2ca682429bf05df435b9927b8edb1d748278f3e42fa11ef358e49bbf4a1b780d",
+                    hiding_nonce:
+                        "09764379667f9a9fa61928947bd925a7f162b21886b750d3b11c226d16b32f58",
+                    binding_nonce:
+                        "b2d3f8cb9da70984354c3fc3511b1f6ed21b7205941cb5553565d2ecade8c694",
+                    binding_factor:
+                        "9bee5aef4012de4b94c9fc1a9a9572181079e293bf1d7545a5af0ef86f824a91",
+                    sig_share: "ca54b18d7449377cfa680760a5770b9e64e201f7ea36b068effeca5fce2155e5",
+                },
+                Signer {
+                    index: 3,
+                    hiding_nonce_randomness:
+                        "70818dd5170672c4a4285fd593d4f222417f941f3118e1244955e7a1098a35d8",
+                    binding_nonce_randomness:
+                        "74ca2da071ed4a2a6cad5087d6758b48a558ab5861c61117fee05757e4b1309e",
+                    hiding_nonce:
+                        "0d92e255e5b42ebc2863f8198d946fc10f388c4983073c18cbb77b88e3bf2e34",
+                    binding_nonce:
+                        "1c7243ce00a499b1e7ce3403e7b731d0c820cf108feb8c5ee7c29b4ef43be5e0",
+                    binding_factor:
+                        "cfe0db2197c94cc355b6ab05610f27f4a874898009c8bf007f2a4e2ce2c8306d",
+                    sig_share: "da13d054e83052568706a6d161d80f112a6bc3f76aa903c022585ae7e091e65e",
+                },
+            ],
+            signature: "024c1ad4e031872661fa6ebd05dfc7fb30db08b38d79f0edbc82051ae931381bc6\
+                        a46881e25c7989d3816eae32074f1ab0d49ee908a59713ed5284c6bade7cfb02",
+        });
+    }
+
+    fn keygen<E: FrostCurve>(t: u16, n: u16) -> Vec<KeyShare<E, Sha256>> {
+        let (vss, shares) = VerifiableSS::share(t, n, &Scalar::random());
+        (1..=n)
+            .zip(shares.iter())
+            .map(|(i, share)| KeyShare::new(i, share.clone(), vss.clone()).unwrap())
+            .collect()
+    }
+
+    fn sign_and_aggregate<E: FrostCurve>() {
+        let key_shares = keygen::<E>(2, 5);
+        let vss = key_shares[0].vss();
+        let signers = [&key_shares[4], &key_shares[1], &key_shares[2]];
+
+        let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(|s| s.commit()).unzip();
+        let package = SigningPackage::new(commitments, b"hello".to_vec()).unwrap();
+        let shares: Vec<_> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(s, nonces)| s.sign(nonces, &package).unwrap())
+            .collect();
+        let signature = aggregate(vss, &package, &shares).unwrap();
+        assert!(signature
+            .verify(key_shares[0].group_public_key(), b"hello")
+            .is_ok());
+        assert_eq!(
+            signature.verify(key_shares[0].group_public_key(), b"hello!"),
+            Err(FrostError::InvalidSignature)
+        );
+        assert_eq!(Signature::from_bytes(&signature.to_bytes()), Ok(signature));
+    }
+
+    #[test]
+    fn sign_and_aggregate_secp256k1() {
+        sign_and_aggregate::<Secp256k1>()
+    }
+
+    #[test]
+    fn sign_and_aggregate_ed25519() {
+        sign_and_aggregate::<Ed25519>()
+    }
+
+    #[test]
+    fn sign_and_aggregate_ristretto() {
+        sign_and_aggregate::<Ristretto>()
+    }
+
+    #[test]
+    fn misbehaving_signers_are_identified() {
+        let key_shares = keygen::<Secp256k1>(1, 4);
+        let vss = key_shares[0].vss();
+        let (nonces, commitments): (Vec<_>, Vec<_>) = key_shares.iter().map(|s| s.commit()).unzip();
+        let package = SigningPackage::new(commitments, b"hello".to_vec()).unwrap();
+        let mut shares: Vec<_> = key_shares
+            .iter()
+            .zip(nonces)
+            .map(|(s, nonces)| s.sign(nonces, &package).unwrap())
+            .collect();
+        shares[1].share = &shares[1].share + Scalar::from(1);
+        shares[3].share = Scalar::random();
+
+        assert!(shares[0].verify(vss, &package).is_ok());
+        assert_eq!(
+            shares[1].verify(vss, &package),
+            Err(FrostError::InvalidSignatureShares(vec![2]))
+        );
+        assert_eq!(
+            aggregate(vss, &package, &shares),
+            Err(FrostError::InvalidSignatureShares(vec![2, 4]))
+        );
+    }
+
+    #[test]
+    fn malformed_packages_are_rejected() {
+        let key_shares = keygen::<Secp256k1>(1, 3);
+        let (nonces, commitments): (Vec<_>, Vec<_>) = key_shares.iter().map(|s| s.commit()).unzip();
+
+        let duplicated = vec![commitments[0].clone(), commitments[0].clone()];
+        assert_eq!(
+            SigningPackage::new(duplicated, b"hello".to_vec()),
+            Err(FrostError::DuplicateIndex(1))
+        );
+
+        for identity_hiding in [true, false] {
+            let mut identity = commitments.clone();
+            if identity_hiding {
+                identity[1].hiding = Point::zero();
+            } else {
+                identity[1].binding = Point::zero();
+            }
+            assert_eq!(
+                SigningPackage::new(identity, b"hello".to_vec()),
+                Err(FrostError::InvalidCommitments(2))
+            );
+        }
+
+        let mut nonces = nonces.into_iter();
+        let package = SigningPackage::new(commitments[..1].to_vec(), b"hello".to_vec()).unwrap();
+        assert_eq!(
+            key_shares[0].sign(nonces.next().unwrap(), &package),
+            Err(FrostError::NotEnoughSigners {
+                required: 2,
+                received: 1
+            })
+        );
+
+        // Package doesn't contain signer's commitments
+        let package = SigningPackage::new(
+            vec![commitments[0].clone(), commitments[2].clone()],
+            b"hello".to_vec(),
+        )
+        .unwrap();
+        assert_eq!(
+            key_shares[1].sign(nonces.next().unwrap(), &package),
+            Err(FrostError::MissingCommitments(2))
+        );
+
+        // Nonces don't match commitments in the package
+        let (_, other_commitments) = key_shares[2].commit();
+        let package = SigningPackage::new(
+            vec![commitments[0].clone(), other_commitments],
+            b"hello".to_vec(),
+        )
+        .unwrap();
+        assert_eq!(
+            key_shares[2].sign(nonces.next().unwrap(), &package),
+            Err(FrostError::MissingCommitments(3))
+        );
+    }
+}
@@ -0,0 +1,14 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+pub mod bls;
+pub mod ecdsa;
+pub mod eddsa;
+pub mod frost;
+pub mod musig2;
+pub mod schnorr_bip340;
+pub mod threshold_bls;
@@ -0,0 +1,1232 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! MuSig2 n-of-n multi-signatures over secp256k1 as specified in [BIP-327]
+//!
+//! Public keys of signers are aggregated into a single key via [KeyAggContext]. Every key is
+//! multiplied by a coefficient derived from the whole list of keys, which protects against
+//! rogue-key attacks (unlike simple summation of public keys). Aggregated key can be tweaked
+//! (e.g. to derive BIP-32 child key or Taproot output key), see
+//! [with_plain_tweak](KeyAggContext::with_plain_tweak) and
+//! [with_xonly_tweak](KeyAggContext::with_xonly_tweak). Signing takes two rounds:
+//! 1. Every signer generates a [SecretNonce] and sends corresponding [PublicNonce] to others.
+//!    Public nonces are summed into [AggregatedNonce].
+//! 2. Every signer produces a [PartialSignature] within a [SigningSession]. Partial signatures
+//!    are aggregated into an ordinary [BIP-340](schnorr_bip340) signature which is valid under
+//!    [aggregated x-only public key](KeyAggContext::x_only_public_key).
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::signatures::musig2::*;
+//! use curv::elliptic::curves::{Point, Scalar, Secp256k1};
+//!
+//! let message: &[u8] = b"hello";
+//! let secret_keys: Vec<Scalar<Secp256k1>> = (0..3).map(|_| Scalar::random()).collect();
+//! let public_keys = secret_keys.iter().map(|sk| Point::generator() * sk).collect();
+//! let key_agg = KeyAggContext::new(public_keys).unwrap();
+//!
+//! // Round 1: signers exchange public nonces
+//! let (secret_nonces, public_nonces): (Vec<_>, Vec<_>) = secret_keys
+//!     .iter()
+//!     .map(|sk| SecretNonce::generate(sk, Some(&key_agg), Some(message)))
+//!     .unzip();
+//! let aggregated_nonce = AggregatedNonce::aggregate(&public_nonces);
+//!
+//! // Round 2: signers produce partial signatures
+//! let session = SigningSession::new(&key_agg, &aggregated_nonce, message);
+//! let partial_signatures: Vec<_> = secret_keys
+//!     .iter()
+//!     .zip(secret_nonces)
+//!     .map(|(sk, secret_nonce)| session.sign(secret_nonce, sk).unwrap())
+//!     .collect();
+//!
+//! let signature = session.aggregate(&partial_signatures);
+//! assert!(signature.verify(&key_agg.x_only_public_key(), message).is_ok());
+//! ```
+//!
+//! [BIP-327]: https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki
+
+use std::convert::TryInto;
+use std::fmt;
+
+use sha2::Digest;
+use thiserror::Error;
+
+use crate::arithmetic::*;
+use crate::cryptographic_primitives::signatures::schnorr_bip340::{
+    self, has_even_y, tagged_hash, x_bytes, XOnlyPublicKey,
+};
+use crate::elliptic::curves::{Point, Scalar, Secp256k1};
+
+/// Aggregated public key of signers along with key aggregation coefficients and applied tweaks
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyAggContext {
+    public_keys: Vec<Point<Secp256k1>>,
+    coefficients: Vec<Scalar<Secp256k1>>,
+    aggregated_public_key: Point<Secp256k1>,
+    /// Accumulated sign `gacc` of aggregated public key (either `1` or `-1`)
+    gacc: Scalar<Secp256k1>,
+    /// Accumulated tweak `tacc`
+    tacc: Scalar<Secp256k1>,
+}
+
+impl KeyAggContext {
+    /// Aggregates public keys of signers
+    ///
+    /// Aggregated key depends on order of public keys. Use [sort_public_keys] to make it
+    /// independent of the order. Returns error if the list is empty or contains zero point.
+    pub fn new(public_keys: Vec<Point<Secp256k1>>) -> Result<Self, MuSig2Error> {
+        if public_keys.is_empty() {
+            return Err(MuSig2Error::NoPublicKeys);
+        }
+        if let Some(i) = public_keys.iter().position(|pk| pk.is_zero()) {
+            return Err(MuSig2Error::InvalidPublicKey(i));
+        }
+
+        let list_hash = public_keys
+            .iter()
+            .fold(tagged_hash(b"KeyAgg list"), |hash, pk| {
+                hash.chain(&*pk.to_bytes(true))
+            })
+            .finalize();
+        // Second distinct key in the list gets coefficient 1
+        let second_key = public_keys.iter().find(|pk| **pk != public_keys[0]);
+        let coefficients: Vec<Scalar<Secp256k1>> = public_keys
+            .iter()
+            .map(|pk| {
+                if Some(pk) == second_key {
+                    Scalar::from(1)
+                } else {
+                    let hash = tagged_hash(b"KeyAgg coefficient")
+                        .chain(list_hash)
+                        .chain(&*pk.to_bytes(true))
+                        .finalize();
+                    Scalar::from_bigint(&BigInt::from_bytes(&hash))
+                }
+            })
+            .collect();
+        let aggregated_public_key = Point::multiscalar_mul(&coefficients, &public_keys);
+        if aggregated_public_key.is_zero() {
+            return Err(MuSig2Error::ZeroAggregatedPublicKey);
+        }
+
+        Ok(Self {
+            public_keys,
+            coefficients,
+            aggregated_public_key,
+            gacc: Scalar::from(1),
+            tacc: Scalar::zero(),
+        })
+    }
+
+    /// Tweaks aggregated public key: `Q' = Q + t G` (`ApplyTweak` with plain tweak)
+    ///
+    /// Plain tweaking is used, for instance, to derive BIP-32 child keys. Returns error if
+    /// tweaked key is zero.
+    pub fn with_plain_tweak(self, tweak: &Scalar<Secp256k1>) -> Result<Self, MuSig2Error> {
+        self.with_tweak(tweak, false)
+    }
+
+    /// Tweaks x-only aggregated public key: `Q' = g Q + t G`, where `g` is `1` if `Q` has even y
+    /// coordinate and `-1` otherwise (`ApplyTweak` with x-only tweak)
+    ///
+    /// X-only tweaking is used, for instance, to derive Taproot output key (BIP-341). Returns
+    /// error if tweaked key is zero.
+    pub fn with_xonly_tweak(self, tweak: &Scalar<Secp256k1>) -> Result<Self, MuSig2Error> {
+        self.with_tweak(tweak, true)
+    }
+
+    fn with_tweak(
+        mut self,
+        tweak: &Scalar<Secp256k1>,
+        is_xonly: bool,
+    ) -> Result<Self, MuSig2Error> {
+        if is_xonly && !has_even_y(&self.aggregated_public_key) {
+            self.aggregated_public_key = -self.aggregated_public_key;
+            self.gacc = -self.gacc;
+            self.tacc = -self.tacc;
+        }
+        self.aggregated_public_key = self.aggregated_public_key + Point::generator() * tweak;
+        if self.aggregated_public_key.is_zero() {
+            return Err(MuSig2Error::InvalidTweak);
+        }
+        self.tacc = self.tacc + tweak;
+        Ok(self)
+    }
+
+    /// Public keys of signers in the order they were aggregated
+    pub fn public_keys(&self) -> &[Point<Secp256k1>] {
+        &self.public_keys
+    }
+
+    /// Aggregated public key `Q = Σ a_i P_i` with applied tweaks
+    ///
+    /// Note that `Q` might have odd y coordinate. Signatures are valid under
+    /// [x-only public key](Self::x_only_public_key).
+    pub fn aggregated_public_key(&self) -> &Point<Secp256k1> {
+        &self.aggregated_public_key
+    }
+
+    /// Aggregated public key in BIP-340 form
+    pub fn x_only_public_key(&self) -> XOnlyPublicKey {
+        XOnlyPublicKey::from_point(&self.aggregated_public_key)
+            .expect("aggregated public key is not zero")
+    }
+
+    /// Key aggregation coefficient `a_i` of a signer, or `None` if the public key is not in the
+    /// list
+    pub fn key_agg_coefficient(&self, public_key: &Point<Secp256k1>) -> Option<&Scalar<Secp256k1>> {
+        self.public_keys
+            .iter()
+            .position(|pk| pk == public_key)
+            .map(|i| &self.coefficients[i])
+    }
+}
+
+/// Sorts public keys in lexicographical order of their compressed encodings (`KeySort`)
+pub fn sort_public_keys(public_keys: &mut [Point<Secp256k1>]) {
+    public_keys.sort_by_cached_key(|pk| pk.to_bytes(true).to_vec());
+}
+
+/// Secret nonce `(k1, k2)` of a signer
+///
+/// Nonce must be used to sign only once, so it's consumed by [SigningSession::sign] and can't be
+/// cloned.
+pub struct SecretNonce {
+    k1: Scalar<Secp256k1>,
+    k2: Scalar<Secp256k1>,
+    public_key: Point<Secp256k1>,
+}
+
+/// Public nonce `(R1, R2) = (k1 G, k2 G)` of a signer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicNonce {
+    pub r1: Point<Secp256k1>,
+    pub r2: Point<Secp256k1>,
+}
+
+/// Sum of public nonces of all signers
+///
+/// Unlike [PublicNonce], either of points might be zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AggregatedNonce {
+    pub r1: Point<Secp256k1>,
+    pub r2: Point<Secp256k1>,
+}
+
+impl SecretNonce {
+    /// Generates a fresh nonce (`NonceGen`)
+    ///
+    /// Aggregated public key and message are optional: they're not needed for security, but
+    /// mixing them into the nonce provides additional protection against a weak randomness
+    /// source.
+    ///
+    /// ## Panics
+    /// Panics if secret key is zero.
+    pub fn generate(
+        secret_key: &Scalar<Secp256k1>,
+        key_agg: Option<&KeyAggContext>,
+        message: Option<&[u8]>,
+    ) -> (SecretNonce, PublicNonce) {
+        assert!(!secret_key.is_zero(), "secret key is zero");
+        let rand: [u8; 32] = rand::random();
+        let aggregated_public_key = key_agg.map(|ctx| ctx.x_only_public_key().to_bytes());
+        Self::generate_with_rand(
+            &rand,
+            Some(secret_key),
+            Point::generator() * secret_key,
+            aggregated_public_key.as_ref(),
+            message,
+            None,
+        )
+    }
+
+    fn generate_with_rand(
+        rand: &[u8; 32],
+        secret_key: Option<&Scalar<Secp256k1>>,
+        public_key: Point<Secp256k1>,
+        aggregated_public_key: Option<&[u8; 32]>,
+        message: Option<&[u8]>,
+        extra_input: Option<&[u8]>,
+    ) -> (SecretNonce, PublicNonce) {
+        let rand: Vec<u8> = match secret_key {
+            Some(secret_key) => {
+                let aux_hash = tagged_hash(b"MuSig/aux").chain(rand).finalize();
+                secret_key
+                    .to_bytes()
+                    .iter()
+                    .zip(aux_hash.iter())
+                    .map(|(d_i, aux_i)| d_i ^ aux_i)
+                    .collect()
+            }
+            None => rand.to_vec(),
+        };
+        let public_key_bytes = public_key.to_bytes(true);
+        let aggregated_public_key: &[u8] = aggregated_public_key.map_or(&[], |pk| &pk[..]);
+
+        let mut hash = tagged_hash(b"MuSig/nonce")
+            .chain(&rand)
+            .chain([public_key_bytes.len() as u8])
+            .chain(&*public_key_bytes)
+            .chain([aggregated_public_key.len() as u8])
+            .chain(aggregated_public_key);
+        hash = match message {
+            Some(message) => hash
+                .chain([1])
+                .chain((message.len() as u64).to_be_bytes())
+                .chain(message),
+            None => hash.chain([0]),
+        };
+        let extra_input = extra_input.unwrap_or(&[]);
+        let extra_input_len: u32 = extra_input
+            .len()
+            .try_into()
+            .expect("extra input is too long");
+        hash = hash.chain(extra_input_len.to_be_bytes()).chain(extra_input);
+
+        let k = |i: u8| {
+            let k_i = hash.clone().chain([i]).finalize();
+            let k_i = Scalar::<Secp256k1>::from_bigint(&BigInt::from_bytes(&k_i));
+            // k_i = 0 happens with negligible probability
+            assert!(!k_i.is_zero(), "nonce is zero");
+            k_i
+        };
+        let (k1, k2) = (k(0), k(1));
+        let public_nonce = PublicNonce {
+            r1: Point::generator() * &k1,
+            r2: Point::generator() * &k2,
+        };
+        let secret_nonce = SecretNonce { k1, k2, public_key };
+        (secret_nonce, public_nonce)
+    }
+}
+
+impl PublicNonce {
+    /// Parses 66 bytes public nonce
+    ///
+    /// Returns error if either half is not a compressed encoding of a non-zero point.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MuSig2Error> {
+        let (r1, r2) = parse_nonce(bytes)?;
+        if r1.is_zero() || r2.is_zero() {
+            return Err(MuSig2Error::MalformedNonce);
+        }
+        Ok(Self { r1, r2 })
+    }
+
+    /// Serializes public nonce as 66 bytes `cbytes(R1) || cbytes(R2)`
+    pub fn to_bytes(&self) -> [u8; 66] {
+        serialize_nonce(&self.r1, &self.r2)
+    }
+}
+
+impl AggregatedNonce {
+    /// Sums public nonces of all signers (`NonceAgg`)
+    pub fn aggregate(public_nonces: &[PublicNonce]) -> Self {
+        Self {
+            r1: public_nonces.iter().map(|n| &n.r1).sum(),
+            r2: public_nonces.iter().map(|n| &n.r2).sum(),
+        }
+    }
+
+    /// Parses 66 bytes aggregated nonce
+    ///
+    /// Zero point is expected to be encoded as 33 zero bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MuSig2Error> {
+        let (r1, r2) = parse_nonce(bytes)?;
+        Ok(Self { r1, r2 })
+    }
+
+    /// Serializes aggregated nonce as 66 bytes `cbytes_ext(R1) || cbytes_ext(R2)`
+    pub fn to_bytes(&self) -> [u8; 66] {
+        serialize_nonce(&self.r1, &self.r2)
+    }
+}
+
+/// Partial signature `s_i` produced by a single signer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartialSignature {
+    pub s: Scalar<Secp256k1>,
+}
+
+impl PartialSignature {
+    /// Parses 32 bytes partial signature
+    ///
+    /// Returns error if it's not less than group order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MuSig2Error> {
+        if bytes.len() != 32 {
+            return Err(MuSig2Error::MalformedPartialSignature);
+        }
+        let s = Scalar::from_bytes(bytes).or(Err(MuSig2Error::MalformedPartialSignature))?;
+        Ok(Self { s })
+    }
+
+    /// Serializes partial signature as 32 bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.s
+            .to_bytes()
+            .as_ref()
+            .try_into()
+            .expect("secp256k1 scalar is 32 bytes")
+    }
+}
+
+/// Values shared by all signers that are needed to produce, verify and aggregate partial
+/// signatures of a message
+#[derive(Clone, Debug)]
+pub struct SigningSession<'a> {
+    key_agg: &'a KeyAggContext,
+    b: Scalar<Secp256k1>,
+    r: Point<Secp256k1>,
+    e: Scalar<Secp256k1>,
+}
+
+impl<'a> SigningSession<'a> {
+    /// Derives session values from aggregated key, aggregated nonce and the message
+    pub fn new(
+        key_agg: &'a KeyAggContext,
+        aggregated_nonce: &AggregatedNonce,
+        message: &[u8],
+    ) -> Self {
+        let q_bytes = x_bytes(&key_agg.aggregated_public_key);
+        let b = tagged_hash(b"MuSig/noncecoef")
+            .chain(aggregated_nonce.to_bytes())
+            .chain(q_bytes)
+            .chain(message)
+            .finalize();
+        let b = Scalar::from_bigint(&BigInt::from_bytes(&b));
+        let r = &aggregated_nonce.r1 + &aggregated_nonce.r2 * &b;
+        let r = if r.is_zero() {
+            Point::generator().to_point()
+        } else {
+            r
+        };
+        let e = schnorr_bip340::challenge(&x_bytes(&r), &q_bytes, message);
+        Self { key_agg, b, r, e }
+    }
+
+    /// Produces a partial signature
+    ///
+    /// Returns error if signer's public key is not in the list of aggregated keys, or if secret
+    /// nonce was generated for a different key.
+    pub fn sign(
+        &self,
+        secret_nonce: SecretNonce,
+        secret_key: &Scalar<Secp256k1>,
+    ) -> Result<PartialSignature, MuSig2Error> {
+        if secret_nonce.k1.is_zero() || secret_nonce.k2.is_zero() {
+            return Err(MuSig2Error::InvalidSecretNonce);
+        }
+        if secret_key.is_zero() {
+            return Err(MuSig2Error::ZeroSecretKey);
+        }
+        let public_key = Point::generator() * secret_key;
+        if public_key != secret_nonce.public_key {
+            return Err(MuSig2Error::MismatchedSecretNonce);
+        }
+        let a = self
+            .key_agg
+            .key_agg_coefficient(&public_key)
+            .ok_or(MuSig2Error::UnknownPublicKey)?;
+
+        let (k1, k2) = if has_even_y(&self.r) {
+            (secret_nonce.k1, secret_nonce.k2)
+        } else {
+            (-secret_nonce.k1, -secret_nonce.k2)
+        };
+        // d = g gacc sk
+        let d = if has_even_y(&self.key_agg.aggregated_public_key) {
+            secret_key * &self.key_agg.gacc
+        } else {
+            -(secret_key * &self.key_agg.gacc)
+        };
+        let s = k1 + &self.b * k2 + &self.e * a * d;
+        Ok(PartialSignature { s })
+    }
+
+    /// Verifies partial signature of a signer with given public nonce and public key
+    pub fn verify_partial_signature(
+        &self,
+        partial_signature: &PartialSignature,
+        public_nonce: &PublicNonce,
+        public_key: &Point<Secp256k1>,
+    ) -> Result<(), MuSig2Error> {
+        let a = self
+            .key_agg
+            .key_agg_coefficient(public_key)
+            .ok_or(MuSig2Error::UnknownPublicKey)?;
+        let r_i = &public_nonce.r1 + &public_nonce.r2 * &self.b;
+        let r_i = if has_even_y(&self.r) { r_i } else { -r_i };
+        let g_e_a = if has_even_y(&self.key_agg.aggregated_public_key) {
+            &self.e * a * &self.key_agg.gacc
+        } else {
+            -(&self.e * a * &self.key_agg.gacc)
+        };
+        if Point::generator() * &partial_signature.s == r_i + public_key * g_e_a {
+            Ok(())
+        } else {
+            Err(MuSig2Error::InvalidPartialSignature)
+        }
+    }
+
+    /// Aggregates partial signatures of all signers into BIP-340 signature (`PartialSigAgg`)
+    ///
+    /// Partial signatures are not verified by this function: make sure to
+    /// [verify](Self::verify_partial_signature) them beforehand, or verify the resulting
+    /// signature against [aggregated key](KeyAggContext::x_only_public_key).
+    pub fn aggregate(&self, partial_signatures: &[PartialSignature]) -> schnorr_bip340::Signature {
+        let s: Scalar<Secp256k1> = partial_signatures.iter().map(|s| &s.s).sum();
+        // s = Σ s_i + e g tacc
+        let e_tacc = &self.e * &self.key_agg.tacc;
+        let s = if has_even_y(&self.key_agg.aggregated_public_key) {
+            s + e_tacc
+        } else {
+            s - e_tacc
+        };
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&x_bytes(&self.r));
+        bytes[32..].copy_from_slice(&s.to_bytes());
+        schnorr_bip340::Signature::from_bytes(&bytes).expect("R is a valid point")
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum MuSig2Error {
+    #[error("list of public keys is empty")]
+    NoPublicKeys,
+    #[error("public key at index {0} is zero")]
+    InvalidPublicKey(usize),
+    #[error("aggregated public key is zero")]
+    ZeroAggregatedPublicKey,
+    #[error("tweaked public key is zero")]
+    InvalidTweak,
+    #[error("public key is not in the list of aggregated keys")]
+    UnknownPublicKey,
+    #[error("secret key is zero")]
+    ZeroSecretKey,
+    #[error("secret nonce is zero, it might have been reused")]
+    InvalidSecretNonce,
+    #[error("secret nonce was generated for a different public key")]
+    MismatchedSecretNonce,
+    #[error("nonce is malformed")]
+    MalformedNonce,
+    #[error("partial signature is malformed")]
+    MalformedPartialSignature,
+    #[error("partial signature is not valid")]
+    InvalidPartialSignature,
+}
+
+fn parse_nonce(bytes: &[u8]) -> Result<(Point<Secp256k1>, Point<Secp256k1>), MuSig2Error> {
+    if bytes.len() != 66 {
+        return Err(MuSig2Error::MalformedNonce);
+    }
+    let r1 = Point::from_bytes(&bytes[..33]).or(Err(MuSig2Error::MalformedNonce))?;
+    let r2 = Point::from_bytes(&bytes[33..]).or(Err(MuSig2Error::MalformedNonce))?;
+    Ok((r1, r2))
+}
+
+fn serialize_nonce(r1: &Point<Secp256k1>, r2: &Point<Secp256k1>) -> [u8; 66] {
+    // Zero point is serialized as 33 zero bytes
+    let mut bytes = [0u8; 66];
+    bytes[..33].copy_from_slice(&r1.to_bytes(true));
+    bytes[33..].copy_from_slice(&r2.to_bytes(true));
+    bytes
+}
+
+impl fmt::Debug for SecretNonce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(f, "SecretNonce {{ public_key: {:?}, .. }}", self.public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(hex: &str) -> Result<Point<Secp256k1>, ()> {
+        let bytes = hex::decode(hex).unwrap();
+        if bytes.len() != 33 {
+            return Err(());
+        }
+        Point::from_bytes(&bytes).or(Err(()))
+    }
+
+    fn scalar(hex: &str) -> Scalar<Secp256k1> {
+        Scalar::from_bytes(&hex::decode(hex).unwrap()).unwrap()
+    }
+
+    fn key_agg(public_keys: &[&str], indices: &[usize]) -> KeyAggContext {
+        let public_keys = indices
+            .iter()
+            .map(|&i| point(public_keys[i]).unwrap())
+            .collect();
+        KeyAggContext::new(public_keys).unwrap()
+    }
+
+    /// Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/key_agg_vectors.json
+    #[test]
+    fn key_agg_vectors() {
+        let public_keys = [
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+            "020000000000000000000000000000000000000000000000000000000000000005",
+            "02FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+            "04F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+        ];
+        let tweaks = [
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            "252E4BD67410A76CDF933D30EAA1608214037F1B105A013ECCD3C5C184A6110B",
+        ];
+        let valid_cases: &[(&[usize], &str)] = &[
+            (
+                &[0, 1, 2],
+                "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+            ),
+            (
+                &[2, 1, 0],
+                "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B",
+            ),
+            (
+                &[0, 0, 0],
+                "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935",
+            ),
+            (
+                &[0, 0, 1, 1],
+                "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E",
+            ),
+        ];
+        for (indices, expected) in valid_cases {
+            let ctx = key_agg(&public_keys, indices);
+            assert_eq!(
+                hex::encode_upper(ctx.x_only_public_key().to_bytes()),
+                *expected
+            );
+        }
+
+        // Invalid public key, public key exceeds field size, first byte is not 2 or 3
+        for invalid in &public_keys[3..6] {
+            assert!(point(invalid).is_err());
+        }
+        // Tweak is out of range
+        assert!(Scalar::<Secp256k1>::from_bytes(&hex::decode(tweaks[0]).unwrap()).is_err());
+        // Intermediate tweaking result is point at infinity
+        assert_eq!(
+            key_agg(&public_keys, &[6]).with_plain_tweak(&scalar(tweaks[1])),
+            Err(MuSig2Error::InvalidTweak)
+        );
+    }
+
+    /// Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/nonce_agg_vectors.json
+    #[test]
+    fn nonce_agg_vectors() {
+        let public_nonces = [
+            "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E66603BA47FBC1834437B3212E89A84D8425E7BF12E0245D98262268EBDCB385D50641",
+            "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+            "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E6660279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60379BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "04FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+            "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B831",
+            "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A602FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+        ];
+        let valid_cases: &[([usize; 2], &str)] = &[
+            (
+                [0, 1],
+                "035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B024725377345BDE0E9C33AF3C43C0A29A9249F2F2956FA8CFEB55C8573D0262DC8",
+            ),
+            (
+                [2, 3],
+                "035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B000000000000000000000000000000000000000000000000000000000000000000",
+            ),
+        ];
+        for (indices, expected) in valid_cases {
+            let nonces: Vec<_> = indices
+                .iter()
+                .map(|&i| PublicNonce::from_bytes(&hex::decode(public_nonces[i]).unwrap()).unwrap())
+                .collect();
+            let aggregated_nonce = AggregatedNonce::aggregate(&nonces);
+            assert_eq!(hex::encode_upper(aggregated_nonce.to_bytes()), *expected);
+            assert_eq!(
+                AggregatedNonce::from_bytes(&hex::decode(expected).unwrap()),
+                Ok(aggregated_nonce)
+            );
+        }
+
+        // Wrong tag, second half is not an x coordinate, second half exceeds field size
+        for invalid in &public_nonces[4..] {
+            assert_eq!(
+                PublicNonce::from_bytes(&hex::decode(invalid).unwrap()),
+                Err(MuSig2Error::MalformedNonce)
+            );
+        }
+    }
+
+    const SIGN_VERIFY_SECRET_KEY: &str =
+        "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671";
+    const SIGN_VERIFY_PUBLIC_KEYS: [&str; 4] = [
+        "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+        "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661",
+        "020000000000000000000000000000000000000000000000000000000000000007",
+    ];
+    const SIGN_VERIFY_SECRET_NONCES: [(&str, &str); 2] = [
+        (
+            "508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61",
+            "FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F7",
+        ),
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        ),
+    ];
+    const SIGN_VERIFY_PUBLIC_NONCES: [&str; 5] = [
+        "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+        "0237C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0387BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        "020000000000000000000000000000000000000000000000000000000000000009",
+    ];
+    const SIGN_VERIFY_AGGREGATED_NONCES: [&str; 5] = [
+        "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9",
+        "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        "048465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9",
+        "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61020000000000000000000000000000000000000000000000000000000000000009",
+        "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD6102FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+    ];
+    const SIGN_VERIFY_MESSAGES: [&str; 3] = [
+        "F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF",
+        "",
+        "2626262626262626262626262626262626262626262626262626262626262626262626262626",
+    ];
+
+    fn secret_nonce(index: usize) -> SecretNonce {
+        let (k1, k2) = SIGN_VERIFY_SECRET_NONCES[index];
+        let k = |hex| Scalar::from_bytes(&hex::decode(hex).unwrap()).unwrap();
+        SecretNonce {
+            k1: k(k1),
+            k2: k(k2),
+            public_key: point(SIGN_VERIFY_PUBLIC_KEYS[0]).unwrap(),
+        }
+    }
+
+    fn parse_aggregated_nonce(index: usize) -> Result<AggregatedNonce, MuSig2Error> {
+        AggregatedNonce::from_bytes(&hex::decode(SIGN_VERIFY_AGGREGATED_NONCES[index]).unwrap())
+    }
+
+    fn parse_public_nonce(index: usize) -> Result<PublicNonce, MuSig2Error> {
+        PublicNonce::from_bytes(&hex::decode(SIGN_VERIFY_PUBLIC_NONCES[index]).unwrap())
+    }
+
+    /// Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/sign_verify_vectors.json
+    #[test]
+    fn sign_verify_vectors() {
+        struct ValidCase {
+            key_indices: &'static [usize],
+            nonce_indices: &'static [usize],
+            aggnonce_index: usize,
+            msg_index: usize,
+            signer_index: usize,
+            expected: &'static str,
+        }
+        let valid_cases = [
+            ValidCase {
+                key_indices: &[0, 1, 2],
+                nonce_indices: &[0, 1, 2],
+                aggnonce_index: 0,
+                msg_index: 0,
+                signer_index: 0,
+                expected: "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            },
+            ValidCase {
+                key_indices: &[1, 0, 2],
+                nonce_indices: &[1, 0, 2],
+                aggnonce_index: 0,
+                msg_index: 0,
+                signer_index: 1,
+                expected: "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52",
+            },
+            ValidCase {
+                key_indices: &[1, 2, 0],
+                nonce_indices: &[1, 2, 0],
+                aggnonce_index: 0,
+                msg_index: 0,
+                signer_index: 2,
+                expected: "FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900",
+            },
+            // Both halves of aggregate nonce correspond to point at infinity
+            ValidCase {
+                key_indices: &[0, 1],
+                nonce_indices: &[0, 3],
+                aggnonce_index: 1,
+                msg_index: 0,
+                signer_index: 0,
+                expected: "AE386064B26105404798F75DE2EB9AF5EDA5387B064B83D049CB7C5E08879531",
+            },
+            // Empty message
+            ValidCase {
+                key_indices: &[0, 1, 2],
+                nonce_indices: &[0, 1, 2],
+                aggnonce_index: 0,
+                msg_index: 1,
+                signer_index: 0,
+                expected: "D7D63FFD644CCDA4E62BC2BC0B1D02DD32A1DC3030E155195810231D1037D82D",
+            },
+            // 38-byte message
+            ValidCase {
+                key_indices: &[0, 1, 2],
+                nonce_indices: &[0, 1, 2],
+                aggnonce_index: 0,
+                msg_index: 2,
+                signer_index: 0,
+                expected: "E184351828DA5094A97C79CABDAAA0BFB87608C32E8829A4DF5340A6F243B78C",
+            },
+        ];
+
+        let secret_key = scalar(SIGN_VERIFY_SECRET_KEY);
+        let public_key = point(SIGN_VERIFY_PUBLIC_KEYS[0]).unwrap();
+        for case in &valid_cases {
+            let ctx = key_agg(&SIGN_VERIFY_PUBLIC_KEYS, case.key_indices);
+            let public_nonces: Vec<_> = case
+                .nonce_indices
+                .iter()
+                .map(|&i| parse_public_nonce(i).unwrap())
+                .collect();
+            let aggregated_nonce = parse_aggregated_nonce(case.aggnonce_index).unwrap();
+            assert_eq!(AggregatedNonce::aggregate(&public_nonces), aggregated_nonce);
+
+            let message = hex::decode(SIGN_VERIFY_MESSAGES[case.msg_index]).unwrap();
+            let session = SigningSession::new(&ctx, &aggregated_nonce, &message);
+            let partial_signature = session.sign(secret_nonce(0), &secret_key).unwrap();
+            assert_eq!(
+                hex::encode_upper(partial_signature.to_bytes()),
+                case.expected
+            );
+            assert!(session
+                .verify_partial_signature(
+                    &partial_signature,
+                    &public_nonces[case.signer_index],
+                    &public_key
+                )
+                .is_ok());
+        }
+
+        // The signer's pubkey is not in the list of pubkeys
+        let aggregated_nonce = parse_aggregated_nonce(0).unwrap();
+        let message = hex::decode(SIGN_VERIFY_MESSAGES[0]).unwrap();
+        let ctx = key_agg(&SIGN_VERIFY_PUBLIC_KEYS, &[1, 2]);
+        let session = SigningSession::new(&ctx, &aggregated_nonce, &message);
+        assert_eq!(
+            session.sign(secret_nonce(0), &secret_key),
+            Err(MuSig2Error::UnknownPublicKey)
+        );
+        // Signer 2 provided an invalid public key
+        assert!(point(SIGN_VERIFY_PUBLIC_KEYS[3]).is_err());
+        // Aggregate nonce is invalid due to wrong tag, second half is not an x coordinate, second
+        // half exceeds field size
+        for i in 2..5 {
+            assert_eq!(parse_aggregated_nonce(i), Err(MuSig2Error::MalformedNonce));
+        }
+        // Secnonce is invalid which may indicate nonce reuse
+        let ctx = key_agg(&SIGN_VERIFY_PUBLIC_KEYS, &[0, 1, 2]);
+        let session = SigningSession::new(&ctx, &aggregated_nonce, &message);
+        assert_eq!(
+            session.sign(secret_nonce(1), &secret_key),
+            Err(MuSig2Error::InvalidSecretNonce)
+        );
+
+        // Wrong signature (which is equal to the negation of valid signature)
+        let public_nonces: Vec<_> = (0..3).map(|i| parse_public_nonce(i).unwrap()).collect();
+        let wrong_signature = PartialSignature::from_bytes(
+            &hex::decode("97AC833ADCB1AFA42EBF9E0725616F3C9A0D5B614F6FE283CEAAA37A8FFAF406")
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            session.verify_partial_signature(&wrong_signature, &public_nonces[0], &public_key),
+            Err(MuSig2Error::InvalidPartialSignature)
+        );
+        // Wrong signer
+        let signature = PartialSignature::from_bytes(
+            &hex::decode("68537CC5234E505BD14061F8DA9E90C220A181855FD8BDB7F127BB12403B4D3B")
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            session.verify_partial_signature(
+                &signature,
+                &public_nonces[1],
+                &point(SIGN_VERIFY_PUBLIC_KEYS[1]).unwrap()
+            ),
+            Err(MuSig2Error::InvalidPartialSignature)
+        );
+        // Signature exceeds group size
+        assert_eq!(
+            PartialSignature::from_bytes(
+                &hex::decode("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141")
+                    .unwrap()
+            ),
+            Err(MuSig2Error::MalformedPartialSignature)
+        );
+        // Invalid pubnonce
+        assert_eq!(parse_public_nonce(4), Err(MuSig2Error::MalformedNonce));
+    }
+
+    /// Applies tweaks `tweaks[i]` listed in `indices`, `is_xonly[j]` specifies kind of j-th tweak
+    fn tweak(
+        mut ctx: KeyAggContext,
+        tweaks: &[&str],
+        indices: &[usize],
+        is_xonly: &[bool],
+    ) -> KeyAggContext {
+        for (&i, &is_xonly) in indices.iter().zip(is_xonly) {
+            let tweak = scalar(tweaks[i]);
+            ctx = if is_xonly {
+                ctx.with_xonly_tweak(&tweak)
+            } else {
+                ctx.with_plain_tweak(&tweak)
+            }
+            .unwrap();
+        }
+        ctx
+    }
+
+    /// Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/tweak_vectors.json
+    #[test]
+    fn tweak_vectors() {
+        let public_keys = [
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        ];
+        let public_nonces = [
+            "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+            "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+        ];
+        let tweaks = [
+            "E8F791FF9225A2AF0102AFFF4A9A723D9612A682A25EBE79802B263CDFCD83BB",
+            "AE2EA797CC0FE72AC5B97B97F3C6957D7E4199A167A58EB08BCAFFDA70AC0455",
+            "F52ECBC565B3D8BEA2DFD5B75A4F457E54369809322E4120831626F290FA87E0",
+            "1969AD73CC177FA0B4FCED6DF1F7BF9907E665FDE9BA196A74FED0A3CF5AEF9D",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        ];
+        let aggregated_nonce = AggregatedNonce::from_bytes(
+            &hex::decode("028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9")
+                .unwrap(),
+        )
+        .unwrap();
+        let message =
+            hex::decode("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF")
+                .unwrap();
+        let secret_key = scalar(SIGN_VERIFY_SECRET_KEY);
+        let public_key = point(public_keys[0]).unwrap();
+
+        // (tweak indices, is x-only, expected partial signature); keys and nonces are given in
+        // order [1, 2, 0], so the signer has index 2
+        let valid_cases: [(&[usize], &[bool], &str); 5] = [
+            // A single x-only tweak
+            (
+                &[0],
+                &[true],
+                "E28A5C66E61E178C2BA19DB77B6CF9F7E2F0F56C17918CD13135E60CC848FE91",
+            ),
+            // A single plain tweak
+            (
+                &[0],
+                &[false],
+                "38B0767798252F21BF5702C48028B095428320F73A4B14DB1E25DE58543D2D2D",
+            ),
+            // A plain tweak followed by an x-only tweak
+            (
+                &[0, 1],
+                &[false, true],
+                "408A0A21C4A0F5DACAF9646AD6EB6FECD7F7A11F03ED1F48DFFF2185BC2C2408",
+            ),
+            // Four tweaks: plain, plain, x-only, x-only
+            (
+                &[0, 1, 2, 3],
+                &[false, false, true, true],
+                "45ABD206E61E3DF2EC9E264A6FEC8292141A633C28586388235541F9ADE75435",
+            ),
+            // Four tweaks: x-only, plain, x-only, plain
+            (
+                &[0, 1, 2, 3],
+                &[true, false, true, false],
+                "B255FDCAC27B40C7CE7848E2D3B7BF5EA0ED756DA81565AC804CCCA3E1D5D239",
+            ),
+        ];
+        let indices = [1, 2, 0];
+        for (tweak_indices, is_xonly, expected) in &valid_cases {
+            let ctx = tweak(
+                key_agg(&public_keys, &indices),
+                &tweaks,
+                tweak_indices,
+                is_xonly,
+            );
+            let nonces: Vec<_> = indices
+                .iter()
+                .map(|&i| PublicNonce::from_bytes(&hex::decode(public_nonces[i]).unwrap()).unwrap())
+                .collect();
+            assert_eq!(AggregatedNonce::aggregate(&nonces), aggregated_nonce);
+
+            let session = SigningSession::new(&ctx, &aggregated_nonce, &message);
+            let partial_signature = session.sign(secret_nonce(0), &secret_key).unwrap();
+            assert_eq!(hex::encode_upper(partial_signature.to_bytes()), *expected);
+            assert!(session
+                .verify_partial_signature(&partial_signature, &nonces[2], &public_key)
+                .is_ok());
+        }
+
+        // Tweak is invalid because it exceeds group size
+        assert!(Scalar::<Secp256k1>::from_bytes(&hex::decode(tweaks[4]).unwrap()).is_err());
+    }
+
+    /// Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/sig_agg_vectors.json
+    #[test]
+    fn sig_agg_vectors() {
+        let public_keys = [
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+            "02D2DC6F5DF7C56ACF38C7FA0AE7A759AE30E19B37359DFDE015872324C7EF6E05",
+            "03C7FB101D97FF930ACD0C6760852EF64E69083DE0B06AC6335724754BB4B0522C",
+            "02352433B21E7E05D3B452B81CAE566E06D2E003ECE16D1074AABA4289E0E3D581",
+        ];
+        let tweaks = [
+            "B511DA492182A91B0FFB9A98020D55F260AE86D7ECBD0399C7383D59A5F2AF7C",
+            "A815FE049EE3C5AAB66310477FBC8BCCCAC2F3395F59F921C364ACD78A2F48DC",
+            "75448A87274B056468B977BE06EB1E9F657577B7320B0A3376EA51FD420D18A8",
+        ];
+        let partial_signatures = [
+            "B15D2CD3C3D22B04DAE438CE653F6B4ECF042F42CFDED7C41B64AAF9B4AF53FB",
+            "6193D6AC61B354E9105BBDC8937A3454A6D705B6D57322A5A472A02CE99FCB64",
+            "9A87D3B79EC67228CB97878B76049B15DBD05B8158D17B5B9114D3C226887505",
+            "66F82EA90923689B855D36C6B7E032FB9970301481B99E01CDB4D6AC7C347A15",
+            "4F5AEE41510848A6447DCD1BBC78457EF69024944C87F40250D3EF2C25D33EFE",
+            "DDEF427BBB847CC027BEFF4EDB01038148917832253EBC355FC33F4A8E2FCCE4",
+            "97B890A26C981DA8102D3BC294159D171D72810FDF7C6A691DEF02F0F7AF3FDC",
+            "53FA9E08BA5243CBCB0D797C5EE83BC6728E539EB76C2D0BF0F971EE4E909971",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        ];
+        let message =
+            hex::decode("599C67EA410D005B9DA90817CF03ED3B1C868E4DA4EDF00A5880B0082C237869")
+                .unwrap();
+        struct ValidCase {
+            aggnonce: &'static str,
+            key_indices: [usize; 2],
+            tweak_indices: &'static [usize],
+            is_xonly: &'static [bool],
+            psig_indices: [usize; 2],
+            expected: &'static str,
+        }
+        let valid_cases = [
+            ValidCase {
+                aggnonce: "0341432722C5CD0268D829C702CF0D1CBCE57033EED201FD335191385227C3210C03D377F2D258B64AADC0E16F26462323D701D286046A2EA93365656AFD9875982B",
+                key_indices: [0, 1],
+                tweak_indices: &[],
+                is_xonly: &[],
+                psig_indices: [0, 1],
+                expected: "041DA22223CE65C92C9A0D6C2CAC828AAF1EEE56304FEC371DDF91EBB2B9EF0912F1038025857FEDEB3FF696F8B99FA4BB2C5812F6095A2E0004EC99CE18DE1E",
+            },
+            ValidCase {
+                aggnonce: "0224AFD36C902084058B51B5D36676BBA4DC97C775873768E58822F87FE437D792028CB15929099EEE2F5DAE404CD39357591BA32E9AF4E162B8D3E7CB5EFE31CB20",
+                key_indices: [0, 2],
+                tweak_indices: &[],
+                is_xonly: &[],
+                psig_indices: [2, 3],
+                expected: "1069B67EC3D2F3C7C08291ACCB17A9C9B8F2819A52EB5DF8726E17E7D6B52E9F01800260A7E9DAC450F4BE522DE4CE12BA91AEAF2B4279219EF74BE1D286ADD9",
+            },
+            ValidCase {
+                aggnonce: "0208C5C438C710F4F96A61E9FF3C37758814B8C3AE12BFEA0ED2C87FF6954FF186020B1816EA104B4FCA2D304D733E0E19CEAD51303FF6420BFD222335CAA402916D",
+                key_indices: [0, 2],
+                tweak_indices: &[0],
+                is_xonly: &[false],
+                psig_indices: [4, 5],
+                expected: "5C558E1DCADE86DA0B2F02626A512E30A22CF5255CAEA7EE32C38E9A71A0E9148BA6C0E6EC7683B64220F0298696F1B878CD47B107B81F7188812D593971E0CC",
+            },
+            ValidCase {
+                aggnonce: "02B5AD07AFCD99B6D92CB433FBD2A28FDEB98EAE2EB09B6014EF0F8197CD58403302E8616910F9293CF692C49F351DB86B25E352901F0E237BAFDA11F1C1CEF29FFD",
+                key_indices: [0, 3],
+                tweak_indices: &[0, 1, 2],
+                is_xonly: &[true, false, true],
+                psig_indices: [6, 7],
+                expected: "839B08820B681DBA8DAF4CC7B104E8F2638F9388F8D7A555DC17B6E6971D7426CE07BF6AB01F1DB50E4E33719295F4094572B79868E440FB3DEFD3FAC1DB589E",
+            },
+        ];
+        for case in &valid_cases {
+            let ctx = tweak(
+                key_agg(&public_keys, &case.key_indices),
+                &tweaks,
+                case.tweak_indices,
+                case.is_xonly,
+            );
+            let aggregated_nonce =
+                AggregatedNonce::from_bytes(&hex::decode(case.aggnonce).unwrap()).unwrap();
+            let partial_signatures: Vec<_> = case
+                .psig_indices
+                .iter()
+                .map(|&i| {
+                    PartialSignature::from_bytes(&hex::decode(partial_signatures[i]).unwrap())
+                        .unwrap()
+                })
+                .collect();
+            let session = SigningSession::new(&ctx, &aggregated_nonce, &message);
+            let signature = session.aggregate(&partial_signatures);
+            assert_eq!(hex::encode_upper(signature.to_bytes()), case.expected);
+            assert!(signature.verify(&ctx.x_only_public_key(), &message).is_ok());
+        }
+
+        // Partial signature is invalid because it exceeds group size
+        assert_eq!(
+            PartialSignature::from_bytes(&hex::decode(partial_signatures[8]).unwrap()),
+            Err(MuSig2Error::MalformedPartialSignature)
+        );
+    }
+
+    /// Test vectors from https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/nonce_gen_vectors.json
+    #[test]
+    fn nonce_gen_vectors() {
+        struct TestCase {
+            rand: [u8; 32],
+            secret_key: Option<&'static str>,
+            public_key: &'static str,
+            aggregated_public_key: Option<[u8; 32]>,
+            message: Option<Vec<u8>>,
+            extra_input: Option<[u8; 32]>,
+            expected_secnonce: &'static str,
+            expected_pubnonce: &'static str,
+        }
+        let test_cases = [
+            TestCase {
+                rand: [0x0F; 32],
+                secret_key: Some("0202020202020202020202020202020202020202020202020202020202020202"),
+                public_key: "024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+                aggregated_public_key: Some([0x07; 32]),
+                message: Some(vec![0x01; 32]),
+                extra_input: Some([0x08; 32]),
+                expected_secnonce: "B114E502BEAA4E301DD08A50264172C84E41650E6CB726B410C0694D59EFFB6495B5CAF28D045B973D63E3C99A44B807BDE375FD6CB39E46DC4A511708D0E9D2024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+                expected_pubnonce: "02F7BE7089E8376EB355272368766B17E88E7DB72047D05E56AA881EA52B3B35DF02C29C8046FDD0DED4C7E55869137200FBDBFE2EB654267B6D7013602CAED3115A",
+            },
+            // Empty message
+            TestCase {
+                rand: [0x0F; 32],
+                secret_key: Some("0202020202020202020202020202020202020202020202020202020202020202"),
+                public_key: "024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+                aggregated_public_key: Some([0x07; 32]),
+                message: Some(vec![]),
+                extra_input: Some([0x08; 32]),
+                expected_secnonce: "E862B068500320088138468D47E0E6F147E01B6024244AE45EAC40ACE5929B9F0789E051170B9E705D0B9EB49049A323BBBBB206D8E05C19F46C6228742AA7A9024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+                expected_pubnonce: "023034FA5E2679F01EE66E12225882A7A48CC66719B1B9D3B6C4DBD743EFEDA2C503F3FD6F01EB3A8E9CB315D73F1F3D287CAFBB44AB321153C6287F407600205109",
+            },
+            // 38-byte message
+            TestCase {
+                rand: [0x0F; 32],
+                secret_key: Some("0202020202020202020202020202020202020202020202020202020202020202"),
+                public_key: "024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+                aggregated_public_key: Some([0x07; 32]),
+                message: Some(vec![0x26; 38]),
+                extra_input: Some([0x08; 32]),
+                expected_secnonce: "3221975ACBDEA6820EABF02A02B7F27D3A8EF68EE42787B88CBEFD9AA06AF3632EE85B1A61D8EF31126D4663A00DD96E9D1D4959E72D70FE5EBB6E7696EBA66F024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+                expected_pubnonce: "02E5BBC21C69270F59BD634FCBFA281BE9D76601295345112C58954625BF23793A021307511C79F95D38ACACFF1B4DA98228B77E65AA216AD075E9673286EFB4EAF3",
+            },
+            // Every optional parameter is absent
+            TestCase {
+                rand: [0x0F; 32],
+                secret_key: None,
+                public_key: "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+                aggregated_public_key: None,
+                message: None,
+                extra_input: None,
+                expected_secnonce: "89BDD787D0284E5E4D5FC572E49E316BAB7E21E3B1830DE37DFE80156FA41A6D0B17AE8D024C53679699A6FD7944D9C4A366B514BAF43088E0708B1023DD289702F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+                expected_pubnonce: "02C96E7CB1E8AA5DAC64D872947914198F607D90ECDE5200DE52978AD5DED63C000299EC5117C2D29EDEE8A2092587C3909BE694D5CFF0667D6C02EA4059F7CD9786",
+            },
+        ];
+        for case in &test_cases {
+            let secret_key = case.secret_key.map(scalar);
+            let (secret_nonce, public_nonce) = SecretNonce::generate_with_rand(
+                &case.rand,
+                secret_key.as_ref(),
+                point(case.public_key).unwrap(),
+                case.aggregated_public_key.as_ref(),
+                case.message.as_deref(),
+                case.extra_input
+                    .as_ref()
+                    .map(|extra_input| &extra_input[..]),
+            );
+            assert_eq!(
+                hex::encode_upper(
+                    [
+                        &*secret_nonce.k1.to_bytes(),
+                        &*secret_nonce.k2.to_bytes(),
+                        &*secret_nonce.public_key.to_bytes(true),
+                    ]
+                    .concat()
+                ),
+                case.expected_secnonce
+            );
+            assert_eq!(
+                hex::encode_upper(public_nonce.to_bytes()),
+                case.expected_pubnonce
+            );
+        }
+    }
+
+    #[test]
+    fn sign_and_aggregate() {
+        let message: &[u8] = b"hello";
+        let secret_keys: Vec<Scalar<Secp256k1>> = (0..4).map(|_| Scalar::random()).collect();
+        let mut public_keys: Vec<_> = secret_keys
+            .iter()
+            .map(|sk| Point::generator() * sk)
+            .collect();
+        sort_public_keys(&mut public_keys);
+        let ctx = KeyAggContext::new(public_keys).unwrap();
+
+        let (secret_nonces, public_nonces): (Vec<_>, Vec<_>) = secret_keys
+            .iter()
+            .map(|sk| SecretNonce::generate(sk, Some(&ctx), Some(message)))
+            .unzip();
+        let aggregated_nonce = AggregatedNonce::aggregate(&public_nonces);
+        let session = SigningSession::new(&ctx, &aggregated_nonce, message);
+        let partial_signatures: Vec<_> = secret_keys
+            .iter()
+            .zip(secret_nonces)
+            .map(|(sk, secret_nonce)| session.sign(secret_nonce, sk).unwrap())
+            .collect();
+        for ((partial_signature, public_nonce), sk) in partial_signatures
+            .iter()
+            .zip(&public_nonces)
+            .zip(&secret_keys)
+        {
+            assert!(session
+                .verify_partial_signature(
+                    partial_signature,
+                    public_nonce,
+                    &(Point::generator() * sk)
+                )
+                .is_ok());
+        }
+
+        let signature = session.aggregate(&partial_signatures);
+        assert!(signature.verify(&ctx.x_only_public_key(), message).is_ok());
+
+        // Secret nonce can't be used with a different key
+        let (secret_nonce, _) = SecretNonce::generate(&secret_keys[0], None, None);
+        assert_eq!(
+            session.sign(secret_nonce, &secret_keys[1]),
+            Err(MuSig2Error::MismatchedSecretNonce)
+        );
+    }
+
+    #[test]
+    fn key_aggregation_rejects_invalid_input() {
+        assert_eq!(KeyAggContext::new(vec![]), Err(MuSig2Error::NoPublicKeys));
+        assert_eq!(
+            KeyAggContext::new(vec![Point::generator().to_point(), Point::zero()]),
+            Err(MuSig2Error::InvalidPublicKey(1))
+        );
+    }
+}
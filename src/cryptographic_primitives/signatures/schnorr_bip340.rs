@@ -0,0 +1,655 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Schnorr signatures over secp256k1 as specified in [BIP-340]
+//!
+//! Public keys are x-only: a key is identified by x coordinate of a point, and the point with even
+//! y coordinate is implied. Signature is encoded as 64 bytes `bytes(R) || bytes(s)`.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::signatures::schnorr_bip340::{KeyPair, Signature};
+//!
+//! let key_pair = KeyPair::random();
+//! let signature = Signature::sign(&key_pair, b"hello");
+//! assert!(signature.verify(key_pair.public_key(), b"hello").is_ok());
+//!
+//! // Signature is 64 bytes, public key is 32 bytes
+//! let signature = Signature::from_bytes(&signature.to_bytes()).unwrap();
+//! assert!(signature.verify(key_pair.public_key(), b"hello").is_ok());
+//! ```
+//!
+//! [BIP-340]: https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+
+use std::convert::TryInto;
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::arithmetic::*;
+use crate::cryptographic_primitives::proofs::{batch_verification_weight, BatchVerificationError};
+use crate::elliptic::curves::{Point, Scalar, Secp256k1};
+
+/// Secp256k1 point with even y coordinate identified by its x coordinate
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XOnlyPublicKey {
+    point: Point<Secp256k1>,
+}
+
+impl XOnlyPublicKey {
+    /// Parses 32 bytes public key
+    ///
+    /// Returns error if bytes are not an x coordinate of a curve point.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Bip340Error> {
+        lift_x(bytes)
+            .map(|point| Self { point })
+            .ok_or(Bip340Error::InvalidPublicKey)
+    }
+
+    /// Converts a point into x-only public key
+    ///
+    /// If the point has odd y coordinate, it's negated, so both `P` and `-P` correspond to the same
+    /// public key. Returns error if point is zero.
+    pub fn from_point(point: &Point<Secp256k1>) -> Result<Self, Bip340Error> {
+        if point.is_zero() {
+            return Err(Bip340Error::InvalidPublicKey);
+        }
+        let point = if has_even_y(point) {
+            point.clone()
+        } else {
+            -point
+        };
+        Ok(Self { point })
+    }
+
+    /// Serializes the public key as 32 bytes x coordinate
+    pub fn to_bytes(&self) -> [u8; 32] {
+        x_bytes(&self.point)
+    }
+
+    /// Returns a point with even y coordinate corresponding to this public key
+    pub fn as_point(&self) -> &Point<Secp256k1> {
+        &self.point
+    }
+}
+
+/// Secret key along with its public key
+///
+/// Secret key is normalized so that its public key has even y coordinate.
+#[derive(Clone, Debug)]
+pub struct KeyPair {
+    secret_key: Scalar<Secp256k1>,
+    public_key: XOnlyPublicKey,
+}
+
+impl KeyPair {
+    /// Samples a random key pair
+    pub fn random() -> Self {
+        loop {
+            if let Ok(key_pair) = Self::from_secret_key(Scalar::random()) {
+                return key_pair;
+            }
+        }
+    }
+
+    /// Constructs a key pair from a secret key
+    ///
+    /// Secret key is negated if its public key has odd y coordinate. Returns error if secret key
+    /// is zero.
+    pub fn from_secret_key(secret_key: Scalar<Secp256k1>) -> Result<Self, Bip340Error> {
+        if secret_key.is_zero() {
+            return Err(Bip340Error::ZeroSecretKey);
+        }
+        let public_key = Point::generator() * &secret_key;
+        let secret_key = if has_even_y(&public_key) {
+            secret_key
+        } else {
+            -secret_key
+        };
+        let public_key = Point::generator() * &secret_key;
+        Ok(Self {
+            secret_key,
+            public_key: XOnlyPublicKey { point: public_key },
+        })
+    }
+
+    /// Normalized secret key `d`, such that `d * G` has even y coordinate
+    pub fn secret_key(&self) -> &Scalar<Secp256k1> {
+        &self.secret_key
+    }
+
+    pub fn public_key(&self) -> &XOnlyPublicKey {
+        &self.public_key
+    }
+}
+
+/// BIP-340 signature `(R, s)`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    r: Point<Secp256k1>,
+    s: Scalar<Secp256k1>,
+}
+
+impl Signature {
+    /// Signs a message using fresh auxiliary randomness
+    pub fn sign(key_pair: &KeyPair, message: &[u8]) -> Self {
+        let aux_rand: [u8; 32] = rand::random();
+        Self::sign_with_aux_rand(key_pair, message, &aux_rand)
+    }
+
+    /// Signs a message using given auxiliary randomness
+    ///
+    /// Signing is deterministic: nonce is derived from the secret key, the message, and `aux_rand`.
+    /// Auxiliary randomness is not required for security, but protects against side-channel
+    /// attacks, so it's recommended to use [sign](Self::sign) unless you need reproducible
+    /// signatures.
+    pub fn sign_with_aux_rand(key_pair: &KeyPair, message: &[u8], aux_rand: &[u8; 32]) -> Self {
+        let d = &key_pair.secret_key;
+        let pk_bytes = key_pair.public_key.to_bytes();
+
+        let aux_hash = tagged_hash(b"BIP0340/aux").chain(aux_rand).finalize();
+        let t: Vec<u8> = d
+            .to_bytes()
+            .iter()
+            .zip(aux_hash.iter())
+            .map(|(d_i, aux_i)| d_i ^ aux_i)
+            .collect();
+        let rand = tagged_hash(b"BIP0340/nonce")
+            .chain(&t)
+            .chain(pk_bytes)
+            .chain(message)
+            .finalize();
+
+        let k = Scalar::<Secp256k1>::from_bigint(&BigInt::from_bytes(&rand));
+        // k = 0 happens with negligible probability
+        assert!(!k.is_zero(), "nonce is zero");
+        let r = Point::generator() * &k;
+        let (k, r) = if has_even_y(&r) { (k, r) } else { (-k, -r) };
+
+        let e = challenge(&x_bytes(&r), &pk_bytes, message);
+        let s = k + e * d;
+        Signature { r, s }
+    }
+
+    /// Verifies the signature
+    pub fn verify(&self, public_key: &XOnlyPublicKey, message: &[u8]) -> Result<(), Bip340Error> {
+        let e = challenge(&x_bytes(&self.r), &public_key.to_bytes(), message);
+        let r = Point::generator() * &self.s - &public_key.point * e;
+        if r == self.r {
+            Ok(())
+        } else {
+            Err(Bip340Error::InvalidSignature)
+        }
+    }
+
+    /// Verifies a batch of signatures at once, `signatures[i]` is verified against
+    /// `public_keys[i]` and `messages[i]`
+    ///
+    /// Signatures are checked as a random linear combination `Σ a_i (s_i G - R_i - e_i P_i) = O`
+    /// computed via single multiscalar multiplication. If the batch is invalid, signatures are
+    /// verified individually to find out which of them failed.
+    ///
+    /// ## Panics
+    /// Panics if `public_keys`, `messages` and `signatures` have different lengths.
+    pub fn batch_verify(
+        public_keys: &[XOnlyPublicKey],
+        messages: &[&[u8]],
+        signatures: &[Signature],
+    ) -> Result<(), BatchVerificationError> {
+        assert_eq!(
+            public_keys.len(),
+            signatures.len(),
+            "public_keys and signatures must have the same length"
+        );
+        assert_eq!(
+            messages.len(),
+            signatures.len(),
+            "messages and signatures must have the same length"
+        );
+
+        let mut scalars = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut points = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut generator_scalar = Scalar::zero();
+        for ((public_key, message), signature) in public_keys.iter().zip(messages).zip(signatures) {
+            let weight = batch_verification_weight::<Secp256k1>();
+            let e = challenge(&x_bytes(&signature.r), &public_key.to_bytes(), message);
+            generator_scalar = generator_scalar + &weight * &signature.s;
+            scalars.push(-(&weight * e));
+            points.push(&public_key.point);
+            scalars.push(-weight);
+            points.push(&signature.r);
+        }
+        let generator = Point::<Secp256k1>::generator().to_point();
+        scalars.push(generator_scalar);
+        points.push(&generator);
+
+        if Point::multiscalar_mul(&scalars, points).is_zero() {
+            return Ok(());
+        }
+        BatchVerificationError::find_invalid_proofs(
+            public_keys
+                .iter()
+                .zip(messages)
+                .zip(signatures)
+                .map(|((public_key, message), signature)| signature.verify(public_key, message)),
+        )
+    }
+
+    /// Parses 64 bytes signature
+    ///
+    /// Returns error if `R` is not an x coordinate of a curve point, or if `s` is not less than
+    /// group order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Bip340Error> {
+        if bytes.len() != 64 {
+            return Err(Bip340Error::MalformedSignature);
+        }
+        let r = lift_x(&bytes[..32]).ok_or(Bip340Error::MalformedSignature)?;
+        let s = Scalar::from_bytes(&bytes[32..]).or(Err(Bip340Error::MalformedSignature))?;
+        Ok(Self { r, s })
+    }
+
+    /// Serializes the signature as 64 bytes `bytes(R) || bytes(s)`
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&x_bytes(&self.r));
+        bytes[32..].copy_from_slice(&self.s.to_bytes());
+        bytes
+    }
+
+    /// Nonce point `R`, it always has even y coordinate
+    pub fn r(&self) -> &Point<Secp256k1> {
+        &self.r
+    }
+
+    pub fn s(&self) -> &Scalar<Secp256k1> {
+        &self.s
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum Bip340Error {
+    #[error("public key is not a valid x coordinate")]
+    InvalidPublicKey,
+    #[error("secret key is zero")]
+    ZeroSecretKey,
+    #[error("signature is malformed")]
+    MalformedSignature,
+    #[error("signature is not valid")]
+    InvalidSignature,
+}
+
+/// `hash_tag(x) = SHA256(SHA256(tag) || SHA256(tag) || x)`, returns a hasher with the prefix
+/// already absorbed
+pub(crate) fn tagged_hash(tag: &[u8]) -> Sha256 {
+    let tag_hash = Sha256::digest(tag);
+    Sha256::new().chain(tag_hash).chain(tag_hash)
+}
+
+pub(crate) fn challenge(r: &[u8; 32], public_key: &[u8; 32], message: &[u8]) -> Scalar<Secp256k1> {
+    let e = tagged_hash(b"BIP0340/challenge")
+        .chain(r)
+        .chain(public_key)
+        .chain(message)
+        .finalize();
+    Scalar::from_bigint(&BigInt::from_bytes(&e))
+}
+
+pub(crate) fn has_even_y(point: &Point<Secp256k1>) -> bool {
+    // Compressed encoding of a point with even y coordinate is prefixed with 0x02
+    point.to_bytes(true)[0] == 0x02
+}
+
+pub(crate) fn x_bytes(point: &Point<Secp256k1>) -> [u8; 32] {
+    point.to_bytes(true)[1..]
+        .try_into()
+        .expect("compressed point is 33 bytes")
+}
+
+/// Returns the point with given x coordinate and even y, or `None` if there's no such point
+fn lift_x(x: &[u8]) -> Option<Point<Secp256k1>> {
+    if x.len() != 32 {
+        return None;
+    }
+    let mut compressed = [0u8; 33];
+    compressed[0] = 0x02;
+    compressed[1..].copy_from_slice(x);
+    Point::from_bytes(&compressed).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestVector {
+        index: usize,
+        secret_key: Option<&'static str>,
+        public_key: &'static str,
+        aux_rand: Option<&'static str>,
+        message: &'static str,
+        signature: &'static str,
+        valid: bool,
+    }
+
+    /// Test vectors 0-18 from https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+    const TEST_VECTORS: &[TestVector] = &[
+        TestVector {
+            index: 0,
+            secret_key: Some("0000000000000000000000000000000000000000000000000000000000000003"),
+            public_key: "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            aux_rand: Some("0000000000000000000000000000000000000000000000000000000000000000"),
+            message: "0000000000000000000000000000000000000000000000000000000000000000",
+            signature: "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA8215\
+                        25F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+            valid: true,
+        },
+        TestVector {
+            index: 1,
+            secret_key: Some("B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF"),
+            public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            aux_rand: Some("0000000000000000000000000000000000000000000000000000000000000001"),
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE3341\
+                        8906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+            valid: true,
+        },
+        TestVector {
+            index: 2,
+            secret_key: Some("C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9"),
+            public_key: "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+            aux_rand: Some("C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906"),
+            message: "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+            signature: "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1B\
+                        AB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7",
+            valid: true,
+        },
+        // test fails if msg is reduced modulo p or n
+        TestVector {
+            index: 3,
+            secret_key: Some("0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710"),
+            public_key: "25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+            aux_rand: Some("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"),
+            message: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+            signature: "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC\
+                        97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3",
+            valid: true,
+        },
+        TestVector {
+            index: 4,
+            secret_key: None,
+            public_key: "D69C3509BB99E412E68B0FE8544E72837DFA30746D8BE2AA65975F29D22DC7B9",
+            aux_rand: None,
+            message: "4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703",
+            signature: "00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C63\
+                        76AFB1548AF603B3EB45C9F8207DEE1060CB71C04E80F593060B07D28308D7F4",
+            valid: true,
+        },
+        // public key not on the curve
+        TestVector {
+            index: 5,
+            secret_key: None,
+            public_key: "EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769\
+                        69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            valid: false,
+        },
+        // has_even_y(R) is false
+        TestVector {
+            index: 6,
+            secret_key: None,
+            public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A1460297556\
+                        3CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2",
+            valid: false,
+        },
+        // negated message
+        TestVector {
+            index: 7,
+            secret_key: None,
+            public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "1FA62E331EDBC21C394792D2AB1100A7B432B013DF3F6FF4F99FCB33E0E1515F\
+                        28890B3EDB6E7189B630448B515CE4F8622A954CFE545735AAEA5134FCCDB2BD",
+            valid: false,
+        },
+        // negated s value
+        TestVector {
+            index: 8,
+            secret_key: None,
+            public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769\
+                        961764B3AA9B2FFCB6EF947B6887A226E8D7C93E00C5ED0C1834FF0D0C2E6DA6",
+            valid: false,
+        },
+        // sG - eP is infinite, test fails in single verification if has_even_y(inf) is defined
+        // as true and x(inf) as 0
+        TestVector {
+            index: 9,
+            secret_key: None,
+            public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "0000000000000000000000000000000000000000000000000000000000000000\
+                        123DDA8328AF9C23A94C1FEECFD123BA4FB73476F0D594DCB65C6425BD186051",
+            valid: false,
+        },
+        // sG - eP is infinite, test fails in single verification if has_even_y(inf) is defined
+        // as true and x(inf) as 1
+        TestVector {
+            index: 10,
+            secret_key: None,
+            public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "0000000000000000000000000000000000000000000000000000000000000001\
+                        7615FBAF5AE28864013C099742DEADB4DBA87F11AC6754F93780D5A1837CF197",
+            valid: false,
+        },
+        // sig[0:32] is not an X coordinate on the curve
+        TestVector {
+            index: 11,
+            secret_key: None,
+            public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "4A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D\
+                        69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            valid: false,
+        },
+        // sig[0:32] is equal to field size
+        TestVector {
+            index: 12,
+            secret_key: None,
+            public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F\
+                        69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            valid: false,
+        },
+        // sig[32:64] is equal to curve order
+        TestVector {
+            index: 13,
+            secret_key: None,
+            public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769\
+                        FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            valid: false,
+        },
+        // public key is not a valid X coordinate because it exceeds the field size
+        TestVector {
+            index: 14,
+            secret_key: None,
+            public_key: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+            aux_rand: None,
+            message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            signature: "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769\
+                        69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            valid: false,
+        },
+        // message of size 0
+        TestVector {
+            index: 15,
+            secret_key: Some("0340034003400340034003400340034003400340034003400340034003400340"),
+            public_key: "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+            aux_rand: Some("0000000000000000000000000000000000000000000000000000000000000000"),
+            message: "",
+            signature: "71535DB165ECD9FBBC046E5FFAEA61186BB6AD436732FCCC25291A55895464CF\
+                        6069CE26BF03466228F19A3A62DB8A649F2D560FAC652827D1AF0574E427AB63",
+            valid: true,
+        },
+        // message of size 1
+        TestVector {
+            index: 16,
+            secret_key: Some("0340034003400340034003400340034003400340034003400340034003400340"),
+            public_key: "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+            aux_rand: Some("0000000000000000000000000000000000000000000000000000000000000000"),
+            message: "11",
+            signature: "08A20A0AFEF64124649232E0693C583AB1B9934AE63B4C3511F3AE1134C6A303\
+                        EA3173BFEA6683BD101FA5AA5DBC1996FE7CACFC5A577D33EC14564CEC2BACBF",
+            valid: true,
+        },
+        // message of size 17
+        TestVector {
+            index: 17,
+            secret_key: Some("0340034003400340034003400340034003400340034003400340034003400340"),
+            public_key: "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+            aux_rand: Some("0000000000000000000000000000000000000000000000000000000000000000"),
+            message: "0102030405060708090A0B0C0D0E0F1011",
+            signature: "5130F39A4059B43BC7CAC09A19ECE52B5D8699D1A71E3C52DA9AFDB6B50AC370\
+                        C4A482B77BF960F8681540E25B6771ECE1E5A37FD80E5A51897C5566A97EA5A5",
+            valid: true,
+        },
+        // message of size 100
+        TestVector {
+            index: 18,
+            secret_key: Some("0340034003400340034003400340034003400340034003400340034003400340"),
+            public_key: "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+            aux_rand: Some("0000000000000000000000000000000000000000000000000000000000000000"),
+            message: "9999999999999999999999999999999999999999999999999999999999999999\
+                      9999999999999999999999999999999999999999999999999999999999999999\
+                      9999999999999999999999999999999999999999999999999999999999999999\
+                      99999999",
+            signature: "403B12B0D8555A344175EA7EC746566303321E5DBFA8BE6F091635163ECA79A8\
+                        585ED3E3170807E7C03B720FC54C7B23897FCBA0E9D0B4A06894CFD249F22367",
+            valid: true,
+        },
+    ];
+
+    fn decode(hex_str: &str) -> Vec<u8> {
+        let hex_str: String = hex_str.split_whitespace().collect();
+        hex::decode(hex_str).unwrap()
+    }
+
+    fn secret_key(hex_str: &str) -> KeyPair {
+        let secret_key = Scalar::from_bytes(&decode(hex_str)).unwrap();
+        KeyPair::from_secret_key(secret_key).unwrap()
+    }
+
+    fn verify_bytes(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        match (
+            XOnlyPublicKey::from_bytes(public_key),
+            Signature::from_bytes(signature),
+        ) {
+            (Ok(public_key), Ok(signature)) => signature.verify(&public_key, message).is_ok(),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn bip340_test_vectors() {
+        for vector in TEST_VECTORS {
+            let public_key = decode(vector.public_key);
+            let message = decode(vector.message);
+            let signature = decode(vector.signature);
+
+            if let (Some(sk), Some(aux_rand)) = (vector.secret_key, vector.aux_rand) {
+                let key_pair = secret_key(sk);
+                assert_eq!(
+                    key_pair.public_key().to_bytes()[..],
+                    public_key[..],
+                    "wrong public key for index {}",
+                    vector.index
+                );
+                let aux_rand = decode(aux_rand).try_into().unwrap();
+                let sig = Signature::sign_with_aux_rand(&key_pair, &message, &aux_rand);
+                assert_eq!(
+                    sig.to_bytes()[..],
+                    signature[..],
+                    "wrong signature for index {}",
+                    vector.index
+                );
+            }
+
+            assert_eq!(
+                verify_bytes(&public_key, &message, &signature),
+                vector.valid,
+                "wrong verification result for index {}",
+                vector.index
+            );
+        }
+    }
+
+    #[test]
+    fn sign_verify() {
+        let key_pair = KeyPair::random();
+        let signature = Signature::sign(&key_pair, b"message");
+        assert!(signature.verify(key_pair.public_key(), b"message").is_ok());
+        assert_eq!(
+            signature.verify(key_pair.public_key(), b"another message"),
+            Err(Bip340Error::InvalidSignature)
+        );
+        assert_eq!(
+            signature.verify(KeyPair::random().public_key(), b"message"),
+            Err(Bip340Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn secret_key_is_normalized() {
+        let key_pair = KeyPair::random();
+        let negated = KeyPair::from_secret_key(-key_pair.secret_key()).unwrap();
+        assert_eq!(key_pair.secret_key(), negated.secret_key());
+        assert_eq!(key_pair.public_key(), negated.public_key());
+        assert_eq!(
+            XOnlyPublicKey::from_point(&-key_pair.public_key().as_point()).unwrap(),
+            *key_pair.public_key()
+        );
+    }
+
+    #[test]
+    fn batch_verify() {
+        let key_pairs: Vec<_> = (0..10).map(|_| KeyPair::random()).collect();
+        let public_keys: Vec<_> = key_pairs.iter().map(|k| k.public_key().clone()).collect();
+        let messages: Vec<Vec<u8>> = (0..10u8).map(|i| vec![i; i as usize]).collect();
+        let messages: Vec<&[u8]> = messages.iter().map(|m| m.as_slice()).collect();
+        let mut signatures: Vec<_> = key_pairs
+            .iter()
+            .zip(&messages)
+            .map(|(key_pair, message)| Signature::sign(key_pair, message))
+            .collect();
+        assert!(Signature::batch_verify(&[], &[], &[]).is_ok());
+        assert!(Signature::batch_verify(&public_keys, &messages, &signatures).is_ok());
+
+        signatures[1].s = Scalar::random();
+        signatures.swap(4, 8);
+        assert_eq!(
+            Signature::batch_verify(&public_keys, &messages, &signatures),
+            Err(BatchVerificationError {
+                invalid_proofs: vec![1, 4, 8]
+            })
+        );
+    }
+}
@@ -0,0 +1,325 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Threshold BLS signatures
+//!
+//! Secret key is shared among `n` parties via [VerifiableSS] over G2, so public key and public
+//! shares are in G2, and signatures are in G1 ([MinSig] variant). Any `t+1` parties can sign a
+//! message: each of them produces a [PartialSignature], which can be verified against public
+//! share [commitment](VerifiableSS::get_point_commitment), and then partial signatures are
+//! [combined](combine) into a regular BLS signature via Lagrange interpolation in the exponent.
+//!
+//! Parties are identified by indexes `1..=n`, i.e. party with index `i` holds share `f(i)` of
+//! secret key `f(0)`.
+//!
+//! ## Example
+//! ```rust
+//! use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+//! use curv::cryptographic_primitives::signatures::threshold_bls::{combine, KeyShare};
+//! use curv::elliptic::curves::{Bls12_381_2, Scalar};
+//! use sha2::Sha256;
+//!
+//! let secret_key = Scalar::<Bls12_381_2>::random();
+//! let (vss, shares) = VerifiableSS::<Bls12_381_2, Sha256>::share(1, 3, &secret_key);
+//! let key_shares: Vec<_> = (1..=3)
+//!     .map(|i| KeyShare::new(i, shares[usize::from(i) - 1].clone(), vss.clone()).unwrap())
+//!     .collect();
+//!
+//! // Parties 1 and 3 sign the message
+//! let partial_signatures: Vec<_> = [&key_shares[0], &key_shares[2]]
+//!     .iter()
+//!     .map(|key_share| key_share.partial_sign(b"hello"))
+//!     .collect();
+//! for partial_signature in &partial_signatures {
+//!     assert!(partial_signature.verify(&vss, b"hello").is_ok());
+//! }
+//!
+//! let signature = combine(&vss, &partial_signatures).unwrap();
+//! let public_key = key_shares[0].public_key().unwrap();
+//! assert!(signature.verify(&public_key, b"hello").is_ok());
+//! ```
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::cryptographic_primitives::hashing::Digest;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::cryptographic_primitives::secret_sharing::Polynomial;
+use crate::cryptographic_primitives::signatures::bls::{BlsVariant, MinSig, PublicKey, Signature};
+use crate::elliptic::curves::{Bls12_381_1, Bls12_381_2, Point, Scalar};
+
+/// Share of BLS secret key held by party `index`
+#[derive(Clone)]
+pub struct KeyShare<H: Digest + Clone> {
+    index: u16,
+    secret_share: Scalar<Bls12_381_2>,
+    vss: VerifiableSS<Bls12_381_2, H>,
+}
+
+/// Signature of a message produced by a single party
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartialSignature {
+    /// Index of the party that produced the signature
+    pub index: u16,
+    /// Signature `x_i * H(m)`, where `x_i` is party's secret share
+    pub signature: Signature<MinSig>,
+}
+
+impl<H: Digest + Clone> KeyShare<H> {
+    /// Constructs a key share of party `index` (`1..=n`)
+    ///
+    /// Returns error if the share doesn't match VSS commitments.
+    pub fn new(
+        index: u16,
+        secret_share: Scalar<Bls12_381_2>,
+        vss: VerifiableSS<Bls12_381_2, H>,
+    ) -> Result<Self, ThresholdBlsError> {
+        if index == 0 || index > vss.parameters.share_count {
+            return Err(ThresholdBlsError::InvalidIndex(index));
+        }
+        vss.validate_share(&secret_share, index)
+            .or(Err(ThresholdBlsError::InvalidShare))?;
+        Ok(Self {
+            index,
+            secret_share,
+            vss,
+        })
+    }
+
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    pub fn secret_share(&self) -> &Scalar<Bls12_381_2> {
+        &self.secret_share
+    }
+
+    pub fn vss(&self) -> &VerifiableSS<Bls12_381_2, H> {
+        &self.vss
+    }
+
+    /// Shared public key
+    ///
+    /// Returns error if public key is zero.
+    pub fn public_key(&self) -> Result<PublicKey<MinSig>, ThresholdBlsError> {
+        PublicKey::from_point(self.vss.commitments[0].clone())
+            .or(Err(ThresholdBlsError::InvalidPublicKey))
+    }
+
+    /// Signs a message with the secret share
+    pub fn partial_sign(&self, message: &[u8]) -> PartialSignature {
+        let share = Scalar::<Bls12_381_1>::from_raw(self.secret_share.as_raw().clone());
+        let hash = Point::<Bls12_381_1>::hash_to_curve(message, MinSig::SIGNATURE_DST);
+        PartialSignature {
+            index: self.index,
+            signature: Signature::from_point(hash * share),
+        }
+    }
+}
+
+impl PartialSignature {
+    /// Verifies partial signature against public share of the party
+    ///
+    /// Checks `e(σ_i, G2) = e(H(m), X_i)`, where `X_i` is public share derived from VSS
+    /// commitments.
+    pub fn verify<H: Digest + Clone>(
+        &self,
+        vss: &VerifiableSS<Bls12_381_2, H>,
+        message: &[u8],
+    ) -> Result<(), ThresholdBlsError> {
+        if self.index == 0 || self.index > vss.parameters.share_count {
+            return Err(ThresholdBlsError::InvalidIndex(self.index));
+        }
+        let public_share = PublicKey::from_point(vss.get_point_commitment(self.index))
+            .or(Err(ThresholdBlsError::InvalidPartialSignature(self.index)))?;
+        self.signature
+            .verify(&public_share, message)
+            .or(Err(ThresholdBlsError::InvalidPartialSignature(self.index)))
+    }
+}
+
+/// Combines partial signatures of at least `t+1` distinct parties into BLS signature
+///
+/// Partial signatures are not verified by this function: make sure to [verify] them beforehand,
+/// or verify the resulting signature against the shared public key.
+///
+/// [verify]: PartialSignature::verify
+pub fn combine<H: Digest + Clone>(
+    vss: &VerifiableSS<Bls12_381_2, H>,
+    partial_signatures: &[PartialSignature],
+) -> Result<Signature<MinSig>, ThresholdBlsError> {
+    let required = usize::from(vss.reconstruct_limit());
+    if partial_signatures.len() < required {
+        return Err(ThresholdBlsError::NotEnoughPartialSignatures {
+            required,
+            received: partial_signatures.len(),
+        });
+    }
+    for (i, partial_signature) in partial_signatures.iter().enumerate() {
+        let index = partial_signature.index;
+        if index == 0 || index > vss.parameters.share_count {
+            return Err(ThresholdBlsError::InvalidIndex(index));
+        }
+        if partial_signatures[..i].iter().any(|s| s.index == index) {
+            return Err(ThresholdBlsError::DuplicateIndex(index));
+        }
+    }
+
+    let xs: Vec<Scalar<Bls12_381_2>> = partial_signatures
+        .iter()
+        .map(|s| Scalar::from(s.index))
+        .collect();
+    let lambdas: Vec<Scalar<Bls12_381_1>> = (0..)
+        .zip(&xs)
+        .map(|(j, _)| {
+            let lambda = Polynomial::lagrange_basis(&Scalar::zero(), j, &xs);
+            Scalar::from_raw(lambda.into_raw())
+        })
+        .collect();
+    let signature = Point::multiscalar_mul(
+        &lambdas,
+        partial_signatures.iter().map(|s| s.signature.as_point()),
+    );
+    Ok(Signature::from_point(signature))
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ThresholdBlsError {
+    #[error("party index {0} is out of range")]
+    InvalidIndex(u16),
+    #[error("secret share doesn't match VSS commitments")]
+    InvalidShare,
+    #[error("shared public key is zero")]
+    InvalidPublicKey,
+    #[error("partial signature of party {0} is not valid")]
+    InvalidPartialSignature(u16),
+    #[error("party {0} appears more than once")]
+    DuplicateIndex(u16),
+    #[error("expected at least {required} partial signatures, received {received}")]
+    NotEnoughPartialSignatures { required: usize, received: usize },
+}
+
+impl<H: Digest + Clone> fmt::Debug for KeyShare<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // blind sensitive data stored by the structure
+        write!(f, "KeyShare {{ index: {}, .. }}", self.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sha2::Sha256;
+
+    fn keygen(t: u16, n: u16) -> (Scalar<Bls12_381_2>, Vec<KeyShare<Sha256>>) {
+        let secret_key = Scalar::random();
+        let (vss, shares) = VerifiableSS::share(t, n, &secret_key);
+        let key_shares = (1..=n)
+            .zip(shares.iter())
+            .map(|(i, share)| KeyShare::new(i, share.clone(), vss.clone()).unwrap())
+            .collect();
+        (secret_key, key_shares)
+    }
+
+    #[test]
+    fn any_t_plus_one_parties_can_sign() {
+        let (t, n) = (2, 5);
+        let (secret_key, key_shares) = keygen(t, n);
+        let vss = key_shares[0].vss();
+        let public_key = key_shares[0].public_key().unwrap();
+        assert_eq!(public_key.as_point(), &(Point::generator() * &secret_key));
+
+        let message = b"hello";
+        let partial_signatures: Vec<_> = key_shares
+            .iter()
+            .map(|key_share| key_share.partial_sign(message))
+            .collect();
+        for partial_signature in &partial_signatures {
+            assert!(partial_signature.verify(vss, message).is_ok());
+        }
+
+        let signers: &[&[usize]] = &[&[0, 1, 2], &[4, 2, 0], &[1, 3, 4], &[0, 1, 2, 3, 4]];
+        let expected = Point::<Bls12_381_1>::hash_to_curve(message, MinSig::SIGNATURE_DST)
+            * Scalar::from_raw(secret_key.as_raw().clone());
+        for signers in signers {
+            let partial_signatures: Vec<_> = signers
+                .iter()
+                .map(|&i| partial_signatures[i].clone())
+                .collect();
+            let signature = combine(vss, &partial_signatures).unwrap();
+            assert!(signature.verify(&public_key, message).is_ok());
+            assert_eq!(signature.as_point(), &expected);
+        }
+    }
+
+    #[test]
+    fn invalid_partial_signature_is_detected() {
+        let (_, key_shares) = keygen(1, 3);
+        let vss = key_shares[0].vss();
+
+        let mut partial_signature = key_shares[1].partial_sign(b"hello");
+        assert_eq!(
+            partial_signature.verify(vss, b"hello!"),
+            Err(ThresholdBlsError::InvalidPartialSignature(2))
+        );
+        partial_signature.index = 3;
+        assert_eq!(
+            partial_signature.verify(vss, b"hello"),
+            Err(ThresholdBlsError::InvalidPartialSignature(3))
+        );
+        partial_signature.index = 4;
+        assert_eq!(
+            partial_signature.verify(vss, b"hello"),
+            Err(ThresholdBlsError::InvalidIndex(4))
+        );
+    }
+
+    #[test]
+    fn combine_rejects_malformed_input() {
+        let (_, key_shares) = keygen(2, 4);
+        let vss = key_shares[0].vss();
+        let partial_signatures: Vec<_> = key_shares
+            .iter()
+            .map(|key_share| key_share.partial_sign(b"hello"))
+            .collect();
+
+        assert_eq!(
+            combine(vss, &partial_signatures[..2]),
+            Err(ThresholdBlsError::NotEnoughPartialSignatures {
+                required: 3,
+                received: 2
+            })
+        );
+        let duplicated = vec![
+            partial_signatures[0].clone(),
+            partial_signatures[1].clone(),
+            partial_signatures[0].clone(),
+        ];
+        assert_eq!(
+            combine(vss, &duplicated),
+            Err(ThresholdBlsError::DuplicateIndex(1))
+        );
+    }
+
+    #[test]
+    fn invalid_share_is_rejected() {
+        let (_, key_shares) = keygen(1, 3);
+        let key_share = &key_shares[0];
+        assert_eq!(
+            KeyShare::new(2, key_share.secret_share().clone(), key_share.vss().clone())
+                .unwrap_err(),
+            ThresholdBlsError::InvalidShare
+        );
+        assert_eq!(
+            KeyShare::new(0, key_share.secret_share().clone(), key_share.vss().clone())
+                .unwrap_err(),
+            ThresholdBlsError::InvalidIndex(0)
+        );
+    }
+}
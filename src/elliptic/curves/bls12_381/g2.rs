@@ -76,6 +76,30 @@ impl Curve for Bls12_381_2 {
     const CURVE_NAME: &'static str = "bls12_381_1";
 }
 
+impl crate::elliptic::curves::traits::HashToCurve for Bls12_381_2 {
+    const SUITE_ID: &'static str = "BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> G2Point {
+        // pairing_plus implements outdated draft of hash-to-curve which isn't compatible with
+        // RFC 9380, so we hash the message using `bls12_381` crate and convert the result
+        use ::bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+        let point =
+            <::bls12_381::G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(
+                msg, dst,
+            );
+        let mut uncompressed = G2Uncompressed::empty();
+        uncompressed
+            .as_mut()
+            .copy_from_slice(&::bls12_381::G2Affine::from(point).to_uncompressed());
+        G2Point {
+            purpose: "hash_to_curve",
+            ge: uncompressed
+                .into_affine()
+                .expect("bls12_381 crate must output a valid point"),
+        }
+    }
+}
+
 impl ECPoint for G2Point {
     type Scalar = FieldScalar;
     type Underlying = PK;
@@ -231,6 +255,24 @@ impl ECPoint for G2Point {
         self.ge.negate();
     }
 
+    fn multiscalar_mul(scalars: &[Self::Scalar], points: &[Self]) -> G2Point {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "scalars and points must have the same length"
+        );
+        let bases = points.iter().map(|p| p.ge).collect::<Vec<_>>();
+        let scalars = scalars
+            .iter()
+            .map(|s| s.underlying_ref().into_repr())
+            .collect::<Vec<_>>();
+        let scalars = scalars.iter().map(|s| &s.0).collect::<Vec<_>>();
+        G2Point {
+            purpose: "multiscalar_mul",
+            ge: PK::sum_of_products(&bases, &scalars).into_affine(),
+        }
+    }
+
     fn underlying_ref(&self) -> &Self::Underlying {
         &self.ge
     }
@@ -55,6 +55,34 @@ impl Pair {
         }
     }
 
+    /// Computes product of pairings `e(p1_1, p2_1) * ... * e(p1_n, p2_n)` with a single final
+    /// exponentiation
+    ///
+    /// ## Panic
+    /// Method panics if miller_loop of product is equal to zero.
+    pub fn compute_pairing_product<'p>(
+        pairs: impl IntoIterator<Item = (&'p Point<Bls12_381_1>, &'p Point<Bls12_381_2>)>,
+    ) -> Self {
+        let prepared: Vec<_> = pairs
+            .into_iter()
+            .map(|(p1, p2)| {
+                (
+                    p1.as_raw().underlying_ref().prepare(),
+                    p2.as_raw().underlying_ref().prepare(),
+                )
+            })
+            .collect();
+        let refs: Vec<_> = prepared.iter().map(|(p1, p2)| (p1, p2)).collect();
+        Pair {
+            e: Bls12::final_exponentiation(&Bls12::miller_loop(refs.iter())).unwrap(),
+        }
+    }
+
+    /// Checks whether the pairing is an identity element of target group
+    pub fn is_one(&self) -> bool {
+        self.e == Fq12::one()
+    }
+
     pub fn add_pair(&self, other: &Pair) -> Self {
         let mut res = *self;
         res.e.mul_assign(&other.e);
@@ -93,4 +121,19 @@ mod tests {
         let e_p_q_add_e_p_r = e_p_q.add_pair(&e_p_r);
         assert_eq!(e_p_q_add_e_p_r, e_p_q_r);
     }
+
+    // e(P,Q)e(-P,Q) = 1
+    #[test]
+    fn pairing_product() {
+        let p = Point::<Bls12_381_1>::generator().to_point();
+        let q = Point::<Bls12_381_2>::generator().to_point();
+        let r = Point::<Bls12_381_2>::base_point2();
+        let product = Pair::compute_pairing_product(vec![(&p, &q), (&p, r)]);
+        assert_eq!(
+            product,
+            Pair::compute_pairing(&p, &q).add_pair(&Pair::compute_pairing(&p, r))
+        );
+        assert!(!product.is_one());
+        assert!(Pair::compute_pairing_product(vec![(&p, &q), (&-&p, &q)]).is_one());
+    }
 }
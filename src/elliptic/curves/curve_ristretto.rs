@@ -12,13 +12,14 @@ use std::sync::atomic;
 
 use curve25519_dalek::constants::{BASEPOINT_ORDER, RISTRETTO_BASEPOINT_POINT};
 use curve25519_dalek::ristretto::CompressedRistretto;
-use curve25519_dalek::traits::{Identity, IsIdentity};
+use curve25519_dalek::traits::{Identity, IsIdentity, VartimeMultiscalarMul};
 use generic_array::GenericArray;
 use rand::thread_rng;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha512};
 use zeroize::{Zeroize, Zeroizing};
 
 use crate::arithmetic::*;
+use crate::cryptographic_primitives::hashing::expand_message_xmd;
 use crate::elliptic::curves::traits::*;
 
 use super::traits::{ECPoint, ECScalar};
@@ -83,6 +84,21 @@ impl Curve for Ristretto {
     const CURVE_NAME: &'static str = "ristretto";
 }
 
+impl HashToCurve for Ristretto {
+    const SUITE_ID: &'static str = "ristretto255_XMD:SHA-512_R255MAP_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> RistrettoPoint {
+        let uniform_bytes: [u8; 64] = expand_message_xmd::<Sha512>(msg, dst, 64)
+            .expect("output length is valid for expand_message_xmd")
+            .try_into()
+            .expect("expand_message_xmd returned 64 bytes");
+        RistrettoPoint {
+            purpose: "hash_to_curve",
+            ge: PK::from_uniform_bytes(&uniform_bytes),
+        }
+    }
+}
+
 impl ECScalar for RistrettoScalar {
     type Underlying = SK;
 
@@ -314,6 +330,21 @@ impl ECPoint for RistrettoPoint {
         }
     }
 
+    fn multiscalar_mul(scalars: &[Self::Scalar], points: &[Self]) -> RistrettoPoint {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "scalars and points must have the same length"
+        );
+        RistrettoPoint {
+            purpose: "multiscalar_mul",
+            ge: PK::vartime_multiscalar_mul(
+                scalars.iter().map(|s| &*s.fe),
+                points.iter().map(|p| &p.ge),
+            ),
+        }
+    }
+
     fn scalar_mul_assign(&mut self, scalar: &Self::Scalar) {
         self.ge *= &*scalar.fe
     }
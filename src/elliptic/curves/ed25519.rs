@@ -10,15 +10,20 @@
 // https://cr.yp.to/ecdh/curve25519-20060209.pdf
 
 use super::{
-    traits::{ECPoint, ECScalar},
+    hash_to_curve::{Elligator2, PrimeField},
+    traits::{ECPoint, ECScalar, HashToCurve},
     Curve, DeserializationError, NotOnCurve, PointCoords,
 };
-use crate::{arithmetic::traits::*, cryptographic_primitives::hashing::Digest, BigInt};
+use crate::{
+    arithmetic::traits::*,
+    cryptographic_primitives::hashing::{hash_to_field, Digest},
+    BigInt,
+};
 use curve25519_dalek::{
     constants,
     edwards::{CompressedEdwardsY, EdwardsPoint},
     scalar::Scalar,
-    traits::Identity,
+    traits::{Identity, VartimeMultiscalarMul},
 };
 use generic_array::GenericArray;
 use std::{convert::TryInto, ptr, str, sync::atomic};
@@ -48,6 +53,20 @@ lazy_static::lazy_static! {
             ge: p * eight,
         }
     };
+
+    /// Elligator 2 map to curve25519 (RFC 9380, section 6.7.1)
+    static ref ELLIGATOR2: Elligator2 = Elligator2 {
+        field: PrimeField::new(BigInt::from(2).pow(255) - BigInt::from(19)),
+        j: BigInt::from(486662),
+        z: BigInt::from(2),
+    };
+
+    /// `sqrt(-486664)` with `sgn0 = 0`, used in rational map from curve25519 to edwards25519
+    static ref SQRT_MINUS_486664: BigInt = {
+        let f = &ELLIGATOR2.field;
+        let root = f.sqrt(&f.neg(&BigInt::from(486664))).expect("-486664 is a square");
+        if PrimeField::sgn0(&root) { f.neg(&root) } else { root }
+    };
 }
 
 const GENERATOR: Ed25519Point = Ed25519Point {
@@ -97,6 +116,49 @@ impl Curve for Ed25519 {
     const CURVE_NAME: &'static str = "ed25519";
 }
 
+impl HashToCurve for Ed25519 {
+    const SUITE_ID: &'static str = "edwards25519_XMD:SHA-512_ELL2_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Ed25519Point {
+        let u = hash_to_field::<sha2::Sha512>(msg, dst, ELLIGATOR2.field.modulus(), 2)
+            .expect("output length is valid for expand_message_xmd");
+        let q = map_to_curve(&u[0]).ge + map_to_curve(&u[1]).ge;
+        Ed25519Point {
+            purpose: "hash_to_curve",
+            ge: q.mul_by_cofactor(),
+        }
+    }
+}
+
+/// Maps field element to edwards25519 point via Elligator 2 and rational map from curve25519
+/// (RFC 9380, Appendix D.1)
+///
+/// Resulting point isn't guaranteed to be in prime order subgroup.
+pub(super) fn map_to_curve(u: &BigInt) -> Ed25519Point {
+    let f = &ELLIGATOR2.field;
+    let (s, t) = ELLIGATOR2.map_to_curve(u);
+    let s_plus_1 = f.add(&s, &BigInt::one());
+    if t == BigInt::zero() || s_plus_1 == BigInt::zero() {
+        return Ed25519Point::zero();
+    }
+    let x = f.mul(&SQRT_MINUS_486664, &f.mul(&s, &f.inv0(&t)));
+    let y = f.mul(&f.sub(&s, &BigInt::one()), &f.inv0(&s_plus_1));
+
+    // Encode the point as described in RFC 8032, section 5.1.2 to avoid running `xrecover`
+    let mut encoded = y
+        .to_bytes_array::<32>()
+        .expect("y is less than field modulus");
+    encoded.reverse();
+    encoded[31] |= (x.is_odd() as u8) << 7;
+    let ge = CompressedEdwardsY(encoded)
+        .decompress()
+        .expect("point must be on curve");
+    Ed25519Point {
+        purpose: "map_to_curve",
+        ge,
+    }
+}
+
 impl ECScalar for Ed25519Scalar {
     type Underlying = SK;
 
@@ -377,6 +439,21 @@ impl ECPoint for Ed25519Point {
         }
     }
 
+    fn multiscalar_mul(scalars: &[Self::Scalar], points: &[Self]) -> Self {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "scalars and points must have the same length"
+        );
+        Ed25519Point {
+            purpose: "multiscalar_mul",
+            ge: PK::vartime_multiscalar_mul(
+                scalars.iter().map(|s| &*s.fe),
+                points.iter().map(|p| &p.ge),
+            ),
+        }
+    }
+
     fn underlying_ref(&self) -> &Self::Underlying {
         &self.ge
     }
@@ -0,0 +1,586 @@
+//! Building blocks of [RFC 9380] hash-to-curve suites
+//!
+//! Libraries backing secp256k1, p256 and ed25519 curves don't expose arithmetic of the base field,
+//! so mappings are implemented on top of [BigInt]. They are not constant time, which is fine as
+//! hashed messages are considered public.
+//!
+//! [RFC 9380]: https://www.rfc-editor.org/rfc/rfc9380.html
+
+use crate::arithmetic::*;
+
+/// Prime field $\mathbb{Z}_p$
+///
+/// Only fields with `p = 3 (mod 4)` or `p = 5 (mod 8)` are supported, for which square roots are
+/// computed by a single exponentiation.
+pub(crate) struct PrimeField {
+    p: BigInt,
+    p_minus_2: BigInt,
+    p_minus_1_div_2: BigInt,
+    sqrt_exp: BigInt,
+    /// $\sqrt{-1}$, used only if `p = 5 (mod 8)`
+    sqrt_minus_one: Option<BigInt>,
+}
+
+impl PrimeField {
+    pub fn new(p: BigInt) -> Self {
+        let one = BigInt::one();
+        let p_minus_1_div_2 = (&p - &one) / BigInt::from(2);
+        let (sqrt_exp, sqrt_minus_one) = if p.modulus(&BigInt::from(4)) == BigInt::from(3) {
+            ((&p + &one) / BigInt::from(4), None)
+        } else if p.modulus(&BigInt::from(8)) == BigInt::from(5) {
+            let sqrt_minus_one =
+                BigInt::mod_pow(&BigInt::from(2), &((&p - &one) / BigInt::from(4)), &p);
+            (
+                (&p + BigInt::from(3)) / BigInt::from(8),
+                Some(sqrt_minus_one),
+            )
+        } else {
+            panic!("only p = 3 (mod 4) and p = 5 (mod 8) fields are supported")
+        };
+        Self {
+            p_minus_2: &p - BigInt::from(2),
+            p_minus_1_div_2,
+            sqrt_exp,
+            sqrt_minus_one,
+            p,
+        }
+    }
+
+    pub fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+
+    pub fn add(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::mod_add(a, b, &self.p)
+    }
+
+    pub fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::mod_sub(a, b, &self.p)
+    }
+
+    pub fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::mod_mul(a, b, &self.p)
+    }
+
+    pub fn neg(&self, a: &BigInt) -> BigInt {
+        BigInt::mod_sub(&BigInt::zero(), a, &self.p)
+    }
+
+    /// Computes $a^{-1}$, or zero if `a` is zero
+    pub fn inv0(&self, a: &BigInt) -> BigInt {
+        BigInt::mod_pow(a, &self.p_minus_2, &self.p)
+    }
+
+    pub fn is_square(&self, a: &BigInt) -> bool {
+        let legendre = BigInt::mod_pow(a, &self.p_minus_1_div_2, &self.p);
+        legendre == BigInt::zero() || legendre == BigInt::one()
+    }
+
+    /// Returns any square root of `a`, or `None` if `a` isn't a square
+    pub fn sqrt(&self, a: &BigInt) -> Option<BigInt> {
+        let mut root = BigInt::mod_pow(a, &self.sqrt_exp, &self.p);
+        if let Some(sqrt_minus_one) = &self.sqrt_minus_one {
+            if self.mul(&root, &root) != a.modulus(&self.p) {
+                root = self.mul(&root, sqrt_minus_one);
+            }
+        }
+        if self.mul(&root, &root) == a.modulus(&self.p) {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    /// Sign of field element as defined in [RFC 9380, section 4.1](https://www.rfc-editor.org/rfc/rfc9380.html#name-the-sgn0-function)
+    pub fn sgn0(a: &BigInt) -> bool {
+        a.test_bit(0)
+    }
+}
+
+/// Simplified Shallue-van de Woestijne-Ulas method mapping field elements to points of
+/// $y^2 = x^3 + Ax + B$
+///
+/// Implements `map_to_curve_simple_swu` defined in [RFC 9380, section 6.6.2][sswu]. `A` and `B`
+/// must be non-zero, so curves like secp256k1 are mapped through isogenous curve
+/// (see [IsogenyMap]).
+///
+/// [sswu]: https://www.rfc-editor.org/rfc/rfc9380.html#name-simplified-shallue-van-de-w
+pub(crate) struct SimplifiedSwu {
+    pub field: PrimeField,
+    pub a: BigInt,
+    pub b: BigInt,
+    pub z: BigInt,
+}
+
+impl SimplifiedSwu {
+    pub fn map_to_curve(&self, u: &BigInt) -> (BigInt, BigInt) {
+        let f = &self.field;
+        let z_u2 = f.mul(&self.z, &f.mul(u, u));
+        let tv1 = f.inv0(&f.add(&f.mul(&z_u2, &z_u2), &z_u2));
+        let x1 = if tv1 == BigInt::zero() {
+            f.mul(&self.b, &f.inv0(&f.mul(&self.z, &self.a)))
+        } else {
+            let minus_b_div_a = f.mul(&f.neg(&self.b), &f.inv0(&self.a));
+            f.mul(&minus_b_div_a, &f.add(&BigInt::one(), &tv1))
+        };
+
+        let (x, y) = match f.sqrt(&self.curve_equation(&x1)) {
+            Some(y1) => (x1, y1),
+            None => {
+                let x2 = f.mul(&z_u2, &x1);
+                let y2 = f
+                    .sqrt(&self.curve_equation(&x2))
+                    .expect("gx2 is guaranteed to be square if gx1 is not");
+                (x2, y2)
+            }
+        };
+
+        if PrimeField::sgn0(u) != PrimeField::sgn0(&y) {
+            (x, f.neg(&y))
+        } else {
+            (x, y)
+        }
+    }
+
+    fn curve_equation(&self, x: &BigInt) -> BigInt {
+        let f = &self.field;
+        let x3 = f.mul(&f.mul(x, x), x);
+        f.add(&f.add(&x3, &f.mul(&self.a, x)), &self.b)
+    }
+}
+
+/// Rational map $(x, y) \mapsto (\frac{x_{num}(x)}{x_{den}(x)}, y \frac{y_{num}(x)}{y_{den}(x)})$
+///
+/// Polynomials coefficients are listed starting from the constant term.
+pub(crate) struct IsogenyMap {
+    pub x_num: Vec<BigInt>,
+    pub x_den: Vec<BigInt>,
+    pub y_num: Vec<BigInt>,
+    pub y_den: Vec<BigInt>,
+}
+
+impl IsogenyMap {
+    /// Applies the map. Returns `None` if the result is the point at infinity.
+    pub fn map(&self, f: &PrimeField, x: &BigInt, y: &BigInt) -> Option<(BigInt, BigInt)> {
+        let eval = |coefs: &[BigInt]| {
+            coefs
+                .iter()
+                .rev()
+                .fold(BigInt::zero(), |acc, c| f.add(&f.mul(&acc, x), c))
+        };
+        let x_den = eval(&self.x_den);
+        let y_den = eval(&self.y_den);
+        if x_den == BigInt::zero() || y_den == BigInt::zero() {
+            return None;
+        }
+        let x = f.mul(&eval(&self.x_num), &f.inv0(&x_den));
+        let y = f.mul(y, &f.mul(&eval(&self.y_num), &f.inv0(&y_den)));
+        Some((x, y))
+    }
+}
+
+/// Elligator 2 method mapping field elements to points of Montgomery curve
+/// $y^2 = x^3 + Jx^2 + x$ (ie. `K = 1`)
+///
+/// Implements `map_to_curve_elligator2` defined in [RFC 9380, section 6.7.1][ell2].
+///
+/// [ell2]: https://www.rfc-editor.org/rfc/rfc9380.html#name-elligator-2-method
+pub(crate) struct Elligator2 {
+    pub field: PrimeField,
+    pub j: BigInt,
+    pub z: BigInt,
+}
+
+impl Elligator2 {
+    pub fn map_to_curve(&self, u: &BigInt) -> (BigInt, BigInt) {
+        let f = &self.field;
+        let minus_j = f.neg(&self.j);
+        let mut x1 = f.mul(
+            &minus_j,
+            &f.inv0(&f.add(&BigInt::one(), &f.mul(&self.z, &f.mul(u, u)))),
+        );
+        if x1 == BigInt::zero() {
+            x1 = minus_j.clone();
+        }
+        let gx1 = self.curve_equation(&x1);
+
+        if f.is_square(&gx1) {
+            let y = f.sqrt(&gx1).expect("gx1 is square");
+            let y = if PrimeField::sgn0(&y) { y } else { f.neg(&y) };
+            (x1, y)
+        } else {
+            let x2 = f.sub(&minus_j, &x1);
+            let y = f
+                .sqrt(&self.curve_equation(&x2))
+                .expect("gx2 is guaranteed to be square if gx1 is not");
+            let y = if PrimeField::sgn0(&y) { f.neg(&y) } else { y };
+            (x2, y)
+        }
+    }
+
+    fn curve_equation(&self, x: &BigInt) -> BigInt {
+        let f = &self.field;
+        let x2 = f.mul(x, x);
+        let x3 = f.mul(&x2, x);
+        f.add(&f.add(&x3, &f.mul(&self.j, &x2)), x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Sha256, Sha512};
+
+    use crate::arithmetic::*;
+    use crate::cryptographic_primitives::hashing::hash_to_field;
+    use crate::elliptic::curves::*;
+    use crate::test_for_all_curves;
+
+    /// Messages used in RFC 9380 test vectors
+    fn test_messages() -> Vec<Vec<u8>> {
+        let q128 = [&b"q128_"[..], &[b'q'; 128]].concat();
+        let a512 = [&b"a512_"[..], &[b'a'; 512]].concat();
+        vec![
+            b"".to_vec(),
+            b"abc".to_vec(),
+            b"abcdef0123456789".to_vec(),
+            q128,
+            a512,
+        ]
+    }
+
+    fn test_dst<E: HashToCurve>() -> Vec<u8> {
+        format!("QUUX-V01-CS02-with-{}", E::SUITE_ID).into_bytes()
+    }
+
+    fn hex_to_bigint(hex: &str) -> BigInt {
+        BigInt::from_hex(hex).unwrap()
+    }
+
+    /// Test vector for curve of prime order: field elements `u` and resulting point `P = (x, y)`
+    struct Vector {
+        u: [&'static str; 2],
+        p: [&'static str; 2],
+    }
+
+    fn check_vectors<E: HashToCurve>(field_modulus: &str, vectors: &[Vector]) {
+        let dst = test_dst::<E>();
+        let p = hex_to_bigint(field_modulus);
+        for (msg, vector) in test_messages().iter().zip(vectors) {
+            let u = hash_to_field::<Sha256>(msg, &dst, &p, 2).unwrap();
+            assert_eq!(u[0], hex_to_bigint(vector.u[0]));
+            assert_eq!(u[1], hex_to_bigint(vector.u[1]));
+
+            let point = Point::<E>::hash_to_curve(msg, &dst);
+            let coords = point.coords().expect("point is not zero");
+            assert_eq!(coords.x, hex_to_bigint(vector.p[0]));
+            assert_eq!(coords.y, hex_to_bigint(vector.p[1]));
+        }
+    }
+
+    // Test vectors are taken from RFC 9380, Appendix J.8.1
+    #[test]
+    fn secp256k1_test_vectors() {
+        check_vectors::<Secp256k1>(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            &[
+                Vector {
+                    u: [
+                        "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3",
+                        "1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16",
+                    ],
+                    p: [
+                        "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+                        "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+                    ],
+                },
+                Vector {
+                    u: [
+                        "128aab5d3679a1f7601e3bdf94ced1f43e491f544767e18a4873f397b08a2b61",
+                        "5897b65da3b595a813d0fdcc75c895dc531be76a03518b044daaa0f2e4689e00",
+                    ],
+                    p: [
+                        "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+                        "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+                    ],
+                },
+                Vector {
+                    u: [
+                        "ea67a7c02f2cd5d8b87715c169d055a22520f74daeb080e6180958380e2f98b9",
+                        "7434d0d1a500d38380d1f9615c021857ac8d546925f5f2355319d823a478da18",
+                    ],
+                    p: [
+                        "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+                        "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+                    ],
+                },
+                Vector {
+                    u: [
+                        "eda89a5024fac0a8207a87e8cc4e85aa3bce10745d501a30deb87341b05bcdf5",
+                        "dfe78cd116818fc2c16f3837fedbe2639fab012c407eac9dfe9245bf650ac51d",
+                    ],
+                    p: [
+                        "e2167bc785333a37aa562f021f1e881defb853839babf52a7f72b102e41890e9",
+                        "f2401dd95cc35867ffed4f367cd564763719fbc6a53e969fb8496a1e6685d873",
+                    ],
+                },
+                Vector {
+                    u: [
+                        "8d862e7e7e23d7843fe16d811d46d7e6480127a6b78838c277bca17df6900e9f",
+                        "68071d2530f040f081ba818d3c7188a94c900586761e9115efa47ae9bd847938",
+                    ],
+                    p: [
+                        "e3c8d35aaaf0b9b647e88a0a0a7ee5d5bed5ad38238152e4e6fd8c1f8cb7c998",
+                        "8446eeb6181bf12f56a9d24e262221cc2f0c4725c7e3803024b5888ee5823aa6",
+                    ],
+                },
+            ],
+        )
+    }
+
+    // Test vectors are taken from RFC 9380, Appendix J.1.1
+    #[test]
+    fn p256_test_vectors() {
+        check_vectors::<Secp256r1>(
+            "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+            &[
+                Vector {
+                    u: [
+                        "ad5342c66a6dd0ff080df1da0ea1c04b96e0330dd89406465eeba11582515009",
+                        "8c0f1d43204bd6f6ea70ae8013070a1518b43873bcd850aafa0a9e220e2eea5a",
+                    ],
+                    p: [
+                        "2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+                        "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+                    ],
+                },
+                Vector {
+                    u: [
+                        "afe47f2ea2b10465cc26ac403194dfb68b7f5ee865cda61e9f3e07a537220af1",
+                        "379a27833b0bfe6f7bdca08e1e83c760bf9a338ab335542704edcd69ce9e46e0",
+                    ],
+                    p: [
+                        "0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f",
+                        "5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e",
+                    ],
+                },
+                Vector {
+                    u: [
+                        "0fad9d125a9477d55cf9357105b0eb3a5c4259809bf87180aa01d651f53d312c",
+                        "b68597377392cd3419d8fcc7d7660948c8403b19ea78bbca4b133c9d2196c0fb",
+                    ],
+                    p: [
+                        "65038ac8f2b1def042a5df0b33b1f4eca6bff7cb0f9c6c1526811864e544ed80",
+                        "cad44d40a656e7aff4002a8de287abc8ae0482b5ae825822bb870d6df9b56ca3",
+                    ],
+                },
+                Vector {
+                    u: [
+                        "3bbc30446f39a7befad080f4d5f32ed116b9534626993d2cc5033f6f8d805919",
+                        "76bb02db019ca9d3c1e02f0c17f8baf617bbdae5c393a81d9ce11e3be1bf1d33",
+                    ],
+                    p: [
+                        "4be61ee205094282ba8a2042bcb48d88dfbb609301c49aa8b078533dc65a0b5d",
+                        "98f8df449a072c4721d241a3b1236d3caccba603f916ca680f4539d2bfb3c29e",
+                    ],
+                },
+                Vector {
+                    u: [
+                        "4ebc95a6e839b1ae3c63b847798e85cb3c12d3817ec6ebc10af6ee51adb29fec",
+                        "4e21af88e22ea80156aff790750121035b3eefaa96b425a8716e0d20b4e269ee",
+                    ],
+                    p: [
+                        "457ae2981f70ca85d8e24c308b14db22f3e3862c5ea0f652ca38b5e49cd64bc5",
+                        "ecb9f0eadc9aeed232dabc53235368c1394c78de05dd96893eefa62b0f4757dc",
+                    ],
+                },
+            ],
+        )
+    }
+
+    /// Test vector for edwards25519: field elements `u` and points `Q0 = map_to_curve(u[0])`,
+    /// `Q1 = map_to_curve(u[1])`
+    struct EdwardsVector {
+        u: [&'static str; 2],
+        q0: [&'static str; 2],
+        q1: [&'static str; 2],
+    }
+
+    // Test vectors are taken from RFC 9380, Appendix J.5.1
+    #[test]
+    fn ed25519_test_vectors() {
+        use super::super::ed25519::map_to_curve;
+
+        let vectors = [
+            EdwardsVector {
+                u: [
+                    "03fef4813c8cb5f98c6eef88fae174e6e7d5380de2b007799ac7ee712d203f3a",
+                    "780bdddd137290c8f589dc687795aafae35f6b674668d92bf92ae793e6a60c75",
+                ],
+                q0: [
+                    "6549118f65bb617b9e8b438decedc73c496eaed496806d3b2eb9ee60b88e09a7",
+                    "7315bcc8cf47ed68048d22bad602c6680b3382a08c7c5d3f439a973fb4cf9feb",
+                ],
+                q1: [
+                    "31dcfc5c58aa1bee6e760bf78cbe71c2bead8cebb2e397ece0f37a3da19c9ed2",
+                    "7876d81474828d8a5928b50c82420b2bd0898d819e9550c5c82c39fc9bafa196",
+                ],
+            },
+            EdwardsVector {
+                u: [
+                    "5081955c4141e4e7d02ec0e36becffaa1934df4d7a270f70679c78f9bd57c227",
+                    "005bdc17a9b378b6272573a31b04361f21c371b256252ae5463119aa0b925b76",
+                ],
+                q0: [
+                    "5c1525bd5d4b4e034512949d187c39d48e8cd84242aa4758956e4adc7d445573",
+                    "2bf426cf7122d1a90abc7f2d108befc2ef415ce8c2d09695a7407240faa01f29",
+                ],
+                q1: [
+                    "37b03bba828860c6b459ddad476c83e0f9285787a269df2156219b7e5c86210c",
+                    "285ebf5412f84d0ad7bb4e136729a9ffd2195d5b8e73c0dc85110ce06958f432",
+                ],
+            },
+            EdwardsVector {
+                u: [
+                    "285ebaa3be701b79871bcb6e225ecc9b0b32dff2d60424b4c50642636a78d5b3",
+                    "2e253e6a0ef658fedb8e4bd6a62d1544fd6547922acb3598ec6b369760b81b31",
+                ],
+                q0: [
+                    "3ac463dd7fddb773b069c5b2b01c0f6b340638f54ee3bd92d452fcec3015b52d",
+                    "7b03ba1e8db9ec0b390d5c90168a6a0b7107156c994c674b61fe696cbeb46baf",
+                ],
+                q1: [
+                    "0757e7e904f5e86d2d2f4acf7e01c63827fde2d363985aa7432106f1b3a444ec",
+                    "50026c96930a24961e9d86aa91ea1465398ff8e42015e2ec1fa397d416f6a1c0",
+                ],
+            },
+            EdwardsVector {
+                u: [
+                    "4fedd25431c41f2a606952e2945ef5e3ac905a42cf64b8b4d4a83c533bf321af",
+                    "02f20716a5801b843987097a8276b6d869295b2e11253751ca72c109d37485a9",
+                ],
+                q0: [
+                    "703e69787ea7524541933edf41f94010a201cc841c1cce60205ec38513458872",
+                    "32bb192c4f89106466f0874f5fd56a0d6b6f101cb714777983336c159a9bec75",
+                ],
+                q1: [
+                    "0c9077c5c31720ed9413abe59bf49ce768506128d810cb882435aa90f713ef6b",
+                    "7d5aec5210db638c53f050597964b74d6dda4be5b54fa73041bf909ccb3826cb",
+                ],
+            },
+            EdwardsVector {
+                u: [
+                    "6e34e04a5106e9bd59f64aba49601bf09d23b27f7b594e56d5de06df4a4ea33b",
+                    "1c1c2cb59fc053f44b86c5d5eb8c1954b64976d0302d3729ff66e84068f5fd96",
+                ],
+                q0: [
+                    "21091b2e3f9258c7dfa075e7ae513325a94a3d8a28e1b1cb3b5b6f5d65675592",
+                    "41a33d324c89f570e0682cdf7bdb78852295daf8084c669f2cc9692896ab5026",
+                ],
+                q1: [
+                    "4c07ec48c373e39a23bd7954f9e9b66eeab9e5ee1279b867b3d5315aa815454f",
+                    "67ccac7c3cb8d1381242d8d6585c57eabaddbb5dca5243a68a8aeb5477d94b3a",
+                ],
+            },
+        ];
+
+        let dst = test_dst::<Ed25519>();
+        let p = BigInt::from(2).pow(255) - BigInt::from(19);
+        let eight = <Ed25519 as Curve>::Scalar::from_bigint(&BigInt::from(8));
+        for (msg, vector) in test_messages().iter().zip(&vectors) {
+            let u = hash_to_field::<Sha512>(msg, &dst, &p, 2).unwrap();
+            assert_eq!(u[0], hex_to_bigint(vector.u[0]));
+            assert_eq!(u[1], hex_to_bigint(vector.u[1]));
+
+            let q0 = map_to_curve(&u[0]);
+            let q1 = map_to_curve(&u[1]);
+            assert_eq!(q0.x_coord(), Some(hex_to_bigint(vector.q0[0])));
+            assert_eq!(q0.y_coord(), Some(hex_to_bigint(vector.q0[1])));
+            assert_eq!(q1.x_coord(), Some(hex_to_bigint(vector.q1[0])));
+            assert_eq!(q1.y_coord(), Some(hex_to_bigint(vector.q1[1])));
+
+            let expected = q0.add_point(&q1).scalar_mul(&eight);
+            assert_eq!(
+                Point::<Ed25519>::hash_to_curve(msg, &dst).as_raw(),
+                &expected
+            );
+        }
+    }
+
+    fn check_bls12_381_vectors<E: HashToCurve>(expected_uncompressed: &[&str]) {
+        let dst = test_dst::<E>();
+        for (msg, expected) in test_messages().iter().zip(expected_uncompressed) {
+            let point = Point::<E>::hash_to_curve(msg, &dst);
+            assert_eq!(hex::encode(&*point.to_bytes(false)), *expected);
+        }
+    }
+
+    // Test vectors are taken from RFC 9380, Appendix J.9.1
+    #[test]
+    fn bls12_381_1_test_vectors() {
+        check_bls12_381_vectors::<Bls12_381_1>(&[
+            "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a108ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+            "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f69030b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+            "11e0b079dea29a68f0383ee94fed1b940995272407e3bb916bbf268c263ddd57a6a27200a784cbc248e84f357ce82d9803a87ae2caf14e8ee52e51fa2ed8eefe80f02457004ba4d486d6aa1f517c0889501dc7413753f9599b099ebcbbd2d709",
+            "15f68eaa693b95ccb85215dc65fa81038d69629f70aeee0d0f677cf22285e7bf58d7cb86eefe8f2e9bc3f8cb84fac4881807a1d50c29f430b8cafc4f8638dfeeadf51211e1602a5f184443076715f91bb90a48ba1e370edce6ae1062f5e6dd38",
+            "082aabae8b7dedb0e78aeb619ad3bfd9277a2f77ba7fad20ef6aabdc6c31d19ba5a6d12283553294c1825c4b3ca2dcfe05b84ae5a942248eea39e1d91030458c40153f3b654ab7872d779ad1e942856a20c438e8d99bc8abfbf74729ce1f7ac8",
+        ])
+    }
+
+    // Test vectors are taken from RFC 9380, Appendix J.10.1
+    #[test]
+    fn bls12_381_2_test_vectors() {
+        check_bls12_381_vectors::<Bls12_381_2>(&[
+            "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d60503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+            "139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd802c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e600aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd161787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48",
+            "190d119345b94fbd15497bcba94ecf7db2cbfd1e1fe7da034d26cbba169fb3968288b3fafb265f9ebd380512a71c3f2c121982811d2491fde9ba7ed31ef9ca474f0e1501297f68c298e9f4c0028add35aea8bb83d53c08cfc007c1e005723cd00bb5e7572275c567462d91807de765611490205a941a5a6af3b1691bfe596c31225d3aabdf15faff860cb4ef17c7c3be05571a0f8d3c08d094576981f4a3b8eda0a8e771fcdcc8ecceaf1356a6acf17574518acb506e435b639353c2e14827c8",
+            "0934aba516a52d8ae479939a91998299c76d39cc0c035cd18813bec433f587e2d7a4fef038260eef0cef4d02aae3eb9119a84dd7248a1066f737cc34502ee5555bd3c19f2ecdb3c7d9e24dc65d4e25e50d83f0f77105e955d78f4762d33c17da09bcccfa036b4847c9950780733633f13619994394c23ff0b32fa6b795844f4a0673e20282d07bc69641cee04f5e566214f81cd421617428bc3b9fe25afbb751d934a00493524bc4e065635b0555084dd54679df1536101b2c979c0152d09192",
+            "11fca2ff525572795a801eed17eb12785887c7b63fb77a42be46ce4a34131d71f7a73e95fee3f812aea3de78b4d0156901a6ba2f9a11fa5598b2d8ace0fbe0a0eacb65deceb476fbbcb64fd24557c2f4b18ecfc5663e54ae16a84f5ab7f6253403a47f8e6d1763ba0cad63d6114c0accbef65707825a511b251a660a9b3994249ae4e63fac38b23da0c398689ee2ab520b6798718c8aed24bc19cb27f866f1c9effcdbf92397ad6448b5c9db90d2b9da6cbabf48adc1adf59a1a28344e79d57e",
+        ])
+    }
+
+    // RFC 9380 doesn't list test vectors for `ristretto255_XMD:SHA-512_R255MAP_RO_` suite defined
+    // in Appendix B. Expected encodings are computed by an independent implementation of
+    // `hash_to_ristretto255` (`expand_message_xmd` with SHA-512 followed by element derivation
+    // from RFC 9496, Section 4.3.4), checked against vectors from RFC 9380, Appendix K.3 and
+    // RFC 9496, Appendix A.3
+    #[test]
+    fn ristretto255_test_vectors() {
+        let expected = [
+            "bed61e1ee1966329962880e236dfdc83afd52fd1ce116f64fb806f1e8acea926",
+            "627b997b104ee62543358e22576c75a98dff9dc5f348d5ab228689735d77b258",
+            "90348aa2cced1007a4cd1b4cef9c1105d09a4b491766dad0de7f6ea39423ea32",
+            "a83367182a9928a7188576376291816ccab9e8293007401f3db8f1cbf1fc6934",
+            "eacd8dcc6376d75f11c2e8126385bfb9aecd91b8482b6226835c097a6b503d23",
+        ];
+        let dst = test_dst::<Ristretto>();
+        for (msg, expected) in test_messages().iter().zip(expected.iter()) {
+            let point = Point::<Ristretto>::hash_to_curve(msg, &dst);
+            assert_eq!(hex::encode(&*point.to_bytes(true)), *expected);
+        }
+    }
+
+    test_for_all_curves!(hash_to_curve_outputs_point_of_group_order);
+    fn hash_to_curve_outputs_point_of_group_order<E: HashToCurve>() {
+        for msg in test_messages() {
+            let point = Point::<E>::hash_to_curve(&msg, b"TEST-DST");
+            assert!(point.as_raw().check_point_order_equals_group_order());
+        }
+    }
+
+    test_for_all_curves!(hash_to_curve_is_domain_separated);
+    fn hash_to_curve_is_domain_separated<E: HashToCurve>() {
+        let point = Point::<E>::hash_to_curve(b"message", b"TEST-DST-1");
+        assert_eq!(point, Point::hash_to_curve(b"message", b"TEST-DST-1"));
+        assert_ne!(point, Point::hash_to_curve(b"message", b"TEST-DST-2"));
+        assert_ne!(
+            point,
+            Point::hash_to_curve(b"another message", b"TEST-DST-1")
+        );
+
+        // Tags longer than 255 bytes are hashed
+        let long_dst = [b'D'; 256];
+        let point = Point::<E>::hash_to_curve(b"message", &long_dst);
+        assert_eq!(point, Point::hash_to_curve(b"message", &long_dst));
+        assert_ne!(point, Point::hash_to_curve(b"message", &long_dst[..255]));
+    }
+}
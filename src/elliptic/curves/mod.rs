@@ -82,6 +82,8 @@ pub mod ed25519;
 pub mod p256;
 pub mod secp256_k1;
 
+mod hash_to_curve;
+mod multiscalar;
 #[cfg(test)]
 mod test;
 mod traits;
@@ -96,7 +98,7 @@ pub use self::{
     secp256_k1::Secp256k1,
 };
 pub use self::{
-    traits::{Curve, ECPoint, ECScalar, PointCoords},
+    traits::{Curve, ECPoint, ECScalar, HashToCurve, PointCoords},
     wrappers::{EncodedPoint, EncodedScalar, Generator, Point, Scalar},
 };
 
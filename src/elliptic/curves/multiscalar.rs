@@ -0,0 +1,174 @@
+//! Generic multiscalar multiplication algorithms
+//!
+//! These algorithms are built on top of point addition only, so they work for any curve.
+//! They are used as a default implementation of [ECPoint::multiscalar_mul] for curves that don't
+//! provide native implementation. Curves which point addition is costly (e.g. goes through affine
+//! coordinates) may run them over another point representation, see [GroupElement].
+
+use crate::arithmetic::*;
+
+use super::traits::{ECPoint, ECScalar};
+
+/// Group operations required by multiscalar multiplication algorithms
+///
+/// Implemented for every [ECPoint]. Curve implementation may also implement it for a point
+/// representation of a third-party library that has cheaper addition (e.g. projective
+/// coordinates), and run algorithms over it.
+pub trait GroupElement: Clone {
+    /// Neutral element
+    fn identity() -> Self;
+    /// Checks whether element is neutral
+    fn is_identity(&self) -> bool;
+    /// Adds `other` to `self`
+    fn add_assign(&mut self, other: &Self);
+    /// Doubles `self`
+    fn double_assign(&mut self) {
+        let copy = self.clone();
+        self.add_assign(&copy)
+    }
+}
+
+impl<P: ECPoint> GroupElement for P {
+    fn identity() -> Self {
+        ECPoint::zero()
+    }
+    fn is_identity(&self) -> bool {
+        ECPoint::is_zero(self)
+    }
+    fn add_assign(&mut self, other: &Self) {
+        ECPoint::add_point_assign(self, other)
+    }
+}
+
+/// Number of terms starting from which Pippenger's algorithm outperforms Straus
+const PIPPENGER_THRESHOLD: usize = 128;
+/// Window size used by Straus algorithm
+const STRAUS_WINDOW: usize = 4;
+
+/// Computes `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]`
+///
+/// Chooses between [straus] and [pippenger] algorithms depending on number of terms.
+///
+/// ## Panics
+/// Panics if `scalars.len() != points.len()`
+pub fn multiscalar_mul<S: ECScalar, P: GroupElement>(scalars: &[S], points: &[P]) -> P {
+    assert_eq!(
+        scalars.len(),
+        points.len(),
+        "scalars and points must have the same length"
+    );
+    if scalars.len() < PIPPENGER_THRESHOLD {
+        straus(scalars, points)
+    } else {
+        pippenger(scalars, points)
+    }
+}
+
+/// Straus (aka interleaved window) multiscalar multiplication
+///
+/// Precomputes `[P, 2P, ..., (2^w-1)P]` for every point, then processes all the scalars at once
+/// sharing doublings between them.
+pub fn straus<S: ECScalar, P: GroupElement>(scalars: &[S], points: &[P]) -> P {
+    assert_eq!(
+        scalars.len(),
+        points.len(),
+        "scalars and points must have the same length"
+    );
+    let tables: Vec<Vec<P>> = points
+        .iter()
+        .map(|point| {
+            let mut table = Vec::with_capacity((1 << STRAUS_WINDOW) - 1);
+            table.push(point.clone());
+            for i in 1..(1 << STRAUS_WINDOW) - 1 {
+                let mut next = table[i - 1].clone();
+                next.add_assign(point);
+                table.push(next);
+            }
+            table
+        })
+        .collect();
+    let digits: Vec<Vec<usize>> = scalars
+        .iter()
+        .map(|s| scalar_digits(s, STRAUS_WINDOW))
+        .collect();
+
+    let mut result = P::identity();
+    for window in (0..windows_count::<S>(STRAUS_WINDOW)).rev() {
+        double_n_times(&mut result, STRAUS_WINDOW);
+        for (table, digits) in tables.iter().zip(&digits) {
+            let digit = digits[window];
+            if digit != 0 {
+                result.add_assign(&table[digit - 1])
+            }
+        }
+    }
+    result
+}
+
+/// Pippenger (aka bucket method) multiscalar multiplication
+///
+/// For each window, points are sorted into buckets by corresponding scalar digit. Buckets sums
+/// are then combined using running sum technique.
+pub fn pippenger<S: ECScalar, P: GroupElement>(scalars: &[S], points: &[P]) -> P {
+    assert_eq!(
+        scalars.len(),
+        points.len(),
+        "scalars and points must have the same length"
+    );
+    let window = match scalars.len() {
+        n if n < 256 => 5,
+        n if n < 500 => 6,
+        n if n < 800 => 7,
+        _ => 8,
+    };
+    let digits: Vec<Vec<usize>> = scalars.iter().map(|s| scalar_digits(s, window)).collect();
+
+    let mut result = P::identity();
+    for w in (0..windows_count::<S>(window)).rev() {
+        double_n_times(&mut result, window);
+
+        let mut buckets = vec![P::identity(); (1 << window) - 1];
+        for (digits, point) in digits.iter().zip(points) {
+            let digit = digits[w];
+            if digit != 0 {
+                buckets[digit - 1].add_assign(point)
+            }
+        }
+
+        // window_sum = 1*buckets[0] + 2*buckets[1] + ... + (2^c-1)*buckets[2^c-2]
+        let mut running_sum = P::identity();
+        let mut window_sum = P::identity();
+        for bucket in buckets.iter().rev() {
+            running_sum.add_assign(bucket);
+            window_sum.add_assign(&running_sum);
+        }
+        result.add_assign(&window_sum);
+    }
+    result
+}
+
+fn windows_count<S: ECScalar>(window: usize) -> usize {
+    let bits = S::group_order().bit_length();
+    bits.div_ceil(window)
+}
+
+/// Splits a scalar into `window`-bits digits, least significant digit goes first
+fn scalar_digits<S: ECScalar>(scalar: &S, window: usize) -> Vec<usize> {
+    let n = scalar.to_bigint();
+    (0..windows_count::<S>(window))
+        .map(|w| {
+            (0..window)
+                .filter(|i| n.test_bit(w * window + i))
+                .fold(0, |digit, i| digit | (1 << i))
+        })
+        .collect()
+}
+
+fn double_n_times<P: GroupElement>(point: &mut P, n: usize) {
+    if point.is_identity() {
+        return;
+    }
+    for _ in 0..n {
+        point.double_assign()
+    }
+}
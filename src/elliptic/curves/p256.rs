@@ -4,7 +4,7 @@ use std::convert::TryFrom;
 
 use p256::elliptic_curve::group::ff::PrimeField;
 use p256::elliptic_curve::group::prime::PrimeCurveAffine;
-use p256::elliptic_curve::ops::Reduce;
+use p256::elliptic_curve::ops::{LinearCombination, Reduce};
 use p256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
 use p256::elliptic_curve::Field;
 use p256::{AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar};
@@ -12,10 +12,13 @@ use p256::{AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar};
 use generic_array::GenericArray;
 use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use zeroize::Zeroize;
 
-use super::traits::{ECPoint, ECScalar};
+use super::hash_to_curve::{self, SimplifiedSwu};
+use super::traits::{ECPoint, ECScalar, HashToCurve};
 use crate::arithmetic::traits::*;
+use crate::cryptographic_primitives::hashing::hash_to_field;
 use crate::elliptic::curves::{Curve, DeserializationError, NotOnCurve, PointCoords};
 use crate::BigInt;
 
@@ -39,6 +42,19 @@ lazy_static::lazy_static! {
         purpose: "generator",
         ge: AffinePoint::generator()
     };
+
+    /// Simplified SWU map to P-256 curve (RFC 9380, section 8.2)
+    static ref SSWU: SimplifiedSwu = {
+        let p = BigInt::from_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff")
+            .expect("invalid hex constant");
+        SimplifiedSwu {
+            a: &p - BigInt::from(3),
+            b: BigInt::from_hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b")
+                .expect("invalid hex constant"),
+            z: &p - BigInt::from(10),
+            field: hash_to_curve::PrimeField::new(p),
+        }
+    };
 }
 
 /* X coordinate of a point of unknown discrete logarithm.
@@ -88,6 +104,24 @@ impl Curve for Secp256r1 {
     const CURVE_NAME: &'static str = "secp256r1";
 }
 
+impl HashToCurve for Secp256r1 {
+    const SUITE_ID: &'static str = "P256_XMD:SHA-256_SSWU_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Secp256r1Point {
+        let u = hash_to_field::<Sha256>(msg, dst, SSWU.field.modulus(), 2)
+            .expect("output length is valid for expand_message_xmd");
+        let map_to_curve = |u: &BigInt| {
+            let (x, y) = SSWU.map_to_curve(u);
+            Secp256r1Point::from_coords(&x, &y).expect("point must be on curve")
+        };
+        // P-256 has cofactor 1, no need to clear it
+        Secp256r1Point {
+            purpose: "hash_to_curve",
+            ..map_to_curve(&u[0]).add_point(&map_to_curve(&u[1]))
+        }
+    }
+}
+
 impl ECScalar for Secp256r1Scalar {
     type Underlying = SK;
 
@@ -364,6 +398,35 @@ impl ECPoint for Secp256r1Point {
         }
     }
 
+    fn multiscalar_mul(scalars: &[Self::Scalar], points: &[Self]) -> Self {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "scalars and points must have the same length"
+        );
+        // Terms are processed in pairs via `lincomb`, accumulator is kept in projective form
+        // to avoid converting to affine after each addition
+        let result =
+            scalars
+                .chunks(2)
+                .zip(points.chunks(2))
+                .fold(ProjectivePoint::IDENTITY, |acc, term| match term {
+                    ([k, l], [x, y]) => {
+                        acc + ProjectivePoint::lincomb(
+                            &ProjectivePoint::from(x.ge),
+                            &k.fe,
+                            &ProjectivePoint::from(y.ge),
+                            &l.fe,
+                        )
+                    }
+                    (k, x) => acc + ProjectivePoint::from(x[0].ge) * *k[0].fe,
+                });
+        Secp256r1Point {
+            purpose: "multiscalar_mul",
+            ge: result.to_affine(),
+        }
+    }
+
     /// Reference to underlying curve implementation
     fn underlying_ref(&self) -> &Self::Underlying {
         &self.ge
@@ -22,15 +22,21 @@ use std::ptr;
 use std::sync::atomic;
 
 use generic_array::GenericArray;
+use k256::elliptic_curve::group::Group;
+use k256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
 use secp256k1::constants::{
     self, GENERATOR_X, GENERATOR_Y, SECRET_KEY_SIZE, UNCOMPRESSED_PUBLIC_KEY_SIZE,
 };
 use secp256k1::{PublicKey, SecretKey, SECP256K1};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use zeroize::{Zeroize, Zeroizing};
 
 use crate::arithmetic::*;
+use crate::cryptographic_primitives::hashing::hash_to_field;
 
+use super::hash_to_curve::{IsogenyMap, PrimeField, SimplifiedSwu};
+use super::multiscalar::GroupElement;
 use super::traits::*;
 
 lazy_static::lazy_static! {
@@ -61,6 +67,47 @@ lazy_static::lazy_static! {
         purpose: "base_point2",
         ge: Some(PK(PublicKey::from_slice(&BASE_POINT2_UNCOMPRESSED[..]).unwrap())),
     };
+
+    /// Simplified SWU map to curve `E'` 3-isogenous to secp256k1 (RFC 9380, section 8.7)
+    static ref SSWU: SimplifiedSwu = SimplifiedSwu {
+        field: PrimeField::new(hex_constant(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+        )),
+        a: hex_constant("3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533"),
+        b: BigInt::from(1771),
+        z: hex_constant("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc24"),
+    };
+
+    /// 3-isogeny map from `E'` to secp256k1 (RFC 9380, Appendix E.1)
+    static ref ISOGENY: IsogenyMap = IsogenyMap {
+        x_num: vec![
+            hex_constant("8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7"),
+            hex_constant("07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581"),
+            hex_constant("534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262"),
+            hex_constant("8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c"),
+        ],
+        x_den: vec![
+            hex_constant("d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b"),
+            hex_constant("edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14"),
+            BigInt::one(),
+        ],
+        y_num: vec![
+            hex_constant("4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c"),
+            hex_constant("c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3"),
+            hex_constant("29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931"),
+            hex_constant("2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84"),
+        ],
+        y_den: vec![
+            hex_constant("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b"),
+            hex_constant("7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573"),
+            hex_constant("6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f"),
+            BigInt::one(),
+        ],
+    };
+}
+
+fn hex_constant(hex: &str) -> BigInt {
+    BigInt::from_hex(hex).expect("invalid hex constant")
 }
 
 /* X coordinate of a point of unknown discrete logarithm.
@@ -136,6 +183,31 @@ impl Curve for Secp256k1 {
     const CURVE_NAME: &'static str = "secp256k1";
 }
 
+impl HashToCurve for Secp256k1 {
+    const SUITE_ID: &'static str = "secp256k1_XMD:SHA-256_SSWU_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Secp256k1Point {
+        let u = hash_to_field::<Sha256>(msg, dst, SSWU.field.modulus(), 2)
+            .expect("output length is valid for expand_message_xmd");
+        let q0 = map_to_curve(&u[0]);
+        let q1 = map_to_curve(&u[1]);
+        // secp256k1 has cofactor 1, no need to clear it
+        Secp256k1Point {
+            purpose: "hash_to_curve",
+            ..q0.add_point(&q1)
+        }
+    }
+}
+
+/// Maps field element to secp256k1 point via simplified SWU and 3-isogeny
+fn map_to_curve(u: &BigInt) -> Secp256k1Point {
+    let (x, y) = SSWU.map_to_curve(u);
+    match ISOGENY.map(&SSWU.field, &x, &y) {
+        Some((x, y)) => Secp256k1Point::from_coords(&x, &y).expect("point must be on curve"),
+        None => Secp256k1Point::zero(),
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Secp256k1Scalar {
     #[allow(dead_code)]
@@ -477,6 +549,44 @@ impl ECPoint for Secp256k1Point {
         Secp256k1Point { purpose: "neg", ge }
     }
 
+    fn multiscalar_mul(scalars: &[Self::Scalar], points: &[Self]) -> Secp256k1Point {
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "scalars and points must have the same length"
+        );
+        // Underlying library exposes neither multiscalar multiplication nor point addition in
+        // projective coordinates (every addition costs a field inversion), so we run generic
+        // Straus/Pippenger over k256 projective points instead
+        let points = points
+            .iter()
+            .map(|p| match &p.ge {
+                Some(ge) => {
+                    let encoded = k256::EncodedPoint::from_bytes(&ge.serialize_uncompressed()[..])
+                        .expect("valid uncompressed point");
+                    // Can't fail as point is on the curve
+                    k256::ProjectivePoint::from(
+                        k256::AffinePoint::from_encoded_point(&encoded).unwrap(),
+                    )
+                }
+                None => k256::ProjectivePoint::IDENTITY,
+            })
+            .collect::<Vec<_>>();
+        let result = super::multiscalar::multiscalar_mul(scalars, &points);
+        let ge = if GroupElement::is_identity(&result) {
+            None
+        } else {
+            let encoded = result.to_affine().to_encoded_point(false);
+            Some(PK(
+                PublicKey::from_slice(encoded.as_bytes()).expect("point is on the curve")
+            ))
+        };
+        Secp256k1Point {
+            purpose: "multiscalar_mul",
+            ge,
+        }
+    }
+
     fn scalar_mul_assign(&mut self, scalar: &Self::Scalar) {
         match (&mut self.ge, &*scalar.fe) {
             (None, _) | (_, None) => {
@@ -504,6 +614,21 @@ impl ECPoint for Secp256k1Point {
     }
 }
 
+impl GroupElement for k256::ProjectivePoint {
+    fn identity() -> Self {
+        k256::ProjectivePoint::IDENTITY
+    }
+    fn is_identity(&self) -> bool {
+        bool::from(Group::is_identity(self))
+    }
+    fn add_assign(&mut self, other: &Self) {
+        *self += other
+    }
+    fn double_assign(&mut self) {
+        *self = Group::double(self)
+    }
+}
+
 impl PartialEq for Secp256k1Point {
     fn eq(&self, other: &Secp256k1Point) -> bool {
         self.underlying_ref() == other.underlying_ref()
@@ -358,3 +358,54 @@ fn scalar_assign_negation<E: Curve>() {
     };
     assert_eq!(s_neg_1, s_neg_2);
 }
+
+fn naive_multiscalar_mul<P: ECPoint>(scalars: &[P::Scalar], points: &[P]) -> P {
+    scalars
+        .iter()
+        .zip(points)
+        .fold(P::zero(), |acc, (s, p)| acc.add_point(&p.scalar_mul(s)))
+}
+
+test_for_all_curves!(multiscalar_mul_matches_naive_sum);
+fn multiscalar_mul_matches_naive_sum<E: Curve>() {
+    for &n in &[0, 1, 2, 5, 33, 40] {
+        let mut scalars: Vec<E::Scalar> =
+            iter::repeat_with(random_nonzero_scalar).take(n).collect();
+        let mut points: Vec<E::Point> =
+            iter::repeat_with(|| E::Point::generator().scalar_mul(&random_nonzero_scalar()))
+                .take(n)
+                .collect();
+        if n > 2 {
+            scalars[0] = E::Scalar::zero();
+            points[1] = E::Point::zero();
+        }
+
+        let expected = naive_multiscalar_mul(&scalars, &points);
+        assert_eq!(
+            E::Point::multiscalar_mul(&scalars, &points),
+            expected,
+            "n = {}",
+            n
+        );
+        assert_eq!(
+            super::multiscalar::straus(&scalars, &points),
+            expected,
+            "straus, n = {}",
+            n
+        );
+        assert_eq!(
+            super::multiscalar::pippenger(&scalars, &points),
+            expected,
+            "pippenger, n = {}",
+            n
+        );
+    }
+}
+
+test_for_all_curves!(multiscalar_mul_of_opposite_terms_is_zero);
+fn multiscalar_mul_of_opposite_terms_is_zero<E: Curve>() {
+    let s: E::Scalar = random_nonzero_scalar();
+    let p = E::Point::generator().scalar_mul(&random_nonzero_scalar());
+    let result = E::Point::multiscalar_mul(&[s.clone(), s.neg()], &[p.clone(), p]);
+    assert!(result.is_zero());
+}
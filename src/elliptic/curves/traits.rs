@@ -25,6 +25,23 @@ pub trait Curve: PartialEq + Clone + fmt::Debug + Sync + Send + 'static {
     const CURVE_NAME: &'static str;
 }
 
+/// Curve supporting hashing to its points as defined in [RFC 9380]
+///
+/// ## Note
+/// This is a low-level trait, you should not use it directly. See [Point::hash_to_curve].
+///
+/// [RFC 9380]: https://www.rfc-editor.org/rfc/rfc9380.html
+/// [Point::hash_to_curve]: super::wrappers::Point::hash_to_curve
+pub trait HashToCurve: Curve {
+    /// Identifier of implemented hash-to-curve suite, e.g. `secp256k1_XMD:SHA-256_SSWU_RO_`
+    const SUITE_ID: &'static str;
+
+    /// Hashes a message to the curve point using domain separation tag `dst`
+    ///
+    /// Resulting point must be either a zero point or a point of [group order](ECScalar::group_order).
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Self::Point;
+}
+
 /// Scalar value modulus [group order](Self::group_order)
 ///
 /// ## Note
@@ -209,6 +226,17 @@ pub trait ECPoint: Zeroize + Clone + PartialEq + fmt::Debug + Sync + Send + 'sta
     fn sub_point(&self, other: &Self) -> Self;
     /// Negates point
     fn neg_point(&self) -> Self;
+    /// Computes multiscalar multiplication `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]`
+    ///
+    /// Default implementation uses generic Straus/Pippenger algorithms built on top of point
+    /// addition. Curves should override it if underlying library provides native implementation.
+    /// Implementation is not required to be constant time.
+    ///
+    /// ## Panics
+    /// Panics if `scalars.len() != points.len()`
+    fn multiscalar_mul(scalars: &[Self::Scalar], points: &[Self]) -> Self {
+        super::multiscalar::multiscalar_mul(scalars, points)
+    }
 
     /// Multiplies the point at scalar value, assigns result to `self`
     fn scalar_mul_assign(&mut self, scalar: &Self::Scalar) {
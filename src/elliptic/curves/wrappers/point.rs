@@ -5,7 +5,7 @@ use crate::BigInt;
 
 use super::{
     error::{MismatchedPointOrder, PointFromBytesError, PointFromCoordsError, ZeroPointError},
-    EncodedPoint, Generator,
+    EncodedPoint, Generator, Scalar,
 };
 use crate::elliptic::curves::wrappers::encoded_point::EncodedPointChoice;
 
@@ -161,6 +161,50 @@ impl<E: Curve> Point<E> {
         }
     }
 
+    /// Computes multiscalar multiplication $s_1 P_1 + \dots{} + s_n P_n$
+    ///
+    /// Uses native implementation if underlying curve library provides it, otherwise falls back
+    /// to generic Straus/Pippenger algorithm. Speed-up over computing each term separately grows
+    /// with number of terms.
+    ///
+    /// Note that computation is not guaranteed to be constant time, so don't use it with secret
+    /// scalars.
+    ///
+    /// ## Panics
+    /// Panics if `scalars` and `points` have different lengths.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use curv::elliptic::curves::{Point, Scalar, Secp256k1};
+    /// let scalars = [Scalar::<Secp256k1>::random(), Scalar::random()];
+    /// let points = [Point::generator().to_point(), Point::base_point2().clone()];
+    ///
+    /// let sum = Point::multiscalar_mul(&scalars, &points);
+    /// assert_eq!(sum, &points[0] * &scalars[0] + &points[1] * &scalars[1]);
+    /// ```
+    pub fn multiscalar_mul<'s, 'p>(
+        scalars: impl IntoIterator<Item = &'s Scalar<E>>,
+        points: impl IntoIterator<Item = &'p Point<E>>,
+    ) -> Self {
+        let scalars = scalars
+            .into_iter()
+            .map(|s| s.as_raw().clone())
+            .collect::<Vec<_>>();
+        let points = points
+            .into_iter()
+            .map(|p| p.as_raw().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            scalars.len(),
+            points.len(),
+            "scalars and points must have the same length"
+        );
+        let result = E::Point::multiscalar_mul(&scalars, &points);
+        // Safety: linear combination of points of group order is either a zero point or a point
+        // of group order
+        unsafe { Self::from_raw_unchecked(result) }
+    }
+
     /// Constructs a `Point<E>` from low-level [ECPoint] implementor
     ///
     /// Returns error if point is not valid. Valid point is either a zero point, or a point of
@@ -264,6 +308,33 @@ impl<E: Curve> Point<E> {
     }
 }
 
+impl<E: HashToCurve> Point<E> {
+    /// Hashes a message to the curve point
+    ///
+    /// Implements hash-to-curve suite defined in [RFC 9380] (see [HashToCurve::SUITE_ID] for
+    /// exact suite used by the curve). Resulting point has unknown discrete logarithm.
+    ///
+    /// `dst` is a domain separation tag which must be unique for every protocol and every its
+    /// usage of hash-to-curve, e.g. `b"MY-PROTOCOL-V1-CS01-with-secp256k1_XMD:SHA-256_SSWU_RO_"`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use curv::elliptic::curves::{Point, Secp256k1};
+    /// let dst = b"MY-PROTOCOL-V1-CS01-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+    /// let point = Point::<Secp256k1>::hash_to_curve(b"message", dst);
+    /// assert_eq!(point, Point::hash_to_curve(b"message", dst));
+    /// assert_ne!(point, Point::hash_to_curve(b"another message", dst));
+    /// ```
+    ///
+    /// [RFC 9380]: https://www.rfc-editor.org/rfc/rfc9380.html
+    pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Self {
+        let point = E::hash_to_curve(msg, dst);
+        // Safety: HashToCurve implementations clear the cofactor, so resulting point is either
+        // a zero point or a point of group order
+        unsafe { Self::from_raw_unchecked(point) }
+    }
+}
+
 impl<E: Curve> PartialEq for Point<E> {
     fn eq(&self, other: &Self) -> bool {
         self.raw_point.eq(&other.raw_point)
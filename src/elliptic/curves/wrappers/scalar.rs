@@ -1,5 +1,8 @@
 use std::{fmt, iter};
 
+use digest::{BlockInput, Digest};
+
+use crate::cryptographic_primitives::hashing::hash_to_field;
 use crate::elliptic::curves::traits::{Curve, ECScalar};
 use crate::elliptic::curves::wrappers::encoded_scalar::EncodedScalar;
 use crate::elliptic::curves::{DeserializationError, ZeroScalarError};
@@ -87,6 +90,30 @@ impl<E: Curve> Scalar<E> {
         ECScalar::deserialize(bytes).map(Self::from_raw)
     }
 
+    /// Hashes a message to the scalar using domain separation tag `dst`
+    ///
+    /// Implements `hash_to_field` defined in [RFC 9380] targeting group order modulus (also known
+    /// as `hash_to_scalar`): message is expanded via `expand_message_xmd` into `L` bytes (e.g.
+    /// `L = 48` for 256 bits group order) that are reduced modulo group order. Resulting scalar is
+    /// indistinguishable from uniformly distributed one. Note that it might be zero (with
+    /// negligible probability).
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use curv::elliptic::curves::{Scalar, Secp256k1};
+    /// use sha2::Sha256;
+    ///
+    /// let challenge = Scalar::<Secp256k1>::hash_from_bytes::<Sha256>(b"MY-PROTOCOL-V1-challenge", b"message");
+    /// assert_ne!(challenge, Scalar::hash_from_bytes::<Sha256>(b"MY-PROTOCOL-V1-nonce", b"message"));
+    /// ```
+    ///
+    /// [RFC 9380]: https://www.rfc-editor.org/rfc/rfc9380.html#name-hash_to_field-implementatio
+    pub fn hash_from_bytes<H: Digest + BlockInput>(dst: &[u8], msg: &[u8]) -> Self {
+        let scalars = hash_to_field::<H>(msg, dst, Self::group_order(), 1)
+            .expect("output length is valid for expand_message_xmd");
+        Self::from_bigint(&scalars[0])
+    }
+
     /// Returns an order of generator point
     pub fn group_order() -> &'static BigInt {
         E::Scalar::group_order()